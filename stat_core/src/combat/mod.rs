@@ -2,6 +2,12 @@
 
 mod resolution;
 mod result;
+mod rotation;
 
-pub use resolution::{resolve_damage, resolve_damage_with_rng};
-pub use result::{CombatResult, DamageTaken};
+pub(crate) use resolution::{chance_to_land, expected_hit_damage};
+pub use resolution::{
+    resolve_damage, resolve_damage_multi, resolve_damage_multi_with_rng,
+    resolve_damage_with_constants, resolve_damage_with_rng, resolve_multi_hit,
+};
+pub use result::{CombatEvent, CombatResult, DamageTaken, MitigationKind};
+pub use rotation::{next_skill, Rotation, RotationCondition, RotationEntry};
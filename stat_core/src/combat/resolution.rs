@@ -1,35 +1,270 @@
 //! Damage resolution - Apply DamagePacket to StatBlock
 
-use super::result::{CombatResult, DamageTaken};
+use super::result::{CombatEvent, CombatResult, DamageTaken, MitigationKind};
+use crate::config::GameConstants;
 use crate::damage::DamagePacket;
-use crate::defense::{apply_evasion_cap, calculate_armour_reduction, calculate_resistance_mitigation};
+use crate::defense::{
+    apply_evasion_cap, calculate_armour_reduction_with_cap, calculate_evade_chance,
+    calculate_hit_chance, calculate_resistance_mitigation_with_percent_pen, cap_penetration,
+};
+use crate::dot::DotRegistry;
+use crate::effect::apply_ailment_from;
 use crate::stat_block::StatBlock;
-use crate::types::Effect;
+use crate::types::{DamagePool, Effect, EvasionMode};
 use loot_core::types::{DamageType, StatusEffect};
-use rand::Rng;
+use rand::{Rng, SeedableRng};
 
 /// Resolve a damage packet against a defending stat block (immutable API)
 ///
 /// Returns the new defender state and combat result. This is the main combat
 /// resolution function that:
-/// 1. Applies resistances to each damage type
+/// 0. Rolls the packet's hit chance first; a miss deals no damage, applies no
+///    effects, and can never be a critical strike. The packet's crit only
+///    registers once the hit has landed. With `HitConstants::roll_accuracy`
+///    the hit must also pass an accuracy vs evasion roll
+///    ([`calculate_hit_chance`]), which likewise sets `missed`.
+///
+///    Block is not rolled here: damage is scaled by the expected value
+///    `1 - block_chance` instead. Use [`resolve_damage_with_rng`] to roll block.
+///
+///    Defenders in `EvasionMode::Chance` then roll to evade the hit
+///    (accuracy vs evasion); an evaded hit behaves like a miss and sets
+///    `was_evaded`.
+/// 1. Applies shock (increased damage taken), reroutes damage the defender
+///    takes as other types (`StatBlock::taken_as`), then applies resistances
+///    to each damage type
 /// 2. Applies armour to physical damage
-/// 3. Applies evasion one-shot protection
-/// 4. Applies damage to ES then life
-/// 5. Processes status effect applications (chance = status_damage / max_health)
+/// 3. Applies evasion one-shot protection (`EvasionMode::DamageCap` only),
+///    then the defender's general `damage_taken_reduction` (which, like
+///    resistances, also reduces DoTs; armour only mitigates hits)
+/// 4. Subtracts damage from pools in order (guard → ward → ES → life by default)
+/// 5. Processes status effect applications (chance = status_damage / max_health),
+///    skipping ailments the defender is immune to or whose status damage is
+///    below the defender's `ailment_threshold` or `AilmentConstants::min_effect_fraction`.
+///    Applications below `full_effect_fraction` have reduced magnitude and DoT.
 pub fn resolve_damage(
     defender: &StatBlock,
     packet: &DamagePacket,
 ) -> (StatBlock, CombatResult) {
     let mut rng = rand::thread_rng();
-    resolve_damage_with_rng(defender, packet, &mut rng)
+    let constants = GameConstants::default();
+    let registry = DotRegistry::shared_defaults();
+    let block = defender.computed_block_chance() / 100.0;
+    if block <= 0.0 {
+        return resolve_hit(
+            defender,
+            packet,
+            &constants,
+            registry,
+            &mut rng,
+            HitRolls::ExpectedBlock,
+        );
+    }
+
+    let mut expected = packet.clone();
+    for damage in &mut expected.damages {
+        damage.scale(1.0 - block);
+    }
+    resolve_hit(
+        defender,
+        &expected,
+        &constants,
+        registry,
+        &mut rng,
+        HitRolls::ExpectedBlock,
+    )
 }
 
 /// Resolve damage with a provided RNG (for deterministic testing)
+///
+/// Each of the packet's `hit_count` hits rolls block independently; blocked
+/// hits deal no damage. If every hit is blocked, no effects are applied.
+/// A packet holds one hit's damage, and each of its landed hits deals all of
+/// it, sharing one damage roll (see `CombatResult::hit_damages`); use
+/// `calculate_damage_per_hit` with [`resolve_multi_hit`] for hits that roll
+/// their own damage and crit.
 pub fn resolve_damage_with_rng(
     defender: &StatBlock,
     packet: &DamagePacket,
     rng: &mut impl Rng,
+) -> (StatBlock, CombatResult) {
+    resolve_damage_with_constants(defender, packet, &GameConstants::default(), rng)
+}
+
+/// Resolve damage using tunable game constants
+///
+/// Set `constants.hit.roll_accuracy` to roll accuracy-based hit chance
+/// before any mitigation; the evasion cap still applies to landed hits.
+pub fn resolve_damage_with_constants(
+    defender: &StatBlock,
+    packet: &DamagePacket,
+    constants: &GameConstants,
+    rng: &mut impl Rng,
+) -> (StatBlock, CombatResult) {
+    resolve_hit(
+        defender,
+        packet,
+        constants,
+        DotRegistry::shared_defaults(),
+        rng,
+        HitRolls::All,
+    )
+}
+
+/// Chance (0.0 to 1.0) for a packet to get past the defender's accuracy and evasion checks
+///
+/// Covers the accuracy roll (with `HitConstants::roll_accuracy`) and
+/// `EvasionMode::Chance` evasion; the packet's own `hit_chance` and block
+/// are separate.
+pub(crate) fn chance_to_land(
+    defender: &StatBlock,
+    packet: &DamagePacket,
+    constants: &GameConstants,
+) -> f64 {
+    let evasion = defender.evasion.compute();
+    let accuracy_hit_chance = if constants.hit.roll_accuracy {
+        calculate_hit_chance(packet.accuracy, evasion)
+    } else {
+        1.0
+    };
+    let evade_chance = if defender.evasion_mode == EvasionMode::Chance {
+        calculate_evade_chance(packet.accuracy, evasion)
+    } else {
+        0.0
+    };
+    accuracy_hit_chance * (1.0 - evade_chance)
+}
+
+/// Expected damage of a packet against a defender, without rolling anything
+///
+/// The damage of a landed hit is scaled by the packet's `hit_chance`,
+/// [`chance_to_land`] and `1 - block_chance`. Status effects are not applied.
+pub(crate) fn expected_hit_damage(
+    defender: &StatBlock,
+    packet: &DamagePacket,
+    constants: &GameConstants,
+) -> f64 {
+    let mut landed = packet.clone();
+    landed.hit_chance = 1.0;
+    landed.status_effects_to_apply.clear();
+
+    // Nothing is rolled in expected mode
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    let registry = DotRegistry::shared_defaults();
+    let (_, result) = resolve_hit(
+        defender,
+        &landed,
+        constants,
+        registry,
+        &mut rng,
+        HitRolls::Expected,
+    );
+
+    let block = defender.computed_block_chance() / 100.0;
+    let hit_chance = packet.hit_chance.clamp(0.0, 1.0);
+    result.total_damage * hit_chance * chance_to_land(defender, packet, constants) * (1.0 - block)
+}
+
+/// Resolve a sequence of per-hit packets, one `CombatResult` per hit
+///
+/// Pair with `calculate_damage_per_hit` so each hit has its own damage and
+/// crit roll. Every hit rolls hit chance, block and effects independently,
+/// and the defender's state carries over, so a later hit can land the
+/// killing blow. Hits after the killing blow are not resolved.
+pub fn resolve_multi_hit(
+    defender: &StatBlock,
+    packets: &[DamagePacket],
+    rng: &mut impl Rng,
+) -> (StatBlock, Vec<CombatResult>) {
+    let mut current = defender.clone();
+    let mut results = Vec::with_capacity(packets.len());
+
+    for packet in packets {
+        let (next, result) = resolve_damage_with_rng(&current, packet, rng);
+        current = next;
+        let killed = result.is_killing_blow;
+        results.push(result);
+        if killed {
+            break;
+        }
+    }
+
+    (current, results)
+}
+
+/// Resolve one packet against several targets (AoE, chains)
+///
+/// The packet is computed once and resolved against each target in turn,
+/// and each target is updated in place. Every target rolls its own hit
+/// chance, evasion, block and ailments. If the packet chains, target `i`
+/// receives `packet.after_chain(i)`, so targets past `chain_count` take
+/// nothing. AoE falloff is not applied here since it depends on distance;
+/// use `DamagePacket::at_distance` per target for that. Ailments stack per
+/// `dot_registry`.
+pub fn resolve_damage_multi(
+    targets: &mut [&mut StatBlock],
+    packet: &DamagePacket,
+    dot_registry: &DotRegistry,
+) -> Vec<CombatResult> {
+    resolve_damage_multi_with_rng(targets, packet, dot_registry, &mut rand::thread_rng())
+}
+
+/// Resolve one packet against several targets with a provided RNG
+pub fn resolve_damage_multi_with_rng(
+    targets: &mut [&mut StatBlock],
+    packet: &DamagePacket,
+    dot_registry: &DotRegistry,
+    rng: &mut impl Rng,
+) -> Vec<CombatResult> {
+    let constants = GameConstants::default();
+    targets
+        .iter_mut()
+        .enumerate()
+        .map(|(index, target)| {
+            let received = if packet.chain_count > 0 {
+                packet.after_chain(index as u32)
+            } else {
+                packet.clone()
+            };
+            let (next, result) = resolve_hit(
+                &**target,
+                &received,
+                &constants,
+                dot_registry,
+                rng,
+                HitRolls::All,
+            );
+            **target = next;
+            result
+        })
+        .collect()
+}
+
+/// Remove `amount` from two damage buckets, emptying `first` before `second`
+fn drain_damage(amount: f64, first: &mut f64, second: &mut f64) {
+    let from_first = amount.min(*first);
+    *first -= from_first;
+    *second -= amount - from_first;
+}
+
+/// Which avoidance checks `resolve_hit` rolls
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HitRolls {
+    /// Roll hit chance, evasion and block
+    All,
+    /// Roll hit chance and evasion; the caller scales damage by expected block
+    ExpectedBlock,
+    /// Roll no avoidance; the caller scales damage by the chance to land
+    Expected,
+}
+
+fn resolve_hit(
+    defender: &StatBlock,
+    packet: &DamagePacket,
+    constants: &GameConstants,
+    dot_registry: &DotRegistry,
+    rng: &mut impl Rng,
+    rolls: HitRolls,
 ) -> (StatBlock, CombatResult) {
     let mut new_defender = defender.clone();
     let mut result = CombatResult::new();
@@ -38,17 +273,101 @@ pub fn resolve_damage_with_rng(
     result.es_before = new_defender.current_energy_shield;
     result.life_before = new_defender.current_life;
 
+    // Step 0: Roll hit before crit so a miss can never crit
+    let accuracy_hit_chance = if constants.hit.roll_accuracy {
+        calculate_hit_chance(packet.accuracy, defender.evasion.compute())
+    } else {
+        1.0
+    };
+    let hit_chance = packet.hit_chance * accuracy_hit_chance;
+    if rolls != HitRolls::Expected && hit_chance < 1.0 && rng.gen::<f64>() >= hit_chance {
+        result.missed = true;
+        result.events.push(CombatEvent::Missed);
+        result.es_after = new_defender.current_energy_shield;
+        result.life_after = new_defender.current_life;
+        return (new_defender, result);
+    }
+
+    // Chance-mode evasion avoids the whole hit
+    if rolls != HitRolls::Expected && defender.evasion_mode == EvasionMode::Chance {
+        let evade_chance = calculate_evade_chance(packet.accuracy, defender.evasion.compute());
+        if evade_chance > 0.0 && rng.gen::<f64>() < evade_chance {
+            result.was_evaded = true;
+            result.events.push(CombatEvent::Evaded);
+            result.es_after = new_defender.current_energy_shield;
+            result.life_after = new_defender.current_life;
+            return (new_defender, result);
+        }
+    }
+    result.is_critical = packet.is_critical;
+
+    // Roll block per hit, before any mitigation
+    let hits = packet.hit_count.max(1);
+    let block = defender.computed_block_chance() / 100.0;
+    if rolls == HitRolls::All && block > 0.0 {
+        result.hits_blocked = (0..hits).filter(|_| rng.gen::<f64>() < block).count() as u32;
+        result.was_blocked = result.hits_blocked > 0;
+        if result.was_blocked {
+            result.events.push(CombatEvent::Blocked {
+                hits_blocked: result.hits_blocked,
+                hits,
+            });
+        }
+        if result.hits_blocked == hits {
+            result.es_after = new_defender.current_energy_shield;
+            result.life_after = new_defender.current_life;
+            return (new_defender, result);
+        }
+    }
+
+    // The packet is one hit's damage; every landed hit deals all of it
+    let landed_hits = hits - result.hits_blocked;
+    let per_hit: f64 = packet.damages.iter().map(|d| d.amount).sum();
+    result.hit_damages = vec![per_hit; landed_hits as usize];
+    let landed: Vec<(DamageType, f64)> = packet
+        .damages
+        .iter()
+        .map(|d| (d.damage_type, d.amount * landed_hits as f64))
+        .collect();
+
+    // Shock increases all incoming hit damage
+    let shock = defender
+        .shock_effect()
+        .min(constants.ailments.max_shock_effect);
+
+    // Record applied resistance reduction on the defender (strongest per type)
+    // and refresh how long it lasts
+    if !packet.resistance_reduction_applied.is_empty() {
+        new_defender
+            .resistance_reduction
+            .max_with(&packet.resistance_reduction_applied);
+        new_defender.resistance_reduction_remaining = constants.resistances.reduction_duration;
+    }
+
+    // Reroute damage the defender takes as other types, before any mitigation
+    let incoming: Vec<(DamageType, f64)> = landed
+        .into_iter()
+        .map(|(damage_type, amount)| (damage_type, amount * (1.0 + shock)))
+        .collect();
+    let (incoming, reroutes) = new_defender.taken_as.reroute(&incoming);
+    result.damage_taken_as = reroutes;
+
     // Step 1: Calculate mitigated damage for each type
-    for final_damage in &packet.damages {
-        let raw = final_damage.amount;
-        let pen = packet.penetration(final_damage.damage_type);
-        let resist = new_defender.resistance(final_damage.damage_type);
+    for (damage_type, raw) in incoming {
+        let pen = cap_penetration(
+            packet.penetration(damage_type),
+            constants.resistances.max_penetration,
+        );
+        let resist = new_defender.effective_resistance(damage_type);
 
-        let after_resist = if final_damage.damage_type == DamageType::Physical {
+        let is_physical = damage_type == DamageType::Physical;
+        let after_resist = if is_physical {
             // Physical uses armour instead of resistance
             raw
         } else {
-            calculate_resistance_mitigation(raw, resist, pen)
+            let max = new_defender.max_resistance(damage_type);
+            let pen_percent = packet.penetration_percent(damage_type);
+            calculate_resistance_mitigation_with_percent_pen(raw, resist, pen_percent, pen, max)
         };
 
         let mitigated = raw - after_resist;
@@ -56,12 +375,11 @@ pub fn resolve_damage_with_rng(
             result.damage_reduced_by_resists += mitigated;
         }
 
-        result.damage_taken.push(DamageTaken::new(
-            final_damage.damage_type,
-            raw,
-            mitigated.max(0.0),
-            after_resist,
-        ));
+        let mut taken = DamageTaken::new(damage_type, raw, mitigated.max(0.0), after_resist);
+        if !is_physical {
+            taken = taken.with_resistance(resist, pen);
+        }
+        result.damage_taken.push(taken);
     }
 
     // Step 2: Apply armour to physical damage
@@ -72,23 +390,45 @@ pub fn resolve_damage_with_rng(
 
     if let Some(phys) = physical_damage {
         if phys.final_amount > 0.0 {
+            // Armour scales with the size of each hit, not the packet's total
             let armour = new_defender.armour.compute();
-            let after_armour = calculate_armour_reduction(armour, phys.final_amount);
+            let per_hit = phys.final_amount / landed_hits as f64;
+            let after_armour = calculate_armour_reduction_with_cap(
+                armour,
+                per_hit,
+                constants.armour.max_reduction,
+            ) * landed_hits as f64;
             let armour_reduced = phys.final_amount - after_armour;
 
             result.damage_reduced_by_armour = armour_reduced;
             phys.mitigated_amount += armour_reduced;
             phys.final_amount = after_armour;
         }
+
+        // Additional physical damage reduction, partly ignored by overwhelm
+        let reduction =
+            (new_defender.physical_damage_reduction - packet.overwhelm).clamp(0.0, 100.0);
+        if phys.final_amount > 0.0 && reduction > 0.0 {
+            let reduced = phys.final_amount * reduction / 100.0;
+            result.damage_reduced_by_armour += reduced;
+            phys.mitigated_amount += reduced;
+            phys.final_amount -= reduced;
+        }
     }
 
     // Recalculate total after armour
     let total_before_evasion: f64 = result.damage_taken.iter().map(|d| d.final_amount).sum();
 
-    // Step 3: Apply evasion one-shot protection (accuracy vs evasion)
-    let evasion = new_defender.evasion.compute();
-    let accuracy = packet.accuracy;
-    let (damage_after_evasion, evaded) = apply_evasion_cap(accuracy, evasion, total_before_evasion);
+    // Step 3: Apply evasion one-shot protection (accuracy vs evasion) to each hit
+    let (damage_after_evasion, evaded) = match new_defender.evasion_mode {
+        EvasionMode::DamageCap => {
+            let per_hit = total_before_evasion / landed_hits as f64;
+            let (after, evaded) =
+                apply_evasion_cap(packet.accuracy, new_defender.evasion.compute(), per_hit);
+            (after * landed_hits as f64, evaded * landed_hits as f64)
+        }
+        EvasionMode::Chance => (total_before_evasion, 0.0),
+    };
 
     if evaded > 0.0 {
         result.triggered_evasion_cap = true;
@@ -105,29 +445,154 @@ pub fn resolve_damage_with_rng(
         }
     }
 
+    // General damage taken reduction (also applies to DoT ticks)
+    let taken_mult = new_defender.damage_taken_multiplier();
+    if taken_mult < 1.0 {
+        for damage in &mut result.damage_taken {
+            let reduced = damage.final_amount * (1.0 - taken_mult);
+            damage.mitigated_amount += reduced;
+            damage.final_amount -= reduced;
+            result.damage_reduced_by_damage_taken += reduced;
+        }
+    }
+
     // Calculate final total damage
     result.total_damage = result.damage_taken.iter().map(|d| d.final_amount).sum();
+    result.damage_added_by_shock = result.total_damage * shock / (1.0 + shock);
+
+    for damage in &result.damage_taken {
+        if damage.final_amount > 0.0 {
+            result.events.push(CombatEvent::HitDealt {
+                damage_type: damage.damage_type,
+                amount: damage.final_amount,
+                is_critical: result.is_critical,
+            });
+        }
+    }
+    for (kind, amount) in [
+        (MitigationKind::Resistance, result.damage_reduced_by_resists),
+        (MitigationKind::Armour, result.damage_reduced_by_armour),
+        (
+            MitigationKind::EvasionCap,
+            result.damage_prevented_by_evasion,
+        ),
+        (
+            MitigationKind::DamageTaken,
+            result.damage_reduced_by_damage_taken,
+        ),
+    ] {
+        if amount > 0.0 {
+            result.events.push(CombatEvent::Mitigated { kind, amount });
+        }
+    }
+
+    // Leech is based on damage dealt; the attacker applies it via `apply_leech_to`
+    if packet.can_leech {
+        result.life_leeched = result.total_damage * packet.life_leech.max(0.0);
+        result.mana_leeched = result.total_damage * packet.mana_leech.max(0.0);
+    }
 
-    // Step 4: Apply damage to ES then life
-    let mut remaining_damage = result.total_damage;
+    // Reflect a share of the physical damage actually taken (after armour and evasion)
+    if new_defender.physical_damage_reflected > 0.0 {
+        let physical_taken = result
+            .damage_of_type(DamageType::Physical)
+            .map(|d| d.final_amount)
+            .unwrap_or(0.0);
+        result.reflected_damage = physical_taken * new_defender.physical_damage_reflected / 100.0;
+    }
+
+    // Life on hit is granted per hit, but only if the hit dealt damage
+    if packet.can_apply_on_hit && result.total_damage > 0.0 {
+        result.life_gained_on_hit =
+            packet.life_on_hit.max(0.0) * (hits - result.hits_blocked) as f64;
+    }
+
+    // Step 4: Subtract damage from pools in the configured order
+    // Damage types that bypass ES are tracked separately and skip that pool
+    let mut bypass_damage: f64 = result
+        .damage_taken
+        .iter()
+        .filter(|d| constants.energy_shield.bypasses(d.damage_type))
+        .map(|d| d.final_amount.max(0.0))
+        .sum();
+    bypass_damage = bypass_damage.min(result.total_damage.max(0.0));
+    let mut remaining_damage = result.total_damage - bypass_damage;
+
+    for pool in &constants.damage_pools.order {
+        if remaining_damage + bypass_damage <= 0.0 {
+            break;
+        }
+        match pool {
+            DamagePool::Guard => {
+                let absorbed =
+                    (remaining_damage + bypass_damage).min(new_defender.current_guard.max(0.0));
+                new_defender.current_guard -= absorbed;
+                drain_damage(absorbed, &mut remaining_damage, &mut bypass_damage);
+                result.damage_absorbed_by_guard += absorbed;
+            }
+            DamagePool::Ward => {
+                let absorbed =
+                    (remaining_damage + bypass_damage).min(new_defender.current_ward.max(0.0));
+                new_defender.current_ward -= absorbed;
+                drain_damage(absorbed, &mut remaining_damage, &mut bypass_damage);
+                result.damage_absorbed_by_ward += absorbed;
+            }
+            DamagePool::EnergyShield => {
+                let absorbed = remaining_damage.min(new_defender.current_energy_shield.max(0.0));
+                new_defender.current_energy_shield -= absorbed;
+                remaining_damage -= absorbed;
+                result.damage_blocked_by_es += absorbed;
+                result.damage_bypassed_es += bypass_damage;
+                if absorbed > 0.0 {
+                    new_defender.es_recharge_delay_remaining =
+                        constants.energy_shield.recharge_delay;
+                }
+            }
+            DamagePool::Life => {
+                let damage = remaining_damage + bypass_damage;
+                new_defender.current_life -= damage;
+                result.damage_to_life += damage;
+                remaining_damage = 0.0;
+                bypass_damage = 0.0;
+            }
+        }
+    }
 
-    // ES absorbs damage first
-    if new_defender.current_energy_shield > 0.0 && remaining_damage > 0.0 {
-        let es_absorbed = remaining_damage.min(new_defender.current_energy_shield);
-        new_defender.current_energy_shield -= es_absorbed;
-        remaining_damage -= es_absorbed;
-        result.damage_blocked_by_es = es_absorbed;
+    // Life always takes whatever the configured pools did not absorb
+    let unabsorbed = remaining_damage + bypass_damage;
+    if unabsorbed > 0.0 {
+        new_defender.current_life -= unabsorbed;
+        result.damage_to_life += unabsorbed;
     }
 
-    // Remaining damage goes to life
-    if remaining_damage > 0.0 {
-        new_defender.current_life -= remaining_damage;
+    for (pool, amount) in [
+        (DamagePool::Guard, result.damage_absorbed_by_guard),
+        (DamagePool::Ward, result.damage_absorbed_by_ward),
+        (DamagePool::EnergyShield, result.damage_blocked_by_es),
+        (DamagePool::Life, result.damage_to_life),
+    ] {
+        if amount > 0.0 {
+            result.events.push(CombatEvent::Absorbed { pool, amount });
+        }
     }
 
-    // Check for death
+    // Check for death; culling strike kills targets left below the threshold
+    let max_life = new_defender.computed_max_life();
+    let culled = new_defender.current_life > 0.0
+        && result.total_damage > 0.0
+        && max_life > 0.0
+        && new_defender.current_life / max_life < packet.culling_strike_threshold;
+    if culled {
+        result.was_culled = true;
+        new_defender.current_life = 0.0;
+    }
     if new_defender.current_life <= 0.0 {
         result.is_killing_blow = true;
+        result.overkill = -new_defender.current_life;
         new_defender.current_life = 0.0;
+        result.events.push(CombatEvent::KillingBlow {
+            overkill: result.overkill,
+        });
     }
 
     // Store final state
@@ -138,20 +603,53 @@ pub fn resolve_damage_with_rng(
     // Chance to apply = status_damage / target_max_health
     let target_max_health = new_defender.computed_max_life();
     for pending_status in &packet.status_effects_to_apply {
+        if new_defender
+            .ailment_immunities
+            .is_immune(pending_status.effect_type)
+        {
+            result.immune_effects.push(pending_status.effect_type);
+            result.events.push(CombatEvent::AilmentImmune {
+                status: pending_status.effect_type,
+            });
+            continue;
+        }
+        // Applications too small relative to the target's life fizzle
+        if pending_status.status_damage < new_defender.ailment_threshold * target_max_health {
+            continue;
+        }
+        let Some(effect_scale) = constants
+            .ailments
+            .effect_scale(pending_status.status_damage, target_max_health)
+        else {
+            continue;
+        };
+
         let apply_chance = pending_status.calculate_apply_chance(target_max_health);
 
         if rng.gen::<f64>() < apply_chance {
-            // Create unified Effect based on status type
-            let effect = create_effect_from_status(
+            // Shock/chill/slow magnitude scales the base effect of the ailment
+            let magnitude = match pending_status.effect_type {
+                StatusEffect::Static => pending_status.magnitude * constants.ailments.shock_effect,
+                StatusEffect::Chill => pending_status.magnitude * constants.ailments.chill_effect,
+                StatusEffect::Slow => pending_status.magnitude * constants.ailments.slow_effect,
+                _ => pending_status.magnitude,
+            };
+
+            // Create unified Effect based on status type; weak applications are scaled down
+            let effect = Effect::from_status(
                 pending_status.effect_type,
                 pending_status.duration,
-                pending_status.magnitude,
-                pending_status.dot_dps,
+                magnitude * effect_scale,
+                pending_status.dot_dps * effect_scale,
                 &packet.source_id,
             );
 
-            // Add to unified effects (handles stacking internally)
-            new_defender.add_effect(effect.clone());
+            // Stack or refresh per the ailment's DoT config
+            apply_ailment_from(&mut new_defender, effect.clone(), dot_registry);
+            result.events.push(CombatEvent::AilmentApplied {
+                status: pending_status.effect_type,
+                duration: pending_status.duration,
+            });
             result.effects_applied.push(effect);
         }
     }
@@ -159,66 +657,6 @@ pub fn resolve_damage_with_rng(
     (new_defender, result)
 }
 
-/// Create an Effect from a pending status effect
-fn create_effect_from_status(
-    status: StatusEffect,
-    duration: f64,
-    magnitude: f64,
-    dot_dps: f64,
-    source_id: &str,
-) -> Effect {
-    match status {
-        StatusEffect::Poison => {
-            let mut e = Effect::poison(dot_dps, source_id);
-            e.duration_remaining = duration;
-            e.total_duration = duration;
-            e
-        }
-        StatusEffect::Bleed => {
-            let mut e = Effect::bleed(dot_dps, source_id);
-            e.duration_remaining = duration;
-            e.total_duration = duration;
-            e
-        }
-        StatusEffect::Burn => {
-            let mut e = Effect::burn(dot_dps, source_id);
-            e.duration_remaining = duration;
-            e.total_duration = duration;
-            e
-        }
-        StatusEffect::Freeze => {
-            let mut e = Effect::freeze(magnitude, source_id);
-            e.duration_remaining = duration;
-            e.total_duration = duration;
-            e
-        }
-        StatusEffect::Chill => {
-            let mut e = Effect::chill(magnitude, source_id);
-            e.duration_remaining = duration;
-            e.total_duration = duration;
-            e
-        }
-        StatusEffect::Static => {
-            let mut e = Effect::shock(magnitude, source_id);
-            e.duration_remaining = duration;
-            e.total_duration = duration;
-            e
-        }
-        StatusEffect::Fear => {
-            let mut e = Effect::fear(magnitude, source_id);
-            e.duration_remaining = duration;
-            e.total_duration = duration;
-            e
-        }
-        StatusEffect::Slow => {
-            let mut e = Effect::slow(magnitude, source_id);
-            e.duration_remaining = duration;
-            e.total_duration = duration;
-            e
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -261,6 +699,494 @@ mod tests {
         assert!((result.damage_reduced_by_resists - 50.0).abs() < 1.0);
     }
 
+    #[test]
+    fn test_damage_taken_records_resistance_and_penetration() {
+        let mut defender = StatBlock::new();
+        defender.current_life = 1000.0;
+        defender.fire_resistance.base = 60.0;
+
+        let mut packet = make_test_packet(vec![(DamageType::Fire, 100.0)]);
+        packet.fire_pen = 20.0;
+
+        let (_, result) = resolve_damage(&defender, &packet);
+        let fire = &result.damage_taken[0];
+
+        assert!((fire.resistance_applied - 60.0).abs() < f64::EPSILON);
+        assert!((fire.penetration_applied - 20.0).abs() < f64::EPSILON);
+        let expected =
+            fire.raw_amount * (1.0 - (fire.resistance_applied - fire.penetration_applied) / 100.0);
+        assert!((fire.final_amount - expected).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_leech_heals_attacker() {
+        let mut defender = StatBlock::new();
+        defender.current_life = 1000.0;
+
+        let mut attacker = StatBlock::new();
+        attacker.current_life = 10.0;
+        attacker.current_mana = 0.0;
+
+        // 10% life / 5% mana leech on 100 fire damage
+        let mut packet = make_test_packet(vec![(DamageType::Fire, 100.0)]);
+        packet.life_leech = 0.10;
+        packet.mana_leech = 0.05;
+
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!((result.life_leeched - 10.0).abs() < 0.01);
+        assert!((result.mana_leeched - 5.0).abs() < 0.01);
+
+        result.apply_leech_to(&mut attacker);
+        assert!((attacker.current_life - 20.0).abs() < 0.01);
+        assert!((attacker.current_mana - 5.0).abs() < 0.01);
+
+        // Capped at max life (50 base)
+        packet.life_leech = 1.0;
+        let (_, result) = resolve_damage(&defender, &packet);
+        result.apply_leech_to(&mut attacker);
+        assert!((attacker.current_life - attacker.computed_max_life()).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_mana_leech_rate_cap() {
+        let defender = StatBlock::new();
+        let mut packet = make_test_packet(vec![(DamageType::Fire, 100.0)]);
+        packet.mana_leech = 0.20;
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!((result.mana_leeched - 20.0).abs() < 0.01);
+
+        // Uncapped: all 20 mana is restored at once
+        let mut attacker = StatBlock::new();
+        attacker.max_mana.base = 100.0;
+        attacker.current_mana = 0.0;
+        result.apply_leech_to(&mut attacker);
+        assert!((attacker.current_mana - 20.0).abs() < 0.01);
+
+        // Capped at 10% of max mana per second: 10 per second
+        let mut attacker = StatBlock::new();
+        attacker.max_mana.base = 100.0;
+        attacker.current_mana = 0.0;
+        attacker.max_mana_leech_rate = 0.10;
+        result.apply_leech_to(&mut attacker);
+        assert!(attacker.current_mana.abs() < f64::EPSILON);
+        assert!((attacker.pending_mana_leech - 20.0).abs() < 0.01);
+
+        attacker.tick_regen(1.0);
+        assert!((attacker.current_mana - 10.0).abs() < 0.01);
+        attacker.tick_regen(5.0);
+        assert!((attacker.current_mana - 20.0).abs() < 0.01);
+        assert!(attacker.pending_mana_leech.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_life_on_hit_per_hit() {
+        let defender = StatBlock::new();
+        let mut attacker = StatBlock::new();
+        attacker.current_life = 10.0;
+
+        let mut packet = make_test_packet(vec![(DamageType::Fire, 30.0)]);
+        packet.life_on_hit = 5.0;
+        packet.hit_count = 3;
+
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!((result.life_gained_on_hit - 15.0).abs() < f64::EPSILON);
+
+        result.apply_leech_to(&mut attacker);
+        assert!((attacker.current_life - 25.0).abs() < 0.01);
+
+        // No damage dealt: no life gained
+        let mut immune = StatBlock::new();
+        immune.max_fire_resistance = 100.0;
+        immune.fire_resistance.base = 100.0;
+        let (_, result) = resolve_damage(&immune, &packet);
+        assert!((result.life_gained_on_hit - 0.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_block_rolls_per_hit() {
+        use rand::SeedableRng;
+
+        let mut defender = StatBlock::new();
+        defender.current_life = 10000.0;
+        defender.block_chance.base = 90.0;
+        assert!((defender.computed_block_chance() - 75.0).abs() < f64::EPSILON);
+
+        let mut packet = make_test_packet(vec![(DamageType::Fire, 90.0)]);
+        packet.hit_count = 3;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+        let mut partial = false;
+        let mut blocked_hits = 0;
+        for _ in 0..400 {
+            let (_, result) = resolve_damage_with_rng(&defender, &packet, &mut rng);
+            blocked_hits += result.hits_blocked;
+            // Each unblocked hit deals the whole packet
+            let expected = 90.0 * (3 - result.hits_blocked) as f64;
+            assert!((result.total_damage - expected).abs() < 0.01);
+            assert_eq!(result.was_blocked, result.hits_blocked > 0);
+            partial |= result.hits_blocked == 1 || result.hits_blocked == 2;
+        }
+
+        // Hits roll independently, so partial blocks occur at ~75% per hit
+        assert!(partial);
+        let rate = blocked_hits as f64 / 1200.0;
+        assert!((rate - 0.75).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_resolve_damage_uses_expected_block() {
+        let mut defender = StatBlock::new();
+        defender.current_life = 1000.0;
+        defender.block_chance.base = 40.0;
+
+        let packet = make_test_packet(vec![(DamageType::Fire, 100.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+
+        assert!(!result.was_blocked);
+        assert!((result.total_damage - 60.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_reflect_uses_physical_damage_taken() {
+        use rand::SeedableRng;
+
+        let mut defender = StatBlock::new();
+        defender.current_life = 1000.0;
+        defender.physical_damage_reflected = 10.0;
+
+        let packet = make_test_packet(vec![(DamageType::Physical, 100.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+        let physical_taken = result
+            .damage_of_type(DamageType::Physical)
+            .unwrap()
+            .final_amount;
+        assert!((result.reflected_damage - physical_taken * 0.10).abs() < 0.01);
+        assert!(result.reflected_damage > 0.0);
+
+        // Armour lowers the damage taken, so it lowers the reflection too
+        defender.armour.base = 1000.0;
+        let (_, armoured) = resolve_damage(&defender, &packet);
+        assert!(armoured.reflected_damage < result.reflected_damage);
+        let physical_taken = armoured
+            .damage_of_type(DamageType::Physical)
+            .unwrap()
+            .final_amount;
+        assert!((armoured.reflected_damage - physical_taken * 0.10).abs() < 0.01);
+
+        // A missed hit reflects nothing
+        let mut missed = packet.clone();
+        missed.hit_chance = 0.0;
+        let (_, result) = resolve_damage(&defender, &missed);
+        assert!(result.missed);
+        assert!(result.reflected_damage.abs() < f64::EPSILON);
+
+        // A blocked hit reflects nothing
+        defender.block_chance.base = 75.0;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        let blocked = (0..100)
+            .map(|_| resolve_damage_with_rng(&defender, &packet, &mut rng).1)
+            .find(|result| result.was_blocked)
+            .unwrap();
+        assert!(blocked.reflected_damage.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_immune_target_never_frozen() {
+        use crate::damage::PendingStatusEffect;
+        use rand::SeedableRng;
+
+        let mut defender = StatBlock::new();
+        defender.current_life = 1000.0;
+        defender.ailment_immunities.grant(StatusEffect::Freeze);
+
+        // Status damage far above max life would otherwise always freeze
+        let mut packet = make_test_packet(vec![(DamageType::Cold, 10.0)]);
+        packet
+            .status_effects_to_apply
+            .push(PendingStatusEffect::new(
+                StatusEffect::Freeze,
+                10_000.0,
+                0.5,
+                1.0,
+            ));
+        packet
+            .status_effects_to_apply
+            .push(PendingStatusEffect::new(
+                StatusEffect::Chill,
+                10_000.0,
+                2.0,
+                0.3,
+            ));
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+        for _ in 0..20 {
+            let (after, result) = resolve_damage_with_rng(&defender, &packet, &mut rng);
+            assert!(!after.is_frozen());
+            assert_eq!(result.immune_effects, vec![StatusEffect::Freeze]);
+            assert!(after.has_active_ailment(StatusEffect::Chill));
+        }
+    }
+
+    #[test]
+    fn test_events_for_crit_that_poisons() {
+        use crate::damage::PendingStatusEffect;
+        use rand::SeedableRng;
+
+        let mut defender = StatBlock::new();
+        defender.current_life = 1000.0;
+        defender.armour.base = 500.0;
+
+        let mut packet = make_test_packet(vec![(DamageType::Physical, 100.0)]);
+        packet.is_critical = true;
+        packet
+            .status_effects_to_apply
+            .push(PendingStatusEffect::new_with_dot(
+                StatusEffect::Poison,
+                10_000.0,
+                2.0,
+                1.0,
+                40.0,
+            ));
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(9);
+        let (_, result) = resolve_damage_with_rng(&defender, &packet, &mut rng);
+
+        assert_eq!(result.events.len(), 4);
+        assert!(matches!(
+            result.events[0],
+            CombatEvent::HitDealt {
+                damage_type: DamageType::Physical,
+                is_critical: true,
+                ..
+            }
+        ));
+        assert_eq!(
+            result.events[1],
+            CombatEvent::Mitigated {
+                kind: MitigationKind::Armour,
+                amount: result.damage_reduced_by_armour,
+            }
+        );
+        assert_eq!(
+            result.events[2],
+            CombatEvent::Absorbed {
+                pool: DamagePool::Life,
+                amount: result.total_damage,
+            }
+        );
+        assert_eq!(
+            result.events[3],
+            CombatEvent::AilmentApplied {
+                status: StatusEffect::Poison,
+                duration: 2.0,
+            }
+        );
+
+        // A miss records only the miss
+        packet.hit_chance = 0.0;
+        let (_, result) = resolve_damage_with_rng(&defender, &packet, &mut rng);
+        assert_eq!(result.events, vec![CombatEvent::Missed]);
+    }
+
+    #[test]
+    fn test_ailment_threshold_fizzles_small_applications() {
+        use crate::damage::PendingStatusEffect;
+
+        let mut defender = StatBlock::new();
+        defender.max_life.base = 1000.0;
+        defender.current_life = 1000.0;
+        defender.ailment_threshold = 0.10;
+
+        // 50 status damage is below 10% of 1000 life
+        let mut packet = make_test_packet(vec![]);
+        packet
+            .status_effects_to_apply
+            .push(PendingStatusEffect::new(
+                StatusEffect::Chill,
+                50.0,
+                2.0,
+                0.3,
+            ));
+        let (after, result) = resolve_damage(&defender, &packet);
+        assert!(result.effects_applied.is_empty());
+        assert!(result.immune_effects.is_empty());
+        assert!(!after.has_active_ailment(StatusEffect::Chill));
+
+        // At or above the threshold the normal chance roll applies
+        packet.status_effects_to_apply[0].status_damage = 5000.0;
+        let (after, _) = resolve_damage(&defender, &packet);
+        assert!(after.has_active_ailment(StatusEffect::Chill));
+    }
+
+    #[test]
+    fn test_ailment_effect_scales_with_status_damage() {
+        use crate::damage::PendingStatusEffect;
+        use rand::SeedableRng;
+
+        let mut defender = StatBlock::new();
+        defender.max_life.base = 1000.0;
+        defender.current_life = 1000.0;
+
+        let mut constants = GameConstants::default();
+        constants.ailments.min_effect_fraction = 0.05;
+        constants.ailments.full_effect_fraction = 0.5;
+
+        // 20 status damage is 2% of life: below the minimum, never applies
+        let mut packet = make_test_packet(vec![]);
+        packet
+            .status_effects_to_apply
+            .push(PendingStatusEffect::new_with_dot(
+                StatusEffect::Poison,
+                20.0,
+                2.0,
+                1.0,
+                40.0,
+            ));
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        for _ in 0..50 {
+            let (_, result) =
+                resolve_damage_with_constants(&defender, &packet, &constants, &mut rng);
+            assert!(result.effects_applied.is_empty());
+        }
+
+        // Well above the full-effect fraction: always applies at full DoT
+        packet.status_effects_to_apply[0].status_damage = 2000.0;
+        let (_, result) = resolve_damage_with_constants(&defender, &packet, &constants, &mut rng);
+        assert!((result.effects_applied[0].dps() - 40.0).abs() < 0.01);
+
+        // Halfway to full effect deals half the DoT
+        assert!(
+            (constants.ailments.effect_scale(250.0, 1000.0).unwrap() - 0.5).abs() < f64::EPSILON
+        );
+    }
+
+    #[test]
+    fn test_overkill() {
+        let mut defender = StatBlock::new();
+        defender.current_life = 100.0;
+
+        // Non-lethal
+        let packet = make_test_packet(vec![(DamageType::Chaos, 60.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!(!result.is_killing_blow);
+        assert!(result.overkill.abs() < f64::EPSILON);
+
+        // Exactly lethal
+        let packet = make_test_packet(vec![(DamageType::Chaos, 100.0)]);
+        let (after, result) = resolve_damage(&defender, &packet);
+        assert!(result.is_killing_blow);
+        assert!(result.overkill.abs() < 0.01);
+        assert!(after.current_life.abs() < f64::EPSILON);
+
+        // Massive overkill
+        let packet = make_test_packet(vec![(DamageType::Chaos, 1100.0)]);
+        let (after, result) = resolve_damage(&defender, &packet);
+        assert!(result.is_killing_blow);
+        assert!((result.overkill - 1000.0).abs() < 0.01);
+        assert!(after.current_life.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_shock_increases_damage_taken() {
+        let mut defender = StatBlock::new();
+        defender.current_life = 1000.0;
+        defender.add_effect(Effect::shock(0.20, "attacker"));
+
+        let packet = make_test_packet(vec![(DamageType::Chaos, 100.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!((result.total_damage - 120.0).abs() < 0.01);
+        assert!((result.damage_added_by_shock - 20.0).abs() < 0.01);
+
+        // Stacks add up, but the total is capped
+        for _ in 0..5 {
+            defender.add_effect(Effect::shock(0.20, "attacker"));
+        }
+        assert!((defender.shock_effect() - 0.60).abs() < 0.01);
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!((result.total_damage - 150.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_expired_shock_adds_no_damage() {
+        let mut defender = StatBlock::new();
+        defender.current_life = 1000.0;
+        let mut shock = Effect::shock(0.20, "attacker");
+        shock.duration_remaining = 0.0;
+        defender.effects.push(shock);
+
+        assert!(defender.shock_effect().abs() < f64::EPSILON);
+        let packet = make_test_packet(vec![(DamageType::Chaos, 100.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!((result.total_damage - 100.0).abs() < 0.01);
+        assert!(result.damage_added_by_shock.abs() < 0.01);
+    }
+
+    #[test]
+    fn test_multi_hit_second_hit_kills() {
+        use crate::damage::{calculate_damage_per_hit, BaseDamage, DamagePacketGenerator};
+        use crate::types::SkillTag;
+        use rand::SeedableRng;
+
+        let skill = DamagePacketGenerator {
+            id: "double_bolt".to_string(),
+            name: "Double Bolt".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Chaos, 60.0, 60.0)],
+            weapon_effectiveness: 0.0,
+            hits_per_attack: 2,
+            tags: vec![SkillTag::Spell],
+            ..Default::default()
+        };
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let packets =
+            calculate_damage_per_hit(&StatBlock::new(), &skill, "caster".to_string(), &mut rng);
+
+        let mut defender = StatBlock::new();
+        defender.current_life = 100.0;
+        let (after, results) = resolve_multi_hit(&defender, &packets, &mut rng);
+
+        assert_eq!(results.len(), 2);
+        assert!(!results[0].is_killing_blow);
+        assert!((results[0].life_after - (100.0 - results[0].total_damage)).abs() < 0.01);
+        assert!((results[1].life_before - results[0].life_after).abs() < f64::EPSILON);
+        assert!(results[1].is_killing_blow);
+        assert!(!after.is_alive());
+    }
+
+    #[test]
+    fn test_zero_leech_restores_nothing() {
+        let defender = StatBlock::new();
+        let mut attacker = StatBlock::new();
+        attacker.current_life = 10.0;
+
+        let packet = make_test_packet(vec![(DamageType::Physical, 30.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!(result.total_damage > 0.0);
+        assert!((result.life_leeched - 0.0).abs() < f64::EPSILON);
+        assert!((result.mana_leeched - 0.0).abs() < f64::EPSILON);
+
+        result.apply_leech_to(&mut attacker);
+        assert!((attacker.current_life - 10.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_element_max_resistance() {
+        let mut defender = StatBlock::new();
+        defender.current_life = 1000.0;
+        defender.max_fire_resistance = 90.0;
+        defender.fire_resistance.base = 80.0;
+
+        let packet = make_test_packet(vec![(DamageType::Fire, 100.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!((result.total_damage - 20.0).abs() < 0.01);
+
+        // Overcapped: limited to 90%, and penetration is half effective at the cap
+        defender.fire_resistance.base = 120.0;
+        let mut packet = make_test_packet(vec![(DamageType::Fire, 100.0)]);
+        packet.fire_pen = 20.0;
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!((result.total_damage - 20.0).abs() < 0.01);
+    }
+
     #[test]
     fn test_armour_reduction() {
         let mut defender = StatBlock::new();
@@ -295,6 +1221,120 @@ mod tests {
         assert!((result.total_damage - 1000.0).abs() < 1.0);
     }
 
+    #[test]
+    fn test_evasion_cap_mode_mitigates_portion() {
+        use rand::SeedableRng;
+
+        let mut defender = StatBlock::new();
+        defender.current_life = 5000.0;
+        defender.evasion.base = 1000.0;
+
+        let mut packet = make_test_packet(vec![(DamageType::Fire, 1500.0)]);
+        packet.accuracy = 2000.0;
+
+        // Cap is 1000: the hit lands for part of its damage
+        let mut rng = rand::rngs::StdRng::seed_from_u64(9);
+        let (_, result) = resolve_damage_with_rng(&defender, &packet, &mut rng);
+        assert!(!result.was_evaded);
+        assert!((result.total_damage - 1000.0).abs() < 1.0);
+        assert!((result.damage_prevented_by_evasion - 500.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_evasion_chance_mode_mostly_misses() {
+        use rand::SeedableRng;
+
+        let mut defender = StatBlock::new();
+        defender.current_life = 5000.0;
+        defender.evasion.base = 9000.0;
+        defender.evasion_mode = EvasionMode::Chance;
+
+        let mut packet = make_test_packet(vec![(DamageType::Fire, 1500.0)]);
+        packet.accuracy = 1000.0;
+
+        // 90% evade chance
+        let mut rng = rand::rngs::StdRng::seed_from_u64(21);
+        let rolls = 2000;
+        let mut evaded = 0;
+        for _ in 0..rolls {
+            let (_, result) = resolve_damage_with_rng(&defender, &packet, &mut rng);
+            if result.was_evaded {
+                assert!(result.total_damage.abs() < f64::EPSILON);
+                evaded += 1;
+            } else {
+                // Landed hits are not capped in chance mode
+                assert!((result.total_damage - 1500.0).abs() < 1.0);
+            }
+        }
+        let rate = evaded as f64 / rolls as f64;
+        assert!((rate - 0.9).abs() < 0.03);
+    }
+
+    #[test]
+    fn test_accuracy_roll_to_hit() {
+        use rand::SeedableRng;
+
+        let mut constants = GameConstants::default();
+        constants.hit.roll_accuracy = true;
+
+        let mut defender = StatBlock::new();
+        defender.current_life = 1_000_000.0;
+        defender.max_life.base = 1_000_000.0;
+        defender.evasion.base = 5000.0;
+
+        let mut packet = make_test_packet(vec![(DamageType::Fire, 100.0)]);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(31);
+        let rolls = 2000;
+        let mut miss_rate = |accuracy: f64| {
+            packet.accuracy = accuracy;
+            let mut misses = 0;
+            for _ in 0..rolls {
+                let (_, result) =
+                    resolve_damage_with_constants(&defender, &packet, &constants, &mut rng);
+                if result.missed {
+                    assert!(result.total_damage.abs() < f64::EPSILON);
+                    misses += 1;
+                }
+            }
+            misses as f64 / rolls as f64
+        };
+
+        // High accuracy always hits
+        assert!(miss_rate(20_000.0).abs() < f64::EPSILON);
+        // Low accuracy mostly misses (about 10.7% to hit)
+        let expected = 1.0 - calculate_hit_chance(200.0, 5000.0);
+        assert!((miss_rate(200.0) - expected).abs() < 0.03);
+    }
+
+    #[test]
+    fn test_damage_taken_reduction_applies_to_hits() {
+        let mut defender = StatBlock::new();
+        defender.current_life = 1000.0;
+        defender.damage_taken_reduction = 0.10;
+
+        let packet = make_test_packet(vec![(DamageType::Chaos, 100.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!((result.total_damage - 90.0).abs() < 0.01);
+        assert!((result.damage_reduced_by_damage_taken - 10.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_overwhelm_bypasses_physical_reduction() {
+        let mut defender = StatBlock::new();
+        defender.current_life = 1000.0;
+        defender.physical_damage_reduction = 30.0;
+
+        let packet = make_test_packet(vec![(DamageType::Physical, 100.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!((result.total_damage - 70.0).abs() < 0.01);
+
+        // Overwhelm 20% leaves 10% effective reduction
+        let mut overwhelming = packet.clone();
+        overwhelming.overwhelm = 20.0;
+        let (_, result) = resolve_damage(&defender, &overwhelming);
+        assert!((result.total_damage - 90.0).abs() < 0.01);
+    }
+
     #[test]
     fn test_es_absorbs_first() {
         let mut defender = StatBlock::new();
@@ -312,6 +1352,135 @@ mod tests {
         assert!((new_defender.current_life - 75.0).abs() < 1.0);
     }
 
+    #[test]
+    fn test_culling_strike() {
+        let mut defender = StatBlock::new();
+        defender.max_life.base = 100.0;
+        defender.current_life = 100.0;
+
+        // Left at 8% with a 10% cull: killed
+        let mut packet = make_test_packet(vec![(DamageType::Fire, 92.0)]);
+        packet.culling_strike_threshold = 0.10;
+        let (new_defender, result) = resolve_damage(&defender, &packet);
+        assert!(result.was_culled);
+        assert!(result.is_killing_blow);
+        assert!(new_defender.current_life.abs() < f64::EPSILON);
+        assert!(result.summary().contains("culled"));
+
+        // Left at 12%: survives
+        let mut packet = make_test_packet(vec![(DamageType::Fire, 88.0)]);
+        packet.culling_strike_threshold = 0.10;
+        let (new_defender, result) = resolve_damage(&defender, &packet);
+        assert!(!result.was_culled);
+        assert!(!result.is_killing_blow);
+        assert!((new_defender.current_life - 12.0).abs() < 0.01);
+
+        // Disabled by default
+        let packet = make_test_packet(vec![(DamageType::Fire, 92.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!(!result.is_killing_blow);
+    }
+
+    #[test]
+    fn test_chaos_bypasses_es() {
+        let mut defender = StatBlock::new();
+        defender.current_life = 100.0;
+        defender.current_energy_shield = 50.0;
+        defender.max_energy_shield = 50.0;
+
+        // Fire depletes ES first
+        let (new_defender, result) =
+            resolve_damage(&defender, &make_test_packet(vec![(DamageType::Fire, 40.0)]));
+        assert!((result.damage_blocked_by_es - 40.0).abs() < 0.01);
+        assert!(result.damage_bypassed_es.abs() < f64::EPSILON);
+        assert!((new_defender.current_energy_shield - 10.0).abs() < 0.01);
+        assert!((new_defender.current_life - 100.0).abs() < 0.01);
+
+        // An equal chaos hit skips ES and hits life
+        let (new_defender, result) = resolve_damage(
+            &defender,
+            &make_test_packet(vec![(DamageType::Chaos, 40.0)]),
+        );
+        assert!(result.damage_blocked_by_es.abs() < f64::EPSILON);
+        assert!((result.damage_bypassed_es - 40.0).abs() < 0.01);
+        assert!((new_defender.current_energy_shield - 50.0).abs() < 0.01);
+        assert!((new_defender.current_life - 60.0).abs() < 0.01);
+
+        // Mixed hit: only the fire part is absorbed
+        let packet = make_test_packet(vec![(DamageType::Fire, 30.0), (DamageType::Chaos, 20.0)]);
+        let (new_defender, result) = resolve_damage(&defender, &packet);
+        assert!((result.damage_blocked_by_es - 30.0).abs() < 0.01);
+        assert!((result.damage_bypassed_es - 20.0).abs() < 0.01);
+        assert!((new_defender.current_life - 80.0).abs() < 0.01);
+
+        // With no bypass configured, chaos is absorbed too
+        let mut constants = GameConstants::default();
+        constants.energy_shield.bypass.clear();
+        let mut rng = rand::thread_rng();
+        let chaos = make_test_packet(vec![(DamageType::Chaos, 40.0)]);
+        let (_, result) = resolve_damage_with_constants(&defender, &chaos, &constants, &mut rng);
+        assert!((result.damage_blocked_by_es - 40.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_damage_pool_order() {
+        let mut defender = StatBlock::new();
+        defender.current_life = 100.0;
+        defender.max_energy_shield = 40.0;
+        defender.current_energy_shield = 40.0;
+        defender.apply_guard(20.0);
+        defender.apply_ward(30.0);
+
+        let packet = make_test_packet(vec![(DamageType::Fire, 150.0)]);
+        let (new_defender, result) = resolve_damage(&defender, &packet);
+
+        // Guard (20) → ward (30) → ES (40) → life (60)
+        assert!((result.damage_absorbed_by_guard - 20.0).abs() < 0.01);
+        assert!((result.damage_absorbed_by_ward - 30.0).abs() < 0.01);
+        assert!((result.damage_blocked_by_es - 40.0).abs() < 0.01);
+        assert!((result.damage_to_life - 60.0).abs() < 0.01);
+        assert!((new_defender.current_guard - 0.0).abs() < 0.01);
+        assert!((new_defender.current_ward - 0.0).abs() < 0.01);
+        assert!((new_defender.current_energy_shield - 0.0).abs() < 0.01);
+        assert!((new_defender.current_life - 40.0).abs() < 0.01);
+
+        // A custom order consumes ES before ward and guard
+        let mut constants = GameConstants::default();
+        constants.damage_pools.order = vec![DamagePool::EnergyShield, DamagePool::Life];
+        let mut rng = rand::thread_rng();
+        let (_, result) = resolve_damage_with_constants(&defender, &packet, &constants, &mut rng);
+        assert!((result.damage_absorbed_by_guard - 0.0).abs() < 0.01);
+        assert!((result.damage_blocked_by_es - 40.0).abs() < 0.01);
+        assert!((result.damage_to_life - 110.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_missed_hits_never_crit() {
+        use rand::SeedableRng;
+
+        let mut defender = StatBlock::new();
+        defender.current_life = 1000.0;
+
+        let mut packet = make_test_packet(vec![(DamageType::Fire, 10.0)]);
+        packet.is_critical = true;
+        packet.hit_chance = 0.5;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let mut misses = 0;
+        for _ in 0..100 {
+            let (new_defender, result) = resolve_damage_with_rng(&defender, &packet, &mut rng);
+            if result.missed {
+                misses += 1;
+                assert!(!result.is_critical);
+                assert!((result.total_damage - 0.0).abs() < f64::EPSILON);
+                assert!((new_defender.current_life - 1000.0).abs() < f64::EPSILON);
+            } else {
+                assert!(result.is_critical);
+            }
+        }
+        assert!(misses > 0 && misses < 100);
+    }
+
     #[test]
     fn test_killing_blow() {
         let mut defender = StatBlock::new();
@@ -330,6 +1499,7 @@ mod tests {
     fn test_penetration() {
         let mut defender = StatBlock::new();
         defender.current_life = 200.0;
+        defender.max_fire_resistance = 100.0;
         defender.fire_resistance.base = 75.0;
 
         let mut packet = make_test_packet(vec![(DamageType::Fire, 100.0)]);
@@ -342,6 +1512,164 @@ mod tests {
         assert!((result.total_damage - 50.0).abs() < 1.0);
     }
 
+    #[test]
+    fn test_penetration_cap() {
+        use rand::SeedableRng;
+
+        let mut defender = StatBlock::new();
+        defender.current_life = 200.0;
+        defender.max_fire_resistance = 100.0;
+        defender.fire_resistance.base = 75.0;
+
+        let mut packet = make_test_packet(vec![(DamageType::Fire, 100.0)]);
+        packet.fire_pen = 40.0;
+
+        // Uncapped: 75% - 40% = 35% effective resist
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let (_, result) =
+            resolve_damage_with_constants(&defender, &packet, &GameConstants::default(), &mut rng);
+        assert!((result.total_damage - 65.0).abs() < 0.01);
+
+        // Capped at 25: 75% - 25% = 50% effective resist
+        let mut constants = GameConstants::default();
+        constants.resistances.max_penetration = Some(25.0);
+        let (_, result) = resolve_damage_with_constants(&defender, &packet, &constants, &mut rng);
+        assert!((result.total_damage - 50.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_physical_taken_as_fire() {
+        let mut defender = StatBlock::new();
+        defender.current_life = 1000.0;
+        defender.armour.base = 250.0;
+        defender.fire_resistance.base = 50.0;
+        defender.taken_as.physical_as_fire = 0.5;
+
+        let packet = make_test_packet(vec![(DamageType::Physical, 100.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+
+        // 50 physical vs 250 armour: 250 / (250 + 5 × 50) = 50% reduced
+        let physical = result.damage_of_type(DamageType::Physical).unwrap();
+        assert!((physical.raw_amount - 50.0).abs() < 0.01);
+        assert!((physical.final_amount - 25.0).abs() < 0.01);
+
+        // 50 fire vs 50% fire resistance
+        let fire = result.damage_of_type(DamageType::Fire).unwrap();
+        assert!((fire.raw_amount - 50.0).abs() < 0.01);
+        assert!((fire.final_amount - 25.0).abs() < 0.01);
+
+        assert_eq!(result.damage_taken_as.len(), 1);
+        assert_eq!(result.damage_taken_as[0].from, DamageType::Physical);
+        assert_eq!(result.damage_taken_as[0].to, DamageType::Fire);
+        assert!((result.damage_taken_as[0].amount - 50.0).abs() < 0.01);
+        assert!((result.total_damage - 50.0).abs() < 0.01);
+
+        // Without the conversion, armour faces the full hit
+        defender.taken_as = Default::default();
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!(result.damage_taken_as.is_empty());
+        assert!(result.damage_of_type(DamageType::Fire).is_none());
+        assert!((result.total_damage - 100.0 * 2.0 / 3.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_one_packet_against_several_targets() {
+        use rand::SeedableRng;
+
+        let mut enemies: Vec<StatBlock> = [0.0, 500.0, 2000.0]
+            .iter()
+            .map(|armour| {
+                let mut enemy = StatBlock::new();
+                enemy.current_life = 1000.0;
+                enemy.armour.base = *armour;
+                enemy
+            })
+            .collect();
+
+        let packet = make_test_packet(vec![(DamageType::Physical, 60.0), (DamageType::Fire, 40.0)]);
+        let mut targets: Vec<&mut StatBlock> = enemies.iter_mut().collect();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(4);
+        let results = resolve_damage_multi_with_rng(
+            &mut targets,
+            &packet,
+            DotRegistry::shared_defaults(),
+            &mut rng,
+        );
+
+        assert_eq!(results.len(), 3);
+        // More armour, less damage; fire is unaffected by armour
+        assert!(results[0].total_damage > results[1].total_damage);
+        assert!(results[1].total_damage > results[2].total_damage);
+        for (enemy, result) in enemies.iter().zip(&results) {
+            assert!(
+                (result
+                    .damage_of_type(DamageType::Fire)
+                    .unwrap()
+                    .final_amount
+                    - 40.0)
+                    .abs()
+                    < 0.01
+            );
+            assert!((enemy.current_life - (1000.0 - result.total_damage)).abs() < 0.01);
+        }
+
+        // Chains scale per jump and stop after chain_count
+        let mut chained = packet.clone();
+        chained.chain_count = 1;
+        chained.chain_damage_multiplier = 0.5;
+        let mut fresh: Vec<StatBlock> = (0..3).map(|_| StatBlock::new()).collect();
+        let mut targets: Vec<&mut StatBlock> = fresh.iter_mut().collect();
+        let results = resolve_damage_multi_with_rng(
+            &mut targets,
+            &chained,
+            DotRegistry::shared_defaults(),
+            &mut rng,
+        );
+        assert!((results[0].total_raw_damage() - 100.0).abs() < 0.01);
+        assert!((results[1].total_raw_damage() - 50.0).abs() < 0.01);
+        assert!(results[2].total_raw_damage().abs() < 0.01);
+    }
+
+    #[test]
+    fn test_percent_and_flat_penetration_vs_capped_resistance() {
+        let mut defender = StatBlock::new();
+        defender.current_life = 1000.0;
+        defender.fire_resistance.base = 90.0;
+        defender.max_fire_resistance = 75.0;
+
+        // 25% of 75% ignored = 56.25%, then 6.25 flat pen = 50%
+        let mut packet = make_test_packet(vec![(DamageType::Fire, 100.0)]);
+        packet.fire_pen_percent = 0.25;
+        packet.fire_pen = 6.25;
+
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!((result.total_damage - 50.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_resistance_reduction_vs_penetration() {
+        let mut defender = StatBlock::new();
+        defender.current_life = 1000.0;
+        defender.fire_resistance.base = 50.0;
+
+        let mut pen_packet = make_test_packet(vec![(DamageType::Fire, 100.0)]);
+        pen_packet.fire_pen = 20.0;
+
+        let mut reduction_packet = make_test_packet(vec![(DamageType::Fire, 100.0)]);
+        reduction_packet.resistance_reduction_applied.fire = 20.0;
+
+        let (after_pen, pen_result) = resolve_damage(&defender, &pen_packet);
+        let (after_reduction, reduction_result) = resolve_damage(&defender, &reduction_packet);
+
+        // Both hits see 30% effective fire resistance
+        assert!((pen_result.total_damage - 70.0).abs() < 0.01);
+        assert!((reduction_result.total_damage - 70.0).abs() < 0.01);
+
+        // Only the applied reduction lingers on the target
+        assert!((after_pen.effective_resistance(DamageType::Fire) - 50.0).abs() < 0.01);
+        assert!((after_reduction.effective_resistance(DamageType::Fire) - 30.0).abs() < 0.01);
+    }
+
     #[test]
     fn test_multiple_damage_types() {
         let mut defender = StatBlock::new();
@@ -1,7 +1,9 @@
 //! CombatResult - Outcome of damage resolution
 
-use crate::types::Effect;
-use loot_core::types::DamageType;
+use crate::defense::DamageReroute;
+use crate::stat_block::StatBlock;
+use crate::types::{DamagePool, Effect};
+use loot_core::types::{DamageType, StatusEffect};
 use serde::{Deserialize, Serialize};
 
 /// Result of applying a DamagePacket to a StatBlock
@@ -12,20 +14,60 @@ pub struct CombatResult {
     pub damage_taken: Vec<DamageTaken>,
     /// Total damage after all mitigation
     pub total_damage: f64,
+    /// Incoming damage the defender took as another type (see `StatBlock::taken_as`)
+    #[serde(default)]
+    pub damage_taken_as: Vec<DamageReroute>,
 
     // === Mitigation Info ===
+    /// Damage absorbed by guard
+    #[serde(default)]
+    pub damage_absorbed_by_guard: f64,
+    /// Damage absorbed by ward
+    #[serde(default)]
+    pub damage_absorbed_by_ward: f64,
     /// Damage absorbed by energy shield
     pub damage_blocked_by_es: f64,
+    /// Damage of types that bypass energy shield (see `EnergyShieldConstants::bypass`)
+    #[serde(default)]
+    pub damage_bypassed_es: f64,
+    /// Damage dealt to life
+    #[serde(default)]
+    pub damage_to_life: f64,
     /// Damage reduced by armour
     pub damage_reduced_by_armour: f64,
     /// Damage reduced by resistances
     pub damage_reduced_by_resists: f64,
     /// Damage prevented by evasion cap
     pub damage_prevented_by_evasion: f64,
+    /// Damage reduced by the defender's general damage taken reduction
+    #[serde(default)]
+    pub damage_reduced_by_damage_taken: f64,
+    /// Extra damage taken because the defender was shocked
+    #[serde(default)]
+    pub damage_added_by_shock: f64,
+
+    // === Attacker Recovery ===
+    /// Life the attacker leeches from this hit
+    #[serde(default)]
+    pub life_leeched: f64,
+    /// Mana the attacker leeches from this hit
+    #[serde(default)]
+    pub mana_leeched: f64,
+    /// Life the attacker gains on hit (life on hit × hits, only if damage was dealt)
+    #[serde(default)]
+    pub life_gained_on_hit: f64,
+
+    // === Attacker Damage ===
+    /// Physical damage reflected back to the attacker (caller applies it)
+    #[serde(default)]
+    pub reflected_damage: f64,
 
     // === Effects Applied ===
     /// Effects that were applied (unified Effect system)
     pub effects_applied: Vec<Effect>,
+    /// Status effects that were not applied because the defender is immune
+    #[serde(default)]
+    pub immune_effects: Vec<StatusEffect>,
 
     // === State Changes ===
     /// ES before damage
@@ -38,10 +80,50 @@ pub struct CombatResult {
     pub life_after: f64,
 
     // === Flags ===
+    /// Whether the hit missed (no damage or effects applied)
+    #[serde(default)]
+    pub missed: bool,
+    /// Whether the defender evaded the hit in chance mode (no damage or effects applied)
+    #[serde(default)]
+    pub was_evaded: bool,
+    /// Whether at least one hit of the packet was blocked
+    #[serde(default)]
+    pub was_blocked: bool,
+    /// Number of the packet's hits that were blocked
+    #[serde(default)]
+    pub hits_blocked: u32,
+    /// Raw damage of each landed (unblocked) hit, before mitigation
+    #[serde(default)]
+    pub hit_damages: Vec<f64>,
+    /// Whether the hit landed as a critical strike (never true for a miss)
+    #[serde(default)]
+    pub is_critical: bool,
     /// Whether this was a killing blow
     pub is_killing_blow: bool,
+    /// Whether the kill came from culling strike rather than damage
+    #[serde(default)]
+    pub was_culled: bool,
+    /// Damage beyond what was needed to kill (how far below zero life would have gone)
+    #[serde(default)]
+    pub overkill: f64,
     /// Whether the evasion cap was triggered
     pub triggered_evasion_cap: bool,
+
+    // === Event Log ===
+    /// What happened during resolution, in order, for UIs and tests
+    #[serde(default)]
+    pub events: Vec<CombatEvent>,
+}
+
+/// Lowercase damage type name for summaries
+fn damage_type_name(damage_type: DamageType) -> &'static str {
+    match damage_type {
+        DamageType::Physical => "physical",
+        DamageType::Fire => "fire",
+        DamageType::Cold => "cold",
+        DamageType::Lightning => "lightning",
+        DamageType::Chaos => "chaos",
+    }
 }
 
 impl Default for CombatResult {
@@ -49,17 +131,38 @@ impl Default for CombatResult {
         CombatResult {
             damage_taken: Vec::new(),
             total_damage: 0.0,
+            damage_taken_as: Vec::new(),
+            damage_absorbed_by_guard: 0.0,
+            damage_absorbed_by_ward: 0.0,
             damage_blocked_by_es: 0.0,
+            damage_bypassed_es: 0.0,
+            damage_to_life: 0.0,
             damage_reduced_by_armour: 0.0,
             damage_reduced_by_resists: 0.0,
             damage_prevented_by_evasion: 0.0,
+            damage_reduced_by_damage_taken: 0.0,
+            damage_added_by_shock: 0.0,
+            life_leeched: 0.0,
+            mana_leeched: 0.0,
+            life_gained_on_hit: 0.0,
+            reflected_damage: 0.0,
             effects_applied: Vec::new(),
+            immune_effects: Vec::new(),
             es_before: 0.0,
             es_after: 0.0,
             life_before: 0.0,
             life_after: 0.0,
+            missed: false,
+            was_evaded: false,
+            was_blocked: false,
+            hits_blocked: 0,
+            hit_damages: Vec::new(),
+            is_critical: false,
             is_killing_blow: false,
+            was_culled: false,
+            overkill: 0.0,
             triggered_evasion_cap: false,
+            events: Vec::new(),
         }
     }
 }
@@ -85,18 +188,110 @@ impl CombatResult {
         self.damage_taken.iter().find(|d| d.damage_type == damage_type)
     }
 
-    /// Get a summary string
+    /// One-line summary of the outcome
+    ///
+    /// e.g. "Dealt 142 damage (88 physical, 54 fire), crit, applied Poison".
+    /// See [`CombatResult::detailed_summary`] for where the damage went.
     pub fn summary(&self) -> String {
+        if self.missed {
+            return "Missed".to_string();
+        }
+        if self.was_evaded {
+            return "Evaded".to_string();
+        }
+
+        let mut parts = Vec::new();
+
+        let per_type: Vec<String> = self
+            .damage_taken
+            .iter()
+            .filter(|d| d.final_amount > 0.0)
+            .map(|d| format!("{:.0} {}", d.final_amount, damage_type_name(d.damage_type)))
+            .collect();
+        if self.total_damage > 0.0 && per_type.is_empty() {
+            parts.push(format!("Dealt {:.0} damage", self.total_damage));
+        } else if self.total_damage > 0.0 {
+            parts.push(format!(
+                "Dealt {:.0} damage ({})",
+                self.total_damage,
+                per_type.join(", ")
+            ));
+        } else {
+            parts.push("No damage".to_string());
+        }
+
+        if self.is_critical {
+            parts.push("crit".to_string());
+        }
+
+        if self.was_blocked {
+            parts.push("blocked".to_string());
+        }
+
+        if !self.effects_applied.is_empty() {
+            let names: Vec<&str> = self
+                .effects_applied
+                .iter()
+                .map(|e| e.name.as_str())
+                .collect();
+            parts.push(format!("applied {}", names.join(", ")));
+        }
+
+        if self.was_culled {
+            parts.push("culled".to_string());
+        }
+
+        if self.is_killing_blow {
+            parts.push("FATAL".to_string());
+        }
+
+        parts.join(", ")
+    }
+
+    /// Summary followed by where the damage went: pools, mitigation and immunities
+    pub fn detailed_summary(&self) -> String {
+        let summary = self.summary();
+        if self.missed || self.was_evaded {
+            return summary;
+        }
+
         let mut parts = Vec::new();
 
-        if self.total_damage > 0.0 {
-            parts.push(format!("{:.0} damage taken", self.total_damage));
+        for reroute in &self.damage_taken_as {
+            parts.push(format!(
+                "{:.0} {} taken as {}",
+                reroute.amount,
+                damage_type_name(reroute.from),
+                damage_type_name(reroute.to)
+            ));
+        }
+
+        if self.damage_absorbed_by_guard > 0.0 {
+            parts.push(format!(
+                "{:.0} absorbed by guard",
+                self.damage_absorbed_by_guard
+            ));
+        }
+
+        if self.damage_absorbed_by_ward > 0.0 {
+            parts.push(format!(
+                "{:.0} absorbed by ward",
+                self.damage_absorbed_by_ward
+            ));
         }
 
         if self.damage_blocked_by_es > 0.0 {
             parts.push(format!("{:.0} blocked by ES", self.damage_blocked_by_es));
         }
 
+        if self.damage_bypassed_es > 0.0 {
+            parts.push(format!("{:.0} bypassed ES", self.damage_bypassed_es));
+        }
+
+        if self.damage_to_life > 0.0 {
+            parts.push(format!("{:.0} to life", self.damage_to_life));
+        }
+
         if self.damage_reduced_by_armour > 0.0 {
             parts.push(format!("{:.0} reduced by armour", self.damage_reduced_by_armour));
         }
@@ -109,17 +304,43 @@ impl CombatResult {
             parts.push(format!("{:.0} evaded", self.damage_prevented_by_evasion));
         }
 
-        if self.is_killing_blow {
-            parts.push("FATAL".to_string());
+        if self.damage_reduced_by_damage_taken > 0.0 {
+            parts.push(format!(
+                "{:.0} reduced by damage taken",
+                self.damage_reduced_by_damage_taken
+            ));
+        }
+
+        if !self.immune_effects.is_empty() {
+            let names: Vec<String> = self
+                .immune_effects
+                .iter()
+                .map(|s| format!("{:?}", s))
+                .collect();
+            parts.push(format!("immune to {}", names.join(", ")));
         }
 
         if parts.is_empty() {
-            "No damage".to_string()
+            summary
         } else {
-            parts.join(", ")
+            format!("{}; {}", summary, parts.join(", "))
         }
     }
 
+    /// Heal the attacker by the life and mana leeched from this hit,
+    /// plus any life gained on hit
+    ///
+    /// Recovery is capped at the attacker's maximum life and unreserved mana.
+    /// With a `max_mana_leech_rate`, mana is queued and restored over time
+    /// by `StatBlock::tick_regen` instead (see `StatBlock::leech_mana`).
+    pub fn apply_leech_to(&self, attacker: &mut StatBlock) {
+        let life = self.life_leeched + self.life_gained_on_hit;
+        if life > 0.0 {
+            attacker.heal(life);
+        }
+        attacker.leech_mana(self.mana_leeched);
+    }
+
     /// Get life change
     pub fn life_change(&self) -> f64 {
         self.life_after - self.life_before
@@ -131,6 +352,48 @@ impl CombatResult {
     }
 }
 
+/// A single step of damage resolution, recorded in `CombatResult::events`
+///
+/// Events are emitted in order: miss/evade/block first, then the damage dealt
+/// per type, the mitigation that applied, pool absorption, the killing blow,
+/// and finally ailments.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum CombatEvent {
+    /// The hit missed
+    Missed,
+    /// The defender evaded the hit (chance-mode evasion)
+    Evaded,
+    /// Some of the packet's hits were blocked
+    Blocked { hits_blocked: u32, hits: u32 },
+    /// Damage of one type dealt after mitigation
+    HitDealt {
+        damage_type: DamageType,
+        amount: f64,
+        is_critical: bool,
+    },
+    /// Damage removed by a defense
+    Mitigated { kind: MitigationKind, amount: f64 },
+    /// Damage taken by a pool (guard, ward, ES or life)
+    Absorbed { pool: DamagePool, amount: f64 },
+    /// A status effect was applied to the defender
+    AilmentApplied { status: StatusEffect, duration: f64 },
+    /// The defender was immune to a status effect
+    AilmentImmune { status: StatusEffect },
+    /// The hit killed the defender
+    KillingBlow { overkill: f64 },
+}
+
+/// The defense behind a `CombatEvent::Mitigated` event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MitigationKind {
+    Resistance,
+    Armour,
+    EvasionCap,
+    DamageTaken,
+}
+
 /// Damage breakdown for a single damage type
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DamageTaken {
@@ -142,6 +405,12 @@ pub struct DamageTaken {
     pub mitigated_amount: f64,
     /// Final damage after mitigation
     pub final_amount: f64,
+    /// Defender resistance used for this hit (0 for physical)
+    #[serde(default)]
+    pub resistance_applied: f64,
+    /// Attacker penetration used for this hit (0 for physical)
+    #[serde(default)]
+    pub penetration_applied: f64,
 }
 
 impl DamageTaken {
@@ -152,9 +421,18 @@ impl DamageTaken {
             raw_amount: raw,
             mitigated_amount: mitigated,
             final_amount: final_dmg,
+            resistance_applied: 0.0,
+            penetration_applied: 0.0,
         }
     }
 
+    /// Record the resistance and penetration used to mitigate this entry
+    pub fn with_resistance(mut self, resistance: f64, penetration: f64) -> Self {
+        self.resistance_applied = resistance;
+        self.penetration_applied = penetration;
+        self
+    }
+
     /// Get mitigation percentage
     pub fn mitigation_percent(&self) -> f64 {
         if self.raw_amount <= 0.0 {
@@ -203,7 +481,54 @@ mod tests {
 
         let summary = result.summary();
         assert!(summary.contains("100 damage"));
-        assert!(summary.contains("armour"));
+        assert!(result.detailed_summary().contains("armour"));
+    }
+
+    #[test]
+    fn test_summary_lists_types_crit_and_ailments() {
+        let mut result = CombatResult::new();
+        result
+            .damage_taken
+            .push(DamageTaken::new(DamageType::Physical, 100.0, 12.0, 88.0));
+        result
+            .damage_taken
+            .push(DamageTaken::new(DamageType::Fire, 60.0, 6.0, 54.0));
+        result.total_damage = 142.0;
+        result.is_critical = true;
+        result
+            .effects_applied
+            .push(Effect::poison(20.0, "attacker"));
+        assert_eq!(
+            result.summary(),
+            "Dealt 142 damage (88 physical, 54 fire), crit, applied Poison"
+        );
+
+        result.damage_reduced_by_resists = 6.0;
+        result.damage_blocked_by_es = 42.0;
+        result.damage_to_life = 100.0;
+        assert_eq!(
+            result.detailed_summary(),
+            "Dealt 142 damage (88 physical, 54 fire), crit, applied Poison; \
+             42 blocked by ES, 100 to life, 6 reduced by resists"
+        );
+    }
+
+    #[test]
+    fn test_summary_misses_and_empty_hits() {
+        let mut result = CombatResult::new();
+        assert_eq!(result.summary(), "No damage");
+        assert_eq!(result.detailed_summary(), "No damage");
+
+        result.was_blocked = true;
+        result.immune_effects.push(StatusEffect::Freeze);
+        assert_eq!(result.summary(), "No damage, blocked");
+        assert_eq!(
+            result.detailed_summary(),
+            "No damage, blocked; immune to Freeze"
+        );
+
+        result.missed = true;
+        assert_eq!(result.detailed_summary(), "Missed");
     }
 
     #[test]
@@ -215,4 +540,17 @@ mod tests {
         let summary = result.summary();
         assert!(summary.contains("FATAL"));
     }
+
+    #[test]
+    fn test_combat_event_serializes_tagged() {
+        let event = CombatEvent::Mitigated {
+            kind: MitigationKind::Armour,
+            amount: 12.0,
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"event\":\"mitigated\""));
+        assert!(json.contains("\"kind\":\"armour\""));
+        let back: CombatEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, event);
+    }
 }
@@ -0,0 +1,154 @@
+//! Skill rotations - Ordered, conditional skill selection for AI and simulation
+
+use crate::damage::DamagePacketGenerator;
+use crate::stat_block::StatBlock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Condition that must hold for a rotation entry to be used
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RotationCondition {
+    /// Always usable
+    Always,
+    /// Target's life is below a percentage (30.0 = 30%)
+    TargetLifeBelow { percent: f64 },
+    /// Target's life is at or above a percentage
+    TargetLifeAbove { percent: f64 },
+    /// Attacker's life is below a percentage
+    AttackerLifeBelow { percent: f64 },
+    /// Attacker's mana is at or above a percentage
+    AttackerManaAbove { percent: f64 },
+}
+
+impl RotationCondition {
+    /// Check whether the condition holds for an attacker and target
+    pub fn is_met(&self, attacker: &StatBlock, target: &StatBlock) -> bool {
+        match self {
+            RotationCondition::Always => true,
+            RotationCondition::TargetLifeBelow { percent } => target.life_percent() < *percent,
+            RotationCondition::TargetLifeAbove { percent } => target.life_percent() >= *percent,
+            RotationCondition::AttackerLifeBelow { percent } => attacker.life_percent() < *percent,
+            RotationCondition::AttackerManaAbove { percent } => attacker.mana_percent() >= *percent,
+        }
+    }
+}
+
+/// A skill in a rotation with the condition for using it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationEntry {
+    /// Skill identifier
+    pub skill_id: String,
+    /// Condition for using this skill
+    #[serde(default = "default_condition")]
+    pub condition: RotationCondition,
+}
+
+fn default_condition() -> RotationCondition {
+    RotationCondition::Always
+}
+
+/// An ordered priority list of skills
+///
+/// The first entry whose condition holds is used, so conditional skills
+/// (e.g., executes) should be listed before fallbacks.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Rotation {
+    #[serde(default)]
+    pub entries: Vec<RotationEntry>,
+}
+
+impl Rotation {
+    /// Create an empty rotation
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a skill that is always usable
+    pub fn with_skill(self, skill_id: impl Into<String>) -> Self {
+        self.with_conditional(skill_id, RotationCondition::Always)
+    }
+
+    /// Add a skill used only when its condition holds
+    pub fn with_conditional(
+        mut self,
+        skill_id: impl Into<String>,
+        condition: RotationCondition,
+    ) -> Self {
+        self.entries.push(RotationEntry {
+            skill_id: skill_id.into(),
+            condition,
+        });
+        self
+    }
+}
+
+/// Pick the next skill from a rotation
+///
+/// Returns the first skill whose condition holds and which exists in `skills`,
+/// or `None` if no entry is usable.
+pub fn next_skill<'a>(
+    rotation: &Rotation,
+    skills: &'a HashMap<String, DamagePacketGenerator>,
+    attacker: &StatBlock,
+    target: &StatBlock,
+) -> Option<&'a DamagePacketGenerator> {
+    rotation
+        .entries
+        .iter()
+        .filter(|entry| entry.condition.is_met(attacker, target))
+        .find_map(|entry| skills.get(&entry.skill_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_skills() -> HashMap<String, DamagePacketGenerator> {
+        let mut skills = HashMap::new();
+        for id in ["strike", "execute"] {
+            skills.insert(
+                id.to_string(),
+                DamagePacketGenerator {
+                    id: id.to_string(),
+                    name: id.to_string(),
+                    ..Default::default()
+                },
+            );
+        }
+        skills
+    }
+
+    #[test]
+    fn test_rotation_switches_to_execute() {
+        let skills = test_skills();
+        let rotation = Rotation::new()
+            .with_conditional(
+                "execute",
+                RotationCondition::TargetLifeBelow { percent: 30.0 },
+            )
+            .with_skill("strike");
+
+        let attacker = StatBlock::new();
+        let mut target = StatBlock::new();
+
+        // Full life: fall back to strike
+        let skill = next_skill(&rotation, &skills, &attacker, &target).unwrap();
+        assert_eq!(skill.id, "strike");
+
+        // Below 30%: execute
+        target.current_life = target.computed_max_life() * 0.2;
+        let skill = next_skill(&rotation, &skills, &attacker, &target).unwrap();
+        assert_eq!(skill.id, "execute");
+    }
+
+    #[test]
+    fn test_rotation_skips_unknown_skills() {
+        let skills = test_skills();
+        let rotation = Rotation::new().with_skill("missing").with_skill("strike");
+
+        let block = StatBlock::new();
+        let skill = next_skill(&rotation, &skills, &block, &block).unwrap();
+        assert_eq!(skill.id, "strike");
+    }
+}
@@ -1,5 +1,8 @@
 //! Game constants configuration
 
+use crate::defense::constants::MAX_PHYS_REDUCTION;
+use crate::types::DamagePool;
+use loot_core::types::DamageType;
 use serde::{Deserialize, Serialize};
 
 /// Tunable game constants
@@ -10,6 +13,16 @@ pub struct GameConstants {
     pub crit: CritConstants,
     pub leech: LeechConstants,
     pub energy_shield: EnergyShieldConstants,
+    #[serde(default)]
+    pub ailments: AilmentConstants,
+    #[serde(default)]
+    pub damage_pools: DamagePoolConstants,
+    #[serde(default)]
+    pub scoring: ScoringConstants,
+    #[serde(default)]
+    pub attributes: AttributeConstants,
+    #[serde(default)]
+    pub hit: HitConstants,
 }
 
 impl Default for GameConstants {
@@ -20,6 +33,11 @@ impl Default for GameConstants {
             crit: CritConstants::default(),
             leech: LeechConstants::default(),
             energy_shield: EnergyShieldConstants::default(),
+            ailments: AilmentConstants::default(),
+            damage_pools: DamagePoolConstants::default(),
+            scoring: ScoringConstants::default(),
+            attributes: AttributeConstants::default(),
+            hit: HitConstants::default(),
         }
     }
 }
@@ -35,6 +53,12 @@ pub struct ResistanceConstants {
     /// Penetration effectiveness vs capped resistance
     #[serde(default = "default_pen_vs_capped")]
     pub penetration_vs_capped: f64,
+    /// Cap on total flat penetration per hit (None = uncapped)
+    #[serde(default)]
+    pub max_penetration: Option<f64>,
+    /// Seconds resistance reduction applied by a hit lasts (refreshed by each hit)
+    #[serde(default = "default_reduction_duration")]
+    pub reduction_duration: f64,
 }
 
 impl Default for ResistanceConstants {
@@ -43,6 +67,8 @@ impl Default for ResistanceConstants {
             max_cap: 100.0,
             min_value: -200.0,
             penetration_vs_capped: 0.5,
+            max_penetration: None,
+            reduction_duration: 4.0,
         }
     }
 }
@@ -56,18 +82,25 @@ fn default_min_value() -> f64 {
 fn default_pen_vs_capped() -> f64 {
     0.5
 }
+fn default_reduction_duration() -> f64 {
+    4.0
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArmourConstants {
     /// Formula constant: reduction = armour / (armour + constant * damage)
     #[serde(default = "default_damage_constant")]
     pub damage_constant: f64,
+    /// Maximum physical damage reduction from armour in percent
+    #[serde(default = "default_max_phys_reduction")]
+    pub max_reduction: f64,
 }
 
 impl Default for ArmourConstants {
     fn default() -> Self {
         ArmourConstants {
             damage_constant: 5.0,
+            max_reduction: MAX_PHYS_REDUCTION,
         }
     }
 }
@@ -76,6 +109,10 @@ fn default_damage_constant() -> f64 {
     5.0
 }
 
+fn default_max_phys_reduction() -> f64 {
+    MAX_PHYS_REDUCTION
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CritConstants {
     /// Base critical strike multiplier (1.5 = 150%)
@@ -123,20 +160,242 @@ pub struct EnergyShieldConstants {
     /// Whether ES takes damage before life
     #[serde(default = "default_damage_priority")]
     pub damage_priority: String,
+    /// Seconds after ES last absorbed damage before it starts recharging
+    #[serde(default = "default_recharge_delay")]
+    pub recharge_delay: f64,
+    /// Fraction of max ES recharged per second (0.0 = no passive recharge)
+    #[serde(default)]
+    pub recharge_rate: f64,
+    /// Damage types that skip energy shield and go on to the next pool
+    #[serde(default = "default_es_bypass")]
+    pub bypass: Vec<DamageType>,
+}
+
+impl EnergyShieldConstants {
+    /// Check if a damage type skips energy shield
+    pub fn bypasses(&self, damage_type: DamageType) -> bool {
+        self.bypass.contains(&damage_type)
+    }
 }
 
 impl Default for EnergyShieldConstants {
     fn default() -> Self {
         EnergyShieldConstants {
             damage_priority: "first".to_string(),
+            recharge_delay: 2.0,
+            recharge_rate: 0.0,
+            bypass: default_es_bypass(),
         }
     }
 }
 
+fn default_recharge_delay() -> f64 {
+    2.0
+}
+
 fn default_damage_priority() -> String {
     "first".to_string()
 }
 
+fn default_es_bypass() -> Vec<DamageType> {
+    vec![DamageType::Chaos]
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AilmentConstants {
+    /// Whether ailment status damage includes the source damage type's increased%
+    #[serde(default = "default_scale_with_damage_increases")]
+    pub scale_with_damage_increases: bool,
+    /// Increased damage taken per shock at base magnitude (0.15 = 15%)
+    #[serde(default = "default_shock_effect")]
+    pub shock_effect: f64,
+    /// Cap on total increased damage taken from shock (0.50 = 50%)
+    #[serde(default = "default_max_shock_effect")]
+    pub max_shock_effect: f64,
+    /// Action speed reduction per chill at base magnitude (0.30 = 30% slower)
+    #[serde(default = "default_chill_effect")]
+    pub chill_effect: f64,
+    /// Action speed reduction per slow at base magnitude (0.30 = 30% slower)
+    #[serde(default = "default_chill_effect")]
+    pub slow_effect: f64,
+    /// Minimum status damage, as a fraction of target max life, for any
+    /// ailment to apply (0.01 = 1%); smaller applications never apply
+    #[serde(default)]
+    pub min_effect_fraction: f64,
+    /// Status damage, as a fraction of target max life, needed for an
+    /// ailment to apply at full magnitude and DoT (0.0 = always full).
+    /// Smaller applications scale down proportionally.
+    #[serde(default)]
+    pub full_effect_fraction: f64,
+}
+
+impl AilmentConstants {
+    /// How strongly an ailment with `status_damage` applies to a target
+    ///
+    /// Returns `None` below `min_effect_fraction`, otherwise a magnitude
+    /// multiplier that reaches 1.0 at `full_effect_fraction` of max life.
+    pub fn effect_scale(&self, status_damage: f64, target_max_life: f64) -> Option<f64> {
+        if target_max_life <= 0.0 {
+            return Some(1.0);
+        }
+        let fraction = status_damage / target_max_life;
+        if fraction < self.min_effect_fraction {
+            return None;
+        }
+        if self.full_effect_fraction <= 0.0 {
+            return Some(1.0);
+        }
+        Some((fraction / self.full_effect_fraction).min(1.0))
+    }
+}
+
+impl Default for AilmentConstants {
+    fn default() -> Self {
+        AilmentConstants {
+            scale_with_damage_increases: true,
+            shock_effect: default_shock_effect(),
+            max_shock_effect: default_max_shock_effect(),
+            chill_effect: default_chill_effect(),
+            slow_effect: default_chill_effect(),
+            min_effect_fraction: 0.0,
+            full_effect_fraction: 0.0,
+        }
+    }
+}
+
+fn default_scale_with_damage_increases() -> bool {
+    true
+}
+
+fn default_shock_effect() -> f64 {
+    0.15
+}
+
+fn default_max_shock_effect() -> f64 {
+    0.50
+}
+
+fn default_chill_effect() -> f64 {
+    0.30
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DamagePoolConstants {
+    /// Order in which pools absorb hit damage after mitigation.
+    /// Life absorbs all remaining damage, so pools listed after it are never reached.
+    #[serde(default = "DamagePool::default_order")]
+    pub order: Vec<DamagePool>,
+}
+
+impl Default for DamagePoolConstants {
+    fn default() -> Self {
+        DamagePoolConstants {
+            order: DamagePool::default_order(),
+        }
+    }
+}
+
+/// Stats derived from attributes
+///
+/// Each full `points_per_bonus` attributes above the character's base grant
+/// the listed bonus once.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttributeConstants {
+    /// Attribute points needed for one bonus step
+    #[serde(default = "default_points_per_bonus")]
+    pub points_per_bonus: f64,
+    /// Flat life per strength step
+    #[serde(default = "default_life_per_strength")]
+    pub life_per_strength: f64,
+    /// Increased physical damage per strength step (0.02 = 2%)
+    #[serde(default = "default_physical_increased_per_strength")]
+    pub physical_increased_per_strength: f64,
+    /// Flat accuracy per dexterity step
+    #[serde(default = "default_accuracy_per_dexterity")]
+    pub accuracy_per_dexterity: f64,
+    /// Increased evasion per dexterity step (0.02 = 2%)
+    #[serde(default = "default_evasion_increased_per_dexterity")]
+    pub evasion_increased_per_dexterity: f64,
+    /// Flat mana per intelligence step
+    #[serde(default = "default_mana_per_intelligence")]
+    pub mana_per_intelligence: f64,
+}
+
+impl Default for AttributeConstants {
+    fn default() -> Self {
+        AttributeConstants {
+            points_per_bonus: 10.0,
+            life_per_strength: 5.0,
+            physical_increased_per_strength: 0.02,
+            accuracy_per_dexterity: 20.0,
+            evasion_increased_per_dexterity: 0.02,
+            mana_per_intelligence: 5.0,
+        }
+    }
+}
+
+fn default_points_per_bonus() -> f64 {
+    10.0
+}
+fn default_life_per_strength() -> f64 {
+    5.0
+}
+fn default_physical_increased_per_strength() -> f64 {
+    0.02
+}
+fn default_accuracy_per_dexterity() -> f64 {
+    20.0
+}
+fn default_evasion_increased_per_dexterity() -> f64 {
+    0.02
+}
+fn default_mana_per_intelligence() -> f64 {
+    5.0
+}
+
+/// To-hit rolls from accuracy, separate from evasion's damage cap or evade chance
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HitConstants {
+    /// Roll `calculate_hit_chance(accuracy, evasion)` before mitigation;
+    /// a failed roll misses (no damage or effects)
+    #[serde(default)]
+    pub roll_accuracy: bool,
+}
+
+/// Weights for the heuristic offense/defense scores
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoringConstants {
+    /// Penalty per unit of crit-induced damage variance (0 = ignore consistency)
+    #[serde(default = "default_crit_variance_weight")]
+    pub crit_variance_weight: f64,
+    /// Seconds of regeneration counted towards defense score
+    #[serde(default = "default_recovery_weight")]
+    pub recovery_weight: f64,
+    /// Physical hit size used to evaluate armour
+    #[serde(default = "default_reference_hit")]
+    pub reference_hit: f64,
+}
+
+impl Default for ScoringConstants {
+    fn default() -> Self {
+        ScoringConstants {
+            crit_variance_weight: 0.25,
+            recovery_weight: 10.0,
+            reference_hit: 100.0,
+        }
+    }
+}
+
+fn default_crit_variance_weight() -> f64 {
+    0.25
+}
+fn default_recovery_weight() -> f64 {
+    10.0
+}
+fn default_reference_hit() -> f64 {
+    100.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,5 +432,7 @@ damage_priority = "first"
 
         let constants: GameConstants = toml::from_str(toml).unwrap();
         assert!((constants.resistances.max_cap - 100.0).abs() < f64::EPSILON);
+        // Missing [ailments] section falls back to defaults
+        assert!(constants.ailments.scale_with_damage_increases);
     }
 }
@@ -24,6 +24,12 @@ pub fn load_dot_configs(path: &Path) -> Result<DotRegistry, ConfigError> {
     Ok(registry)
 }
 
+/// Read the DoT configurations in a TOML file without building a registry
+pub(crate) fn read_dot_configs(path: &Path) -> Result<Vec<DotConfig>, ConfigError> {
+    let config: DotsConfig = super::load_toml(path)?;
+    Ok(config.dot_types)
+}
+
 /// Load DoT configurations from a TOML string
 pub fn parse_dot_configs(content: &str) -> Result<DotRegistry, ConfigError> {
     let config: DotsConfig = super::parse_toml(content)?;
@@ -2,10 +2,18 @@
 
 mod constants;
 mod dots;
+mod scenario;
+mod skill_tree;
 mod skills;
 
-pub use constants::GameConstants;
+pub use constants::{
+    AilmentConstants, AttributeConstants, DamagePoolConstants, EnergyShieldConstants,
+    GameConstants, HitConstants, ScoringConstants,
+};
 pub use dots::load_dot_configs;
+pub(crate) use dots::read_dot_configs;
+pub use scenario::Scenario;
+pub use skill_tree::{load_skill_tree, parse_skill_tree, SkillNodeConfig, SkillTreeConfig};
 pub use skills::{default_skills, load_skill_configs};
 
 use std::fs;
@@ -19,6 +27,8 @@ pub enum ConfigError {
     IoError(#[from] std::io::Error),
     #[error("Failed to parse TOML: {0}")]
     ParseError(#[from] toml::de::Error),
+    #[error("Failed to read or write JSON: {0}")]
+    JsonError(#[from] serde_json::Error),
     #[error("Configuration validation error: {0}")]
     ValidationError(String),
 }
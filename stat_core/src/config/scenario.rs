@@ -0,0 +1,124 @@
+//! Combat scenario loading
+//!
+//! A scenario bundles everything needed to replay a fight: the player and
+//! enemy stat blocks, the skills available, and the DoT registry.
+
+use super::ConfigError;
+use crate::damage::DamagePacketGenerator;
+use crate::dot::DotRegistry;
+use crate::stat_block::StatBlock;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// A reproducible combat setup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scenario {
+    /// Scenario name
+    #[serde(default)]
+    pub name: String,
+    /// The attacking entity
+    pub player: StatBlock,
+    /// The defending entity
+    pub enemy: StatBlock,
+    /// Skills available in this scenario
+    #[serde(default)]
+    pub skills: Vec<DamagePacketGenerator>,
+    /// DoT types used when resolving ailments (defaults if omitted)
+    #[serde(default = "DotRegistry::with_defaults")]
+    pub dot_registry: DotRegistry,
+}
+
+impl Scenario {
+    /// Create a scenario with no skills and the default DoT types
+    pub fn new(name: impl Into<String>, player: StatBlock, enemy: StatBlock) -> Self {
+        Scenario {
+            name: name.into(),
+            player,
+            enemy,
+            skills: Vec::new(),
+            dot_registry: DotRegistry::with_defaults(),
+        }
+    }
+
+    /// Add a skill to the scenario
+    pub fn with_skill(mut self, skill: DamagePacketGenerator) -> Self {
+        self.skills.push(skill);
+        self
+    }
+
+    /// Get a skill by ID
+    pub fn skill(&self, id: &str) -> Option<&DamagePacketGenerator> {
+        self.skills.iter().find(|s| s.id == id)
+    }
+
+    /// Load a scenario from a file (TOML if the extension is `.toml`, otherwise JSON)
+    ///
+    /// Both stat blocks are restored with [`StatBlock::post_load`].
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let mut scenario: Scenario = if path.extension().is_some_and(|ext| ext == "toml") {
+            super::load_toml(path)?
+        } else {
+            serde_json::from_str(&fs::read_to_string(path)?)?
+        };
+        scenario.player.post_load();
+        scenario.enemy.post_load();
+        Ok(scenario)
+    }
+
+    /// Save the scenario as JSON
+    pub fn save(&self, path: &Path) -> Result<(), ConfigError> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::{AuraSource, BuffSource};
+    use loot_core::types::StatType;
+
+    #[test]
+    fn test_scenario_round_trip() {
+        let mut player = StatBlock::with_id("player");
+        player.apply_buff(
+            BuffSource::new("rage".to_string(), "Rage".to_string(), 10.0, false).with_modifier(
+                StatType::IncreasedPhysicalDamage,
+                25.0,
+                false,
+            ),
+        );
+        player.toggle_aura(
+            "determination",
+            AuraSource::new("determination".to_string(), "Determination".to_string())
+                .with_modifier(StatType::IncreasedArmour, 50.0)
+                .with_mana_reservation(25.0),
+        );
+        let mut enemy = StatBlock::with_id("goblin");
+        enemy.fire_resistance.base = 25.0;
+        let scenario = Scenario::new("goblin_fight", player.clone(), enemy)
+            .with_skill(DamagePacketGenerator::basic_attack());
+
+        let path =
+            std::env::temp_dir().join(format!("stat_core_scenario_{}.json", std::process::id()));
+        scenario.save(&path).unwrap();
+        let loaded = Scenario::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        let loaded = loaded.unwrap();
+
+        assert_eq!(loaded.name, "goblin_fight");
+        assert_eq!(loaded.enemy.id, "goblin");
+        assert!((loaded.enemy.fire_resistance.base - 25.0).abs() < f64::EPSILON);
+        assert!(loaded.skill("basic_attack").is_some());
+        assert!(loaded.dot_registry.get("poison").is_some());
+
+        // Buffs and auras survive, and the rebuilt player matches the saved one
+        assert_eq!(loaded.player.active_buff_sources().len(), 1);
+        assert!(loaded.player.is_aura_active("determination"));
+        assert_eq!(
+            serde_json::to_string(&loaded.player).unwrap(),
+            serde_json::to_string(&player).unwrap()
+        );
+    }
+}
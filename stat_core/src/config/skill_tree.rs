@@ -0,0 +1,119 @@
+//! Skill tree node configuration loading
+
+use super::ConfigError;
+use crate::source::{NodeModifier, SkillTreeSource};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Container for skill tree node definitions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillTreeConfig {
+    #[serde(default)]
+    pub nodes: Vec<SkillNodeConfig>,
+}
+
+/// Definition of a single skill tree node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillNodeConfig {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub modifiers: Vec<NodeModifier>,
+}
+
+/// Load skill tree node definitions from a TOML file
+///
+/// Returns a `SkillTreeSource` with every node registered and none allocated.
+pub fn load_skill_tree(path: &Path) -> Result<SkillTreeSource, ConfigError> {
+    let config: SkillTreeConfig = super::load_toml(path)?;
+    build_skill_tree(config)
+}
+
+/// Load skill tree node definitions from a TOML string
+pub fn parse_skill_tree(content: &str) -> Result<SkillTreeSource, ConfigError> {
+    let config: SkillTreeConfig = super::parse_toml(content)?;
+    build_skill_tree(config)
+}
+
+fn build_skill_tree(config: SkillTreeConfig) -> Result<SkillTreeSource, ConfigError> {
+    let mut seen = HashSet::new();
+    let mut tree = SkillTreeSource::new();
+    for node in config.nodes {
+        if !seen.insert(node.id.clone()) {
+            return Err(ConfigError::ValidationError(format!(
+                "Duplicate skill tree node '{}'",
+                node.id
+            )));
+        }
+        tree.register_node(node.id, node.modifiers);
+    }
+    Ok(tree)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::StatSource;
+    use crate::stat_block::StatAccumulator;
+    use loot_core::types::StatType;
+
+    fn stat_name(stat: StatType) -> String {
+        serde_json::to_value(stat)
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_parse_skill_tree_and_allocate() {
+        let toml = format!(
+            r#"
+[[nodes]]
+id = "toughness"
+name = "Toughness"
+modifiers = [{{ stat = "{life}", value = 20.0 }}]
+
+[[nodes]]
+id = "vitality"
+name = "Vitality"
+modifiers = [
+    {{ stat = "{life}", value = 15.0 }},
+    {{ stat = "{fire}", value = 10.0 }},
+]
+
+[[nodes]]
+id = "unallocated"
+modifiers = [{{ stat = "{life}", value = 1000.0 }}]
+"#,
+            life = stat_name(StatType::AddedLife),
+            fire = stat_name(StatType::FireResistance),
+        );
+
+        let mut tree = parse_skill_tree(&toml).unwrap();
+        tree.allocate("toughness".into());
+        tree.allocate("vitality".into());
+
+        let mut acc = StatAccumulator::new();
+        tree.apply(&mut acc);
+        assert!((acc.life_flat - 35.0).abs() < 0.01);
+        assert!((acc.fire_resistance - 10.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_duplicate_node_rejected() {
+        let toml = r#"
+[[nodes]]
+id = "a"
+
+[[nodes]]
+id = "a"
+"#;
+        assert!(matches!(
+            parse_skill_tree(toml),
+            Err(ConfigError::ValidationError(_))
+        ));
+    }
+}
@@ -14,11 +14,14 @@ pub struct SkillsConfig {
 }
 
 /// Load skill configurations from a TOML file
+///
+/// Every skill is checked with `DamagePacketGenerator::validate`.
 pub fn load_skill_configs(path: &Path) -> Result<HashMap<String, DamagePacketGenerator>, ConfigError> {
     let config: SkillsConfig = super::load_toml(path)?;
 
     let mut map = HashMap::new();
     for skill in config.skills {
+        skill.validate()?;
         map.insert(skill.id.clone(), skill);
     }
 
@@ -31,6 +34,7 @@ pub fn parse_skill_configs(content: &str) -> Result<HashMap<String, DamagePacket
 
     let mut map = HashMap::new();
     for skill in config.skills {
+        skill.validate()?;
         map.insert(skill.id.clone(), skill);
     }
 
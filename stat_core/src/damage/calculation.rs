@@ -1,8 +1,14 @@
 //! Damage calculation - turning a skill + stats into a DamagePacket
 
-use super::{DamagePacket, DamagePacketGenerator, PendingStatusEffect, SkillStatusConversions};
-use crate::stat_block::{StatusEffectData, StatusEffectStats, StatBlock};
-use crate::types::Effect;
+use super::{
+    DamagePacket, DamagePacketGenerator, DamageProvenance, PendingStatusEffect,
+    SkillStatusConversions, TypeProvenance,
+};
+use crate::combat::{chance_to_land, expected_hit_damage};
+use crate::config::GameConstants;
+use crate::dot::DotRegistry;
+use crate::stat_block::{StatBlock, StatusEffectData, StatusEffectStats};
+use crate::types::{Effect, ALL_DAMAGE_TYPES};
 use loot_core::types::{DamageType, StatusEffect};
 use rand::Rng;
 use std::collections::HashMap;
@@ -14,104 +20,278 @@ pub fn calculate_damage(
     source_id: String,
     rng: &mut impl Rng,
 ) -> DamagePacket {
-    let mut packet = DamagePacket::new(source_id, skill.id.clone());
+    calculate_damage_with_constants(attacker, skill, source_id, &GameConstants::default(), rng)
+}
 
-    // Step 1: Gather base damage (pre-conversion, pre-scaling)
-    let mut base_damages: HashMap<DamageType, f64> = HashMap::new();
+/// Calculate damage from a skill and attacker's stats using tunable game constants
+pub fn calculate_damage_with_constants(
+    attacker: &StatBlock,
+    skill: &DamagePacketGenerator,
+    source_id: String,
+    constants: &GameConstants,
+    rng: &mut impl Rng,
+) -> DamagePacket {
+    build_damage_packet(
+        attacker,
+        skill,
+        source_id,
+        constants,
+        DotRegistry::shared_defaults(),
+        &mut RandomRolls(rng),
+    )
+}
 
-    // Skill base damages
-    for base_dmg in &skill.base_damages {
-        let rolled = if base_dmg.min >= base_dmg.max {
-            base_dmg.max
-        } else {
-            rng.gen_range(base_dmg.min..=base_dmg.max)
-        };
-        *base_damages.entry(base_dmg.damage_type).or_insert(0.0) += rolled;
+/// Calculate damage using a specific DoT registry for ailment durations and DPS
+pub fn calculate_damage_with_registry(
+    attacker: &StatBlock,
+    skill: &DamagePacketGenerator,
+    source_id: String,
+    registry: &DotRegistry,
+    rng: &mut impl Rng,
+) -> DamagePacket {
+    build_damage_packet(
+        attacker,
+        skill,
+        source_id,
+        &GameConstants::default(),
+        registry,
+        &mut RandomRolls(rng),
+    )
+}
+
+/// Calculate the expected damage packet for a skill, without RNG
+///
+/// Damage ranges use their average and crits are folded in as
+/// `1 + (crit_multiplier - 1) × crit_chance`, so the result is stable
+/// between calls. Lucky and unlucky rolls use their expected values.
+/// `crit_multiplier` holds the expected multiplier already applied to the
+/// damages; `is_critical` is only set at 100% crit chance. Ailment
+/// durations and DoT percents come from `registry`; `source_id` is left
+/// empty.
+pub fn calculate_expected_damage(
+    attacker: &StatBlock,
+    skill: &DamagePacketGenerator,
+    registry: &DotRegistry,
+) -> DamagePacket {
+    build_damage_packet(
+        attacker,
+        skill,
+        String::new(),
+        &GameConstants::default(),
+        registry,
+        &mut ExpectedRolls,
+    )
+}
+
+/// Whether a value is rolled once, or twice keeping the better or worse result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Luck {
+    Normal,
+    Lucky,
+    Unlucky,
+}
+
+impl Luck {
+    /// Lucky and unlucky together cancel out
+    fn from_flags(lucky: bool, unlucky: bool) -> Self {
+        match (lucky, unlucky) {
+            (true, false) => Luck::Lucky,
+            (false, true) => Luck::Unlucky,
+            _ => Luck::Normal,
+        }
     }
 
-    // Weapon damage if this is an attack skill
-    if skill.is_attack() && skill.weapon_effectiveness > 0.0 {
-        for damage_type in [
-            DamageType::Physical,
-            DamageType::Fire,
-            DamageType::Cold,
-            DamageType::Lightning,
-            DamageType::Chaos,
-        ] {
-            let (min, max) = attacker.weapon_damage(damage_type);
-            if max > 0.0 {
-                let scaled_min = min * skill.weapon_effectiveness;
-                let scaled_max = max * skill.weapon_effectiveness;
-                let rolled = if scaled_min >= scaled_max {
-                    scaled_max
-                } else {
-                    rng.gen_range(scaled_min..=scaled_max)
-                };
-                *base_damages.entry(damage_type).or_insert(0.0) += rolled;
-            }
+    /// Expected position of a uniform roll within its range (0.0 = min, 1.0 = max)
+    fn expected_fraction(self) -> f64 {
+        match self {
+            Luck::Normal => 0.5,
+            Luck::Lucky => 2.0 / 3.0,
+            Luck::Unlucky => 1.0 / 3.0,
         }
     }
 
-    // Step 2: Apply damage type conversions (before scaling)
-    let converted_damages = if skill.damage_conversions.has_conversions() {
-        skill.damage_conversions.apply(&base_damages)
-    } else {
-        base_damages
-    };
+    /// Chance to succeed at a check with success chance `chance` (0.0-1.0)
+    fn chance(self, chance: f64) -> f64 {
+        match self {
+            Luck::Normal => chance,
+            Luck::Lucky => 1.0 - (1.0 - chance).powi(2),
+            Luck::Unlucky => chance * chance,
+        }
+    }
+}
 
-    // Step 3: Apply damage scaling to each type
-    for (damage_type, base_amount) in converted_damages {
-        if base_amount <= 0.0 {
-            continue;
+/// Source of the random parts of a damage packet
+trait DamageRolls {
+    /// Damage value for a `min..=max` range
+    fn roll_range(&mut self, min: f64, max: f64, luck: Luck) -> f64;
+    /// How much of a crit the hit gets for a crit chance in 0.0-1.0
+    /// (0.0 = normal hit, 1.0 = full crit)
+    fn crit_weight(&mut self, chance: f64, luck: Luck) -> f64;
+}
+
+/// Rolls from an RNG
+struct RandomRolls<'a, R>(&'a mut R);
+
+impl<R: Rng> DamageRolls for RandomRolls<'_, R> {
+    fn roll_range(&mut self, min: f64, max: f64, luck: Luck) -> f64 {
+        if min >= max {
+            return max;
         }
+        let roll = self.0.gen_range(min..=max);
+        match luck {
+            Luck::Normal => roll,
+            Luck::Lucky => roll.max(self.0.gen_range(min..=max)),
+            Luck::Unlucky => roll.min(self.0.gen_range(min..=max)),
+        }
+    }
 
-        let damage_stat = match damage_type {
-            DamageType::Physical => &attacker.global_physical_damage,
-            DamageType::Fire => &attacker.global_fire_damage,
-            DamageType::Cold => &attacker.global_cold_damage,
-            DamageType::Lightning => &attacker.global_lightning_damage,
-            DamageType::Chaos => &attacker.global_chaos_damage,
+    fn crit_weight(&mut self, chance: f64, luck: Luck) -> f64 {
+        // Lower rolls crit, so lucky keeps the lower of two
+        let roll = self.0.gen::<f64>();
+        let roll = match luck {
+            Luck::Normal => roll,
+            Luck::Lucky => roll.min(self.0.gen::<f64>()),
+            Luck::Unlucky => roll.max(self.0.gen::<f64>()),
         };
+        if roll < chance {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
 
-        let increased_mult = damage_stat.total_increased_multiplier();
-        let more_mult = damage_stat.total_more_multiplier();
-        let type_eff = skill.type_effectiveness.get(damage_type);
+/// Average rolls with crits weighted by their chance
+struct ExpectedRolls;
 
-        let scaled_damage = base_amount * increased_mult * more_mult * skill.damage_effectiveness * type_eff;
-        if scaled_damage > 0.0 {
-            packet.add_damage(damage_type, scaled_damage);
+impl DamageRolls for ExpectedRolls {
+    fn roll_range(&mut self, min: f64, max: f64, luck: Luck) -> f64 {
+        if min >= max {
+            max
+        } else {
+            min + (max - min) * luck.expected_fraction()
         }
     }
 
+    fn crit_weight(&mut self, chance: f64, luck: Luck) -> f64 {
+        luck.chance(chance)
+    }
+}
+
+/// Build a damage packet, taking damage rolls and crits from `rolls`
+fn build_damage_packet(
+    attacker: &StatBlock,
+    skill: &DamagePacketGenerator,
+    source_id: String,
+    constants: &GameConstants,
+    registry: &DotRegistry,
+    rolls: &mut impl DamageRolls,
+) -> DamagePacket {
+    let mut packet = DamagePacket::new(source_id, skill.id.clone());
+    let damage_luck = Luck::from_flags(skill.lucky_damage, skill.unlucky_damage);
+
+    // Step 1: Gather skill + weapon base damage into one map (pre-conversion, pre-scaling)
+    let base_ranges = gather_base_ranges(attacker, skill);
+    let base_damages = sum_base_damages(&base_ranges, |min, max| {
+        rolls.roll_range(min, max, damage_luck)
+    });
+
+    // Steps 2 and 3: Apply damage type conversions, then scale each type
+    let mut provenance = DamageProvenance {
+        types: scale_base_damages(attacker, skill, &base_damages),
+        crit_multiplier: 1.0,
+    };
+    for scaled in &provenance.types {
+        packet.add_damage(scaled.damage_type, scaled.scaled_amount());
+    }
+
+    // Record the damage range each type could have rolled, from the same gathered ranges
+    let scale_bound = |roll: fn(f64, f64) -> f64| {
+        scale_base_damages(attacker, skill, &sum_base_damages(&base_ranges, roll))
+    };
+    let min_damages = scale_bound(|min, _| min);
+    let max_damages = scale_bound(|_, max| max);
+    let amount_of = |damages: &[TypeProvenance], damage_type| {
+        damages
+            .iter()
+            .find(|scaled| scaled.damage_type == damage_type)
+            .map_or(0.0, TypeProvenance::scaled_amount)
+    };
+    for damage in &mut packet.damages {
+        damage.range = Some((
+            amount_of(&min_damages, damage.damage_type),
+            amount_of(&max_damages, damage.damage_type),
+        ));
+    }
+
     // Step 4: Calculate crit
     let crit_chance = calculate_crit_chance(attacker, skill);
-    packet.is_critical = rng.gen::<f64>() < crit_chance / 100.0;
+    let crit_luck = Luck::from_flags(skill.lucky_crit, skill.unlucky_crit);
+    let crit_weight = rolls.crit_weight(crit_chance / 100.0, crit_luck);
+    packet.is_critical = crit_weight >= 1.0;
 
-    if packet.is_critical {
-        packet.crit_multiplier = attacker.computed_crit_multiplier() + skill.crit_multiplier_bonus;
+    if crit_weight > 0.0 {
+        let full_multiplier = attacker.computed_crit_multiplier() + skill.crit_multiplier_bonus;
+        packet.crit_multiplier = 1.0 + (full_multiplier - 1.0) * crit_weight;
         // Apply crit multiplier to all damages
         for damage in &mut packet.damages {
-            damage.amount *= packet.crit_multiplier;
+            damage.scale(packet.crit_multiplier);
         }
+        provenance.crit_multiplier = packet.crit_multiplier;
     }
-
-    // Step 4: Set penetration from attacker stats
-    packet.fire_pen = attacker.fire_penetration.compute();
-    packet.cold_pen = attacker.cold_penetration.compute();
-    packet.lightning_pen = attacker.lightning_penetration.compute();
-    packet.chaos_pen = attacker.chaos_penetration.compute();
+    let hit_crit_multiplier = provenance.crit_multiplier;
+    packet.provenance = Some(provenance);
+
+    // Step 4: Set penetration from attacker stats plus the skill's own
+    packet.fire_pen = attacker.fire_penetration.compute() + skill.penetration.fire;
+    packet.cold_pen = attacker.cold_penetration.compute() + skill.penetration.cold;
+    packet.lightning_pen = attacker.lightning_penetration.compute() + skill.penetration.lightning;
+    packet.chaos_pen = attacker.chaos_penetration.compute() + skill.penetration.chaos;
+    packet.skill_penetration = skill.penetration;
+    packet.fire_pen_percent = attacker.fire_penetration_percent;
+    packet.cold_pen_percent = attacker.cold_penetration_percent;
+    packet.lightning_pen_percent = attacker.lightning_penetration_percent;
+    packet.chaos_pen_percent = attacker.chaos_penetration_percent;
+    packet.resistance_reduction_applied = attacker.enemy_resistance_reduction;
+    packet.overwhelm = attacker.overwhelm + skill.overwhelm;
+    packet.culling_strike_threshold = attacker.culling_strike_threshold;
 
     // Step 5: Set accuracy from attacker stats
     packet.accuracy = attacker.accuracy.compute();
+    packet.is_spell = skill.is_spell();
+    packet.life_leech = attacker.life_leech.compute();
+    packet.mana_leech = attacker.mana_leech.compute();
+    packet.life_on_hit = attacker.life_on_hit;
 
     // Step 6: Calculate status effect applications
     // Status damage is converted from hit damage (combining skill + player conversions)
     // Status damage determines: chance to apply = status_damage / target_max_health
     // For damaging DoTs: DoT DPS = base_dot_percent * status_damage
+    // Hit damage already includes increases; strip them if ailments shouldn't scale with them
+    // Likewise strip the crit multiplier if the skill's ailments can't crit
+    let scale_with_increases = constants.ailments.scale_with_damage_increases;
+    let ailment_crit_divisor = if skill.ailments_can_crit {
+        1.0
+    } else {
+        hit_crit_multiplier.max(f64::EPSILON)
+    };
     let damages_vec: Vec<(DamageType, f64)> = packet
         .damages
         .iter()
-        .map(|d| (d.damage_type, d.amount))
+        .map(|d| {
+            let amount = d.amount / ailment_crit_divisor;
+            if scale_with_increases {
+                (d.damage_type, amount)
+            } else {
+                // Converted damage also carries its source types' increases
+                let increased_mult = packet
+                    .provenance
+                    .as_ref()
+                    .and_then(|p| p.types.iter().find(|t| t.damage_type == d.damage_type))
+                    .map_or(1.0, |t| t.increased_multiplier);
+                (d.damage_type, amount / increased_mult.max(f64::EPSILON))
+            }
+        })
         .collect();
 
     for status in [
@@ -134,12 +314,19 @@ pub fn calculate_damage(
 
         if status_damage > 0.0 {
             let stats = attacker.status_effect_stats.get_stats(status);
-            let base_duration = Effect::base_duration_for(status);
-            let duration = base_duration * (1.0 + stats.duration_increased);
+            let config = registry.config_for(status);
+            let increased = stats.duration_increased + attacker.effect_duration_increased;
+            let duration = config.map_or_else(
+                || Effect::base_duration_for(status) * (1.0 + increased),
+                |c| c.scaled_duration(increased),
+            );
             let magnitude = 1.0 + stats.magnitude;
 
             // For damaging DoTs, calculate DoT DPS based on status damage
-            let base_dot_percent = Effect::base_dot_percent_for(status);
+            let base_dot_percent = config.map_or_else(
+                || Effect::base_dot_percent_for(status),
+                |c| c.base_damage_percent,
+            );
             let dot_dps = calculate_status_dot_dps(base_dot_percent, status_damage, stats);
 
             packet.status_effects_to_apply.push(PendingStatusEffect::new_with_dot(
@@ -152,12 +339,152 @@ pub fn calculate_damage(
         }
     }
 
+    // Step 7: DoT-only skills keep their ailments but deal no hit damage
+    if skill.hit_deals_no_damage {
+        packet.damages.clear();
+        packet.can_leech = false;
+    }
+
     // Step 8: Set hit count for multi-hit skills
     packet.hit_count = skill.hits_per_attack;
+    packet.aoe_falloff = skill.aoe_falloff;
+    if skill.can_chain {
+        packet.chain_count = skill.chain_count;
+        packet.chain_damage_multiplier = skill.chain_damage_multiplier;
+    }
 
     packet
 }
 
+/// Calculate one damage packet per hit for multi-hit skills
+///
+/// Each hit rolls its own damage and crit from the provided RNG instead of
+/// sharing a single rolled value, so overlapping hits keep their variance.
+/// Rolls honor the skill's lucky and unlucky flags like any other packet.
+/// Every returned packet has a `hit_count` of 1; resolve them with
+/// `resolve_multi_hit`.
+pub fn calculate_damage_per_hit(
+    attacker: &StatBlock,
+    skill: &DamagePacketGenerator,
+    source_id: String,
+    rng: &mut impl Rng,
+) -> Vec<DamagePacket> {
+    let mut packets = Vec::with_capacity(skill.hits_per_attack as usize);
+
+    for _ in 0..skill.hits_per_attack.max(1) {
+        let mut packet = calculate_damage(attacker, skill, source_id.clone(), rng);
+        packet.hit_count = 1;
+        packets.push(packet);
+    }
+
+    packets
+}
+
+/// Whether a skill adds the attacker's weapon damage and weapon crit chance
+///
+/// Only attacks use the weapon; spells (even ones also tagged as attacks)
+/// scale purely from their own base damage and crit chance.
+fn uses_weapon(skill: &DamagePacketGenerator) -> bool {
+    skill.is_attack() && !skill.is_spell()
+}
+
+/// Gather a skill's base damage ranges, before conversion and scaling
+///
+/// Returns one `(type, min, max)` entry per damage source, in roll order.
+/// Attacks add the weapon's damage of every type (physical and flat
+/// elemental/chaos) scaled by `weapon_effectiveness`; spells use
+/// `base_damages` only.
+fn gather_base_ranges(
+    attacker: &StatBlock,
+    skill: &DamagePacketGenerator,
+) -> Vec<(DamageType, f64, f64)> {
+    let mut ranges: Vec<(DamageType, f64, f64)> = skill
+        .base_damages
+        .iter()
+        .map(|base_dmg| (base_dmg.damage_type, base_dmg.min, base_dmg.max))
+        .collect();
+
+    if uses_weapon(skill) && skill.weapon_effectiveness > 0.0 {
+        for damage_type in ALL_DAMAGE_TYPES {
+            let (min, max) = attacker.weapon_damage(damage_type);
+            if max > 0.0 {
+                ranges.push((
+                    damage_type,
+                    min * skill.weapon_effectiveness,
+                    max * skill.weapon_effectiveness,
+                ));
+            }
+        }
+    }
+
+    ranges
+}
+
+/// Sum gathered ranges into one base damage per type
+///
+/// `roll` turns each `min..=max` range into a value. Everything lands in one
+/// per-type map, so conversions see weapon elemental damage too.
+fn sum_base_damages(
+    ranges: &[(DamageType, f64, f64)],
+    mut roll: impl FnMut(f64, f64) -> f64,
+) -> HashMap<DamageType, f64> {
+    let mut base_damages: HashMap<DamageType, f64> = HashMap::new();
+    for (damage_type, min, max) in ranges {
+        *base_damages.entry(*damage_type).or_insert(0.0) += roll(*min, *max);
+    }
+    base_damages
+}
+
+/// Convert base damage and apply the attacker's increased damage
+///
+/// The pipeline order is added damage → conversion → scaling. Skill and
+/// player conversions combine, and converted damage adds together the
+/// increased modifiers of its original and its resulting type (see
+/// `DamageConversions::apply_with_scaling`). Returns the converted base
+/// damage and the same damage after increases, keyed by final type.
+///
+/// Gains as extra (skill + player) are then taken from the converted damage
+/// and added to their destination types, scaled only by the destination
+/// type's increases.
+///
+/// Spells also add the attacker's `spell_damage_increased` to every type, as
+/// do tag-scoped increases matching the skill's tags.
+fn convert_and_increase(
+    attacker: &StatBlock,
+    skill: &DamagePacketGenerator,
+    base_damages: &HashMap<DamageType, f64>,
+) -> (HashMap<DamageType, f64>, HashMap<DamageType, f64>) {
+    let conversions = skill
+        .damage_conversions
+        .combined(&attacker.damage_conversions);
+    let mut converted = conversions.apply(base_damages);
+    let spell_increased = if skill.is_spell() {
+        attacker.spell_damage_increased
+    } else {
+        0.0
+    };
+    let skill_increased = spell_increased + attacker.tag_damage_increased_for(&skill.tags);
+    let type_increased = |damage_type| {
+        attacker
+            .global_damage(damage_type)
+            .total_increased_multiplier()
+            - 1.0
+    };
+    let increased_multiplier = |damage_type| 1.0 + type_increased(damage_type) + skill_increased;
+
+    // Skill-wide increases apply once, however many types the damage passed through
+    let mut increased = conversions.apply_with_scaling(base_damages, type_increased);
+    for (damage_type, amount) in &converted {
+        *increased.entry(*damage_type).or_insert(0.0) += amount * skill_increased;
+    }
+
+    let gains = skill.gain_as_extra.combined(&attacker.gain_as_extra);
+    for (damage_type, extra) in gains.extra_damages(&converted) {
+        *converted.entry(damage_type).or_insert(0.0) += extra;
+        *increased.entry(damage_type).or_insert(0.0) += extra * increased_multiplier(damage_type);
+    }
+    (converted, increased)
+}
 
 /// Calculate combined status damage from skill conversions + player stat conversions
 fn calculate_combined_status_damage(
@@ -197,7 +524,7 @@ fn calculate_status_dot_dps(base_dot_percent: f64, status_damage: f64, stats: &S
 /// Calculate critical strike chance
 fn calculate_crit_chance(attacker: &StatBlock, skill: &DamagePacketGenerator) -> f64 {
     // Base crit = skill base + weapon base (for attacks)
-    let base_crit = if skill.is_attack() {
+    let base_crit = if uses_weapon(skill) {
         skill.base_crit_chance + attacker.weapon_crit_chance
     } else {
         skill.base_crit_chance
@@ -213,111 +540,233 @@ fn calculate_crit_chance(attacker: &StatBlock, skill: &DamagePacketGenerator) ->
     (flat_crit * increased_mult * more_mult).clamp(0.0, 100.0)
 }
 
-/// Calculate effective DPS for a skill
+/// Calculate the expected critical strike chance (0.0 to 1.0) of a skill
+///
+/// Includes the skill's lucky or unlucky crit rolls.
+pub fn calculate_effective_crit_chance(attacker: &StatBlock, skill: &DamagePacketGenerator) -> f64 {
+    Luck::from_flags(skill.lucky_crit, skill.unlucky_crit)
+        .chance(calculate_crit_chance(attacker, skill) / 100.0)
+}
+
+/// Expected DPS of a skill, split into hits and ailments
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DpsBreakdown {
+    /// DPS from hits, including crits
+    pub hit_dps: f64,
+    /// Sustained DPS from damaging ailments (Poison, Bleed, Burn)
+    pub dot_dps: f64,
+    /// `hit_dps + dot_dps`
+    pub total: f64,
+}
+
+/// Calculate effective DPS for a skill using the default DoT configs
+///
+/// Shorthand for `calculate_skill_dps_breakdown(..).total`.
 pub fn calculate_skill_dps(
     attacker: &StatBlock,
     skill: &DamagePacketGenerator,
 ) -> f64 {
+    calculate_skill_dps_breakdown(attacker, skill, DotRegistry::shared_defaults()).total
+}
+
+/// Calculate expected hit and ailment DPS for a skill
+///
+/// Ailment DPS assumes every hit applies each damaging ailment its status
+/// conversions (skill plus `status_effect_stats`) produce. Each application
+/// deals `base_damage_percent` of its status damage per second for the
+/// registry's base duration (scaled by increased duration), and overlapping
+/// applications combine according to the DoT's stacking mode.
+pub fn calculate_skill_dps_breakdown(
+    attacker: &StatBlock,
+    skill: &DamagePacketGenerator,
+    registry: &DotRegistry,
+) -> DpsBreakdown {
     // Use average damage instead of random
     let avg_damages = calculate_average_damage_by_type(attacker, skill);
     let total_avg_damage: f64 = avg_damages.iter().map(|(_, amt)| amt).sum();
 
     // Calculate crit contribution
-    let crit_chance = calculate_crit_chance(attacker, skill) / 100.0;
+    let crit_chance = calculate_effective_crit_chance(attacker, skill);
     let crit_mult = attacker.computed_crit_multiplier() + skill.crit_multiplier_bonus;
     let crit_dps_mult = 1.0 + (crit_mult - 1.0) * crit_chance;
 
-    // Get attack/cast speed
-    let speed = if skill.is_attack() {
-        attacker.computed_attack_speed() * skill.attack_speed_modifier
+    // Spells use cast speed, attacks use attack speed
+    let speed = attacker.computed_skill_speed(skill);
+    let hits_per_second = speed * skill.hits_per_attack as f64;
+
+    // Calculate hit DPS (before crit scaling on avg damages)
+    let hit_dps = if skill.hit_deals_no_damage {
+        0.0
     } else {
-        attacker.computed_cast_speed() * skill.attack_speed_modifier
+        total_avg_damage * crit_dps_mult * hits_per_second
     };
 
-    // Calculate hit DPS (before crit scaling on avg damages)
-    let hit_dps = total_avg_damage * crit_dps_mult * speed * skill.hits_per_attack as f64;
+    let dot_dps: f64 = dot_dps_by_type(attacker, skill, registry, &avg_damages, hits_per_second)
+        .iter()
+        .map(|(_, dps)| dps)
+        .sum();
 
-    // Calculate status DoT DPS contribution from damaging statuses (Poison, Bleed, Burn)
-    let mut dot_dps = 0.0;
+    DpsBreakdown {
+        hit_dps,
+        dot_dps,
+        total: hit_dps + dot_dps,
+    }
+}
+
+/// Calculate expected DPS after a defender's mitigation
+///
+/// The expected packet (see [`calculate_expected_damage`]) is resolved
+/// against `defender` without rolling anything, so its resistances, armour
+/// and damage taken reductions apply; the defender itself is not changed.
+/// Hit DPS is scaled by the skill's hit chance, the defender's chance to
+/// evade (`EvasionMode::Chance`) and its block chance. Ailment DPS is
+/// mitigated like DoT ticks: by resistance to each DoT's damage type and
+/// the defender's DoT damage taken multiplier, and evaded hits apply none.
+pub fn calculate_effective_dps(
+    attacker: &StatBlock,
+    skill: &DamagePacketGenerator,
+    defender: &StatBlock,
+    registry: &DotRegistry,
+) -> DpsBreakdown {
+    calculate_effective_dps_with_constants(
+        attacker,
+        skill,
+        defender,
+        registry,
+        &GameConstants::default(),
+    )
+}
+
+/// Calculate expected DPS after a defender's mitigation using tunable game constants
+///
+/// As [`calculate_effective_dps`], with `constants` used both to build the
+/// expected packet and to resolve it, as `resolve_damage_with_constants` does.
+pub fn calculate_effective_dps_with_constants(
+    attacker: &StatBlock,
+    skill: &DamagePacketGenerator,
+    defender: &StatBlock,
+    registry: &DotRegistry,
+    constants: &GameConstants,
+) -> DpsBreakdown {
+    let avg_damages = calculate_average_damage_by_type(attacker, skill);
+    let attacks_per_second = attacker.computed_skill_speed(skill);
+    let hits_per_second = attacks_per_second * skill.hits_per_attack as f64;
+
+    let packet = build_damage_packet(
+        attacker,
+        skill,
+        String::new(),
+        constants,
+        registry,
+        &mut ExpectedRolls,
+    );
+
+    // The packet already resolves every hit of one attack
+    let hit_dps = if skill.hit_deals_no_damage {
+        0.0
+    } else {
+        expected_hit_damage(defender, &packet, constants) * attacks_per_second
+    };
+
+    // Evaded hits apply no ailments either
+    let dot_mult =
+        defender.dot_damage_taken_multiplier() * chance_to_land(defender, &packet, constants);
+    let dot_dps: f64 = dot_dps_by_type(attacker, skill, registry, &avg_damages, hits_per_second)
+        .iter()
+        .map(|&(damage_type, dps)| dps * defender.dot_resistance_multiplier(damage_type) * dot_mult)
+        .sum();
+
+    DpsBreakdown {
+        hit_dps,
+        dot_dps,
+        total: hit_dps + dot_dps,
+    }
+}
+
+/// Sustained DoT DPS from damaging statuses (Poison, Bleed, Burn), keyed by the DoT's damage type
+fn dot_dps_by_type(
+    attacker: &StatBlock,
+    skill: &DamagePacketGenerator,
+    registry: &DotRegistry,
+    avg_damages: &[(DamageType, f64)],
+    hits_per_second: f64,
+) -> Vec<(DamageType, f64)> {
+    let mut result = Vec::new();
     for status in [StatusEffect::Poison, StatusEffect::Bleed, StatusEffect::Burn] {
+        let Some(config) = registry.config_for(status) else {
+            continue;
+        };
         let status_damage = calculate_combined_status_damage(
             status,
-            &avg_damages,
+            avg_damages,
             &skill.status_conversions,
             &attacker.status_effect_stats,
         );
 
         if status_damage > 0.0 {
             let stats = attacker.status_effect_stats.get_stats(status);
-            let base_dot_percent = Effect::base_dot_percent_for(status);
-            let status_dot_dps = calculate_status_dot_dps(base_dot_percent, status_damage, stats);
-            // Scale by attack speed (more hits = more DoT applications)
-            dot_dps += status_dot_dps * speed;
+            let per_application =
+                calculate_status_dot_dps(config.base_damage_percent, status_damage, stats);
+            let duration = config
+                .scaled_duration(stats.duration_increased + attacker.effect_duration_increased);
+            result.push((
+                config.damage_type,
+                config.sustained_dps(per_application, hits_per_second, duration),
+            ));
         }
     }
-
-    hit_dps + dot_dps
+    result
 }
 
 /// Calculate average damage by type (non-random)
 /// Returns Vec of (DamageType, scaled_amount) after conversions and scaling
 pub fn calculate_average_damage_by_type(attacker: &StatBlock, skill: &DamagePacketGenerator) -> Vec<(DamageType, f64)> {
     // Step 1: Gather base damage averages (pre-conversion, pre-scaling)
-    let mut base_damages: HashMap<DamageType, f64> = HashMap::new();
+    let luck = Luck::from_flags(skill.lucky_damage, skill.unlucky_damage);
+    let base_damages = sum_base_damages(&gather_base_ranges(attacker, skill), |min, max| {
+        ExpectedRolls.roll_range(min, max, luck)
+    });
 
-    // Skill base damages
-    for base_dmg in &skill.base_damages {
-        let avg = (base_dmg.min + base_dmg.max) / 2.0;
-        *base_damages.entry(base_dmg.damage_type).or_insert(0.0) += avg;
-    }
-
-    // Weapon damages for attacks
-    if skill.is_attack() && skill.weapon_effectiveness > 0.0 {
-        for damage_type in [
-            DamageType::Physical,
-            DamageType::Fire,
-            DamageType::Cold,
-            DamageType::Lightning,
-            DamageType::Chaos,
-        ] {
-            let (min, max) = attacker.weapon_damage(damage_type);
-            if max > 0.0 {
-                let avg = (min + max) / 2.0 * skill.weapon_effectiveness;
-                *base_damages.entry(damage_type).or_insert(0.0) += avg;
-            }
-        }
-    }
+    scale_base_damages(attacker, skill, &base_damages)
+        .iter()
+        .map(|scaled| (scaled.damage_type, scaled.scaled_amount()))
+        .collect()
+}
 
+/// Convert and scale base damages (steps 2 and 3 of the damage pipeline)
+///
+/// Returns how each resulting type was scaled; types that scale to nothing
+/// are left out.
+fn scale_base_damages(
+    attacker: &StatBlock,
+    skill: &DamagePacketGenerator,
+    base_damages: &HashMap<DamageType, f64>,
+) -> Vec<TypeProvenance> {
     // Step 2: Apply damage type conversions
-    let converted_damages = if skill.damage_conversions.has_conversions() {
-        skill.damage_conversions.apply(&base_damages)
-    } else {
-        base_damages
-    };
+    let (converted_damages, increased_damages) =
+        convert_and_increase(attacker, skill, base_damages);
 
-    // Step 3: Apply damage scaling to each type
-    let mut result: Vec<(DamageType, f64)> = Vec::new();
+    // Step 3: Apply the remaining damage scaling to each type
+    let mut result: Vec<TypeProvenance> = Vec::new();
 
+    let tag_more = attacker.tag_damage_multiplier(&skill.tags);
     for (damage_type, base_amount) in converted_damages {
         if base_amount <= 0.0 {
             continue;
         }
 
-        let damage_stat = match damage_type {
-            DamageType::Physical => &attacker.global_physical_damage,
-            DamageType::Fire => &attacker.global_fire_damage,
-            DamageType::Cold => &attacker.global_cold_damage,
-            DamageType::Lightning => &attacker.global_lightning_damage,
-            DamageType::Chaos => &attacker.global_chaos_damage,
-        };
-
-        let increased_mult = damage_stat.total_increased_multiplier();
-        let more_mult = damage_stat.total_more_multiplier();
-        let type_eff = skill.type_effectiveness.get(damage_type);
+        let damage_stat = attacker.global_damage(damage_type);
 
-        let scaled = base_amount * increased_mult * more_mult * skill.damage_effectiveness * type_eff;
-        if scaled > 0.0 {
-            result.push((damage_type, scaled));
+        let scaled = TypeProvenance {
+            damage_type,
+            base: base_amount,
+            increased_multiplier: increased_damages.get(&damage_type).copied().unwrap_or(0.0)
+                / base_amount,
+            more_multiplier: damage_stat.total_more_multiplier() * tag_more,
+            effectiveness: skill.damage_effectiveness * skill.type_effectiveness.get(damage_type),
+        };
+        if scaled.scaled_amount() > 0.0 {
+            result.push(scaled);
         }
     }
 
@@ -327,7 +776,7 @@ pub fn calculate_average_damage_by_type(attacker: &StatBlock, skill: &DamagePack
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::damage::BaseDamage;
+    use crate::damage::{BaseDamage, DamageConversions};
     use crate::types::SkillTag;
     use rand::SeedableRng;
 
@@ -418,6 +867,212 @@ mod tests {
         assert!((packet.total_damage() - 150.0).abs() < 1.0);
     }
 
+    #[test]
+    fn test_provenance_reproduces_damages() {
+        let mut attacker = StatBlock::new();
+        attacker.global_physical_damage.add_increased(0.50);
+        attacker.global_physical_damage.add_more(0.20);
+        attacker.global_fire_damage.add_increased(0.30);
+        attacker.critical_chance.flat = 100.0;
+
+        let skill = DamagePacketGenerator {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            base_damages: vec![
+                BaseDamage::new(DamageType::Physical, 80.0, 120.0),
+                BaseDamage::new(DamageType::Fire, 40.0, 60.0),
+            ],
+            weapon_effectiveness: 0.0,
+            damage_effectiveness: 1.25,
+            ..Default::default()
+        };
+
+        let mut rng = make_test_rng();
+        let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+        let provenance = packet.provenance.as_ref().expect("provenance recorded");
+
+        assert!((provenance.crit_multiplier - packet.crit_multiplier).abs() < f64::EPSILON);
+        for damage_type in [DamageType::Physical, DamageType::Fire] {
+            let expected = packet.damage_of_type(damage_type);
+            assert!(expected > 0.0);
+            assert!((provenance.final_amount(damage_type) - expected).abs() < 0.001);
+        }
+
+        let phys = provenance.get(DamageType::Physical).unwrap();
+        assert!((phys.increased_multiplier - 1.5).abs() < 0.001);
+        assert!((phys.more_multiplier - 1.2).abs() < 0.001);
+        assert!((phys.effectiveness - 1.25).abs() < 0.001);
+    }
+
+    fn bleed_dps(attacker: &StatBlock, constants: &GameConstants) -> f64 {
+        let skill = DamagePacketGenerator {
+            id: "rend".to_string(),
+            name: "Rend".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Physical, 100.0, 100.0)],
+            weapon_effectiveness: 0.0,
+            base_crit_chance: 0.0,
+            tags: vec![SkillTag::Spell],
+            status_conversions: SkillStatusConversions {
+                physical_to_bleed: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut rng = make_test_rng();
+        let packet = calculate_damage_with_constants(
+            attacker,
+            &skill,
+            "player".to_string(),
+            constants,
+            &mut rng,
+        );
+        packet
+            .status_effects_to_apply
+            .iter()
+            .find(|s| s.effect_type == StatusEffect::Bleed)
+            .map(|s| s.dot_dps)
+            .unwrap_or(0.0)
+    }
+
+    #[test]
+    fn test_ailments_scale_with_damage_increases_flag() {
+        let base = StatBlock::new();
+        let mut scaled = StatBlock::new();
+        scaled.global_physical_damage.add_increased(1.0);
+
+        let mut constants = GameConstants::default();
+        constants.ailments.scale_with_damage_increases = true;
+        let base_on = bleed_dps(&base, &constants);
+        let scaled_on = bleed_dps(&scaled, &constants);
+        assert!(base_on > 0.0);
+        assert!((scaled_on - base_on * 2.0).abs() < 0.01);
+
+        constants.ailments.scale_with_damage_increases = false;
+        let base_off = bleed_dps(&base, &constants);
+        let scaled_off = bleed_dps(&scaled, &constants);
+        assert!((scaled_off - base_off).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_hit_deals_no_damage_still_poisons() {
+        let attacker = StatBlock::new();
+        let skill = DamagePacketGenerator {
+            id: "caustic_cloud".to_string(),
+            name: "Caustic Cloud".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Chaos, 100.0, 100.0)],
+            weapon_effectiveness: 0.0,
+            tags: vec![SkillTag::Spell],
+            status_conversions: SkillStatusConversions {
+                chaos_to_poison: 1.0,
+                ..Default::default()
+            },
+            hit_deals_no_damage: true,
+            ..Default::default()
+        };
+
+        let mut rng = make_test_rng();
+        let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+
+        assert!((packet.total_damage() - 0.0).abs() < f64::EPSILON);
+        let poison = packet
+            .status_effects_to_apply
+            .iter()
+            .find(|s| s.effect_type == StatusEffect::Poison)
+            .expect("poison seeded from would-be hit damage");
+        // 100 chaos fully converted to poison status damage
+        assert!((poison.status_damage - 100.0).abs() < 0.01);
+        assert!(poison.dot_dps > 0.0);
+    }
+
+    #[test]
+    fn test_per_hit_rolls_independently() {
+        let attacker = StatBlock::new();
+        let skill = DamagePacketGenerator {
+            id: "triple".to_string(),
+            name: "Triple".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Physical, 1.0, 1000.0)],
+            weapon_effectiveness: 0.0,
+            hits_per_attack: 3,
+            tags: vec![SkillTag::Spell],
+            ..Default::default()
+        };
+
+        let mut rng = make_test_rng();
+        let packets = calculate_damage_per_hit(&attacker, &skill, "player".to_string(), &mut rng);
+
+        assert_eq!(packets.len(), 3);
+        assert!(packets.iter().all(|p| p.hit_count == 1));
+
+        // Each hit rolled its own damage
+        let totals: Vec<f64> = packets.iter().map(|p| p.total_damage()).collect();
+        assert!((totals[0] - totals[1]).abs() > f64::EPSILON);
+        assert!((totals[1] - totals[2]).abs() > f64::EPSILON);
+        assert!((totals[0] - totals[2]).abs() > f64::EPSILON);
+    }
+
+    #[test]
+    fn test_multi_hit_resolution_matches_dps() {
+        let attacker = StatBlock::new();
+        let skill = DamagePacketGenerator {
+            id: "triple".to_string(),
+            name: "Triple".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Chaos, 50.0, 50.0)],
+            weapon_effectiveness: 0.0,
+            hits_per_attack: 3,
+            tags: vec![SkillTag::Spell],
+            ..Default::default()
+        };
+
+        let mut defender = StatBlock::new();
+        defender.current_life = 10000.0;
+        let mut rng = make_test_rng();
+        let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+        let (_, result) = crate::combat::resolve_damage_with_rng(&defender, &packet, &mut rng);
+
+        // Three hits of 50 per cast, at the skill's speed
+        let speed = attacker.computed_skill_speed(&skill);
+        let breakdown =
+            calculate_skill_dps_breakdown(&attacker, &skill, DotRegistry::shared_defaults());
+        assert!((result.total_damage - 150.0).abs() < 0.01);
+        assert!((result.total_damage * speed - breakdown.hit_dps).abs() < 0.01);
+
+        let effective =
+            calculate_effective_dps(&attacker, &skill, &defender, DotRegistry::shared_defaults());
+        assert!((effective.hit_dps - breakdown.hit_dps).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_per_hit_rolls_are_lucky() {
+        let attacker = StatBlock::new();
+        let mut skill = DamagePacketGenerator {
+            id: "triple".to_string(),
+            name: "Triple".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Physical, 0.0, 300.0)],
+            weapon_effectiveness: 0.0,
+            hits_per_attack: 3,
+            tags: vec![SkillTag::Spell],
+            ..Default::default()
+        };
+        let average_hit = |skill: &DamagePacketGenerator| {
+            let mut rng = make_test_rng();
+            let hits: Vec<f64> = (0..200)
+                .flat_map(|_| {
+                    calculate_damage_per_hit(&attacker, skill, "player".to_string(), &mut rng)
+                })
+                .map(|p| p.total_damage())
+                .collect();
+            hits.iter().sum::<f64>() / hits.len() as f64
+        };
+
+        // A uniform roll averages 150; the better of two averages 200
+        let normal = average_hit(&skill);
+        skill.lucky_damage = true;
+        let lucky = average_hit(&skill);
+        assert!((normal - 150.0).abs() < 15.0);
+        assert!((lucky - 200.0).abs() < 15.0);
+    }
+
     #[test]
     fn test_skill_dps() {
         let mut attacker = StatBlock::new();
@@ -442,4 +1097,665 @@ mod tests {
         assert!(dps > 100.0);
         assert!(dps < 110.0);
     }
+
+    #[test]
+    fn test_expected_damage_matches_average_of_rolls() {
+        let mut attacker = StatBlock::new();
+        attacker.global_physical_damage.add_increased(0.5);
+        let skill = DamagePacketGenerator {
+            id: "rend".to_string(),
+            name: "Rend".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Physical, 50.0, 150.0)],
+            weapon_effectiveness: 0.0,
+            tags: vec![SkillTag::Spell],
+            base_crit_chance: 25.0,
+            status_conversions: SkillStatusConversions {
+                physical_to_bleed: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let expected = calculate_expected_damage(&attacker, &skill, DotRegistry::shared_defaults());
+        assert!(!expected.is_critical);
+        // 25% crit at 1.5x: 1 + 0.5 * 0.25
+        assert!((expected.crit_multiplier - 1.125).abs() < 0.001);
+        // 100 average * 1.5 increased * 1.125 expected crit
+        assert!((expected.total_damage() - 168.75).abs() < 0.01);
+        // Identical across calls
+        let again = calculate_expected_damage(&attacker, &skill, DotRegistry::shared_defaults());
+        assert!((again.total_damage() - expected.total_damage()).abs() < f64::EPSILON);
+
+        let mut rng = make_test_rng();
+        let rolls = 20_000;
+        let mut total_damage = 0.0;
+        let mut total_bleed_dps = 0.0;
+        for _ in 0..rolls {
+            let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+            total_damage += packet.total_damage();
+            total_bleed_dps += packet
+                .status_effects_to_apply
+                .iter()
+                .find(|s| s.effect_type == StatusEffect::Bleed)
+                .map_or(0.0, |s| s.dot_dps);
+        }
+
+        let expected_bleed = expected
+            .status_effects_to_apply
+            .iter()
+            .find(|s| s.effect_type == StatusEffect::Bleed)
+            .expect("bleed should be applied");
+        let average_damage = total_damage / rolls as f64;
+        let average_bleed_dps = total_bleed_dps / rolls as f64;
+        assert!((average_damage - expected.total_damage()).abs() / expected.total_damage() < 0.02);
+        assert!((average_bleed_dps - expected_bleed.dot_dps).abs() / expected_bleed.dot_dps < 0.02);
+    }
+
+    #[test]
+    fn test_converted_damage_scales_with_both_types() {
+        let mut attacker = StatBlock::new();
+        attacker.global_physical_damage.add_increased(1.0);
+        attacker.global_fire_damage.add_increased(1.0);
+
+        let skill = DamagePacketGenerator {
+            id: "flame_strike".to_string(),
+            name: "Flame Strike".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Physical, 100.0, 100.0)],
+            weapon_effectiveness: 0.0,
+            base_crit_chance: 0.0,
+            damage_conversions: DamageConversions {
+                physical_to_fire: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut rng = make_test_rng();
+        let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+
+        // 50 physical at +100%; 50 converted fire at +100% physical plus +100% fire (additive)
+        let physical = packet
+            .damages
+            .iter()
+            .find(|d| d.damage_type == DamageType::Physical)
+            .unwrap();
+        let fire = packet
+            .damages
+            .iter()
+            .find(|d| d.damage_type == DamageType::Fire)
+            .unwrap();
+        assert!((physical.amount - 100.0).abs() < 0.01);
+        assert!((fire.amount - 150.0).abs() < 0.01);
+
+        // Player conversions from gear combine with the skill's
+        attacker.damage_conversions.physical_to_fire = 0.5;
+        let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+        assert!(packet
+            .damages
+            .iter()
+            .all(|d| d.damage_type != DamageType::Physical));
+        assert!((packet.total_damage() - 300.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_weapon_elemental_damage_joins_conversion() {
+        let mut attacker = StatBlock::new();
+        attacker.weapon_physical_min = 100.0;
+        attacker.weapon_physical_max = 100.0;
+        attacker.weapon_fire_min = 20.0;
+        attacker.weapon_fire_max = 20.0;
+        attacker.global_fire_damage.add_increased(1.0);
+
+        let skill = DamagePacketGenerator {
+            id: "frost_strike".to_string(),
+            name: "Frost Strike".to_string(),
+            base_damages: vec![],
+            weapon_effectiveness: 1.0,
+            base_crit_chance: 0.0,
+            tags: vec![SkillTag::Attack],
+            damage_conversions: DamageConversions {
+                physical_to_cold: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut rng = make_test_rng();
+        let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+
+        // Half the weapon physical becomes cold; weapon fire is kept and scaled
+        assert!((packet.damage_of_type(DamageType::Physical) - 50.0).abs() < 0.01);
+        assert!((packet.damage_of_type(DamageType::Cold) - 50.0).abs() < 0.01);
+        assert!((packet.damage_of_type(DamageType::Fire) - 40.0).abs() < 0.01);
+        assert!((packet.total_damage() - 140.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_gain_as_extra_adds_without_removing() {
+        let mut attacker = StatBlock::new();
+        attacker.global_physical_damage.add_increased(1.0);
+        attacker.global_lightning_damage.add_increased(0.5);
+
+        let mut skill = DamagePacketGenerator {
+            id: "thunder_strike".to_string(),
+            name: "Thunder Strike".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Physical, 100.0, 100.0)],
+            weapon_effectiveness: 0.0,
+            base_crit_chance: 0.0,
+            gain_as_extra: crate::damage::GainAsExtra {
+                physical_as_lightning: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // 100 physical at +100%; 50 extra lightning at +50% only
+        let mut rng = make_test_rng();
+        let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+        assert!((packet.damage_of_type(DamageType::Physical) - 200.0).abs() < 0.01);
+        assert!((packet.damage_of_type(DamageType::Lightning) - 75.0).abs() < 0.01);
+
+        // Gains read post-conversion damage and stack with the player's own
+        skill.damage_conversions.physical_to_fire = 0.5;
+        attacker.gain_as_extra.physical_as_lightning = 0.5;
+        let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+        assert!((packet.damage_of_type(DamageType::Physical) - 100.0).abs() < 0.01);
+        assert!((packet.damage_of_type(DamageType::Lightning) - 75.0).abs() < 0.01);
+        assert!(packet.damage_of_type(DamageType::Fire) > 0.0);
+    }
+
+    #[test]
+    fn test_tag_scoped_damage_only_scales_matching_skills() {
+        let mut attacker = StatBlock::new();
+        attacker.global_physical_damage.add_increased(0.3);
+        attacker.tag_damage_increased.insert(SkillTag::Melee, 0.2);
+        attacker.tag_damage_more.insert(SkillTag::Melee, vec![0.5]);
+
+        let melee = DamagePacketGenerator {
+            id: "slam".to_string(),
+            name: "Slam".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Physical, 100.0, 100.0)],
+            weapon_effectiveness: 0.0,
+            base_crit_chance: 0.0,
+            tags: vec![SkillTag::Attack, SkillTag::Melee],
+            ..Default::default()
+        };
+        let spell = DamagePacketGenerator {
+            id: "stone_bolt".to_string(),
+            name: "Stone Bolt".to_string(),
+            tags: vec![SkillTag::Spell],
+            ..melee.clone()
+        };
+
+        // +30% global and +20% melee are additive, then 50% more for melee
+        let mut rng = make_test_rng();
+        let packet = calculate_damage(&attacker, &melee, "player".to_string(), &mut rng);
+        assert!((packet.total_damage() - 100.0 * 1.5 * 1.5).abs() < 0.01);
+        assert!((attacker.tag_damage_multiplier(&melee.tags) - 1.5).abs() < f64::EPSILON);
+
+        let packet = calculate_damage(&attacker, &spell, "player".to_string(), &mut rng);
+        assert!((packet.total_damage() - 130.0).abs() < 0.01);
+        assert!((attacker.tag_damage_multiplier(&spell.tags) - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_chain_settings_only_copied_for_chaining_skills() {
+        let mut skill = DamagePacketGenerator {
+            id: "arc".to_string(),
+            name: "Arc".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Lightning, 100.0, 100.0)],
+            weapon_effectiveness: 0.0,
+            tags: vec![SkillTag::Spell],
+            chain_count: 3,
+            chain_damage_multiplier: 0.9,
+            ..Default::default()
+        };
+
+        let mut rng = make_test_rng();
+        let packet = calculate_damage(&StatBlock::new(), &skill, "player".to_string(), &mut rng);
+        assert_eq!(packet.chain_count, 0);
+        assert!((packet.after_chain(0).total_damage() - 100.0).abs() < 0.01);
+
+        skill.can_chain = true;
+        let packet = calculate_damage(&StatBlock::new(), &skill, "player".to_string(), &mut rng);
+        assert!((packet.after_chain(3).total_damage() - 72.9).abs() < 0.01);
+    }
+
+    fn crit_rate(skill: &DamagePacketGenerator, rolls: u32) -> f64 {
+        let mut rng = make_test_rng();
+        let crits = (0..rolls)
+            .filter(|_| {
+                calculate_damage(&StatBlock::new(), skill, "player".to_string(), &mut rng)
+                    .is_critical
+            })
+            .count();
+        crits as f64 / rolls as f64
+    }
+
+    #[test]
+    fn test_lucky_and_unlucky_crit_rates() {
+        let mut skill = DamagePacketGenerator {
+            id: "spark".to_string(),
+            name: "Spark".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Lightning, 10.0, 10.0)],
+            weapon_effectiveness: 0.0,
+            tags: vec![SkillTag::Spell],
+            base_crit_chance: 30.0,
+            ..Default::default()
+        };
+
+        assert!((crit_rate(&skill, 20_000) - 0.30).abs() < 0.015);
+
+        // 1 - (1 - 0.3)^2 = 0.51
+        skill.lucky_crit = true;
+        assert!((crit_rate(&skill, 20_000) - 0.51).abs() < 0.015);
+
+        // 0.3^2 = 0.09
+        skill.lucky_crit = false;
+        skill.unlucky_crit = true;
+        assert!((crit_rate(&skill, 20_000) - 0.09).abs() < 0.015);
+
+        // Both cancel out
+        skill.lucky_crit = true;
+        assert!((crit_rate(&skill, 20_000) - 0.30).abs() < 0.015);
+    }
+
+    #[test]
+    fn test_lucky_damage_favors_high_rolls() {
+        let mut skill = DamagePacketGenerator {
+            id: "spark".to_string(),
+            name: "Spark".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Lightning, 0.0, 300.0)],
+            weapon_effectiveness: 0.0,
+            tags: vec![SkillTag::Spell],
+            lucky_damage: true,
+            ..Default::default()
+        };
+
+        let average = |skill: &DamagePacketGenerator| {
+            let mut rng = make_test_rng();
+            (0..20_000)
+                .map(|_| {
+                    calculate_damage(&StatBlock::new(), skill, "player".to_string(), &mut rng)
+                        .total_damage()
+                })
+                .sum::<f64>()
+                / 20_000.0
+        };
+
+        // The higher of two uniform rolls averages 2/3 of the range
+        assert!((average(&skill) - 200.0).abs() < 3.0);
+        let expected =
+            calculate_expected_damage(&StatBlock::new(), &skill, DotRegistry::shared_defaults());
+        assert!((expected.total_damage() - 200.0).abs() < 0.01);
+
+        skill.lucky_damage = false;
+        skill.unlucky_damage = true;
+        assert!((average(&skill) - 100.0).abs() < 3.0);
+    }
+
+    #[test]
+    fn test_pure_hit_skill_has_no_dot_dps() {
+        let skill = DamagePacketGenerator {
+            id: "spark".to_string(),
+            name: "Spark".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Lightning, 100.0, 100.0)],
+            weapon_effectiveness: 0.0,
+            tags: vec![SkillTag::Spell],
+            ..Default::default()
+        };
+
+        let dps =
+            calculate_skill_dps_breakdown(&StatBlock::new(), &skill, &DotRegistry::with_defaults());
+        assert!((dps.hit_dps - 100.0).abs() < 0.01);
+        assert!(dps.dot_dps.abs() < f64::EPSILON);
+        assert!((dps.total - dps.hit_dps).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_bleed_conversion_adds_dot_dps() {
+        let skill = DamagePacketGenerator {
+            id: "rend".to_string(),
+            name: "Rend".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Physical, 100.0, 100.0)],
+            weapon_effectiveness: 0.0,
+            tags: vec![SkillTag::Spell],
+            status_conversions: SkillStatusConversions {
+                physical_to_bleed: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let registry = DotRegistry::with_defaults();
+
+        // 50 bleed status damage at 20% = 10 DPS per application. One
+        // application per second lasting 5s overlaps 5 times: the first
+        // counts fully, the next four at 50%.
+        let dps = calculate_skill_dps_breakdown(&StatBlock::new(), &skill, &registry);
+        assert!((dps.hit_dps - 100.0).abs() < 0.01);
+        assert!((dps.dot_dps - 10.0 * (1.0 + 4.0 * 0.5)).abs() < 0.01);
+        assert!((dps.total - (dps.hit_dps + dps.dot_dps)).abs() < f64::EPSILON);
+        assert!((calculate_skill_dps(&StatBlock::new(), &skill) - dps.total).abs() < f64::EPSILON);
+
+        // Player bleed duration extends the overlap until the 8-stack cap
+        let mut attacker = StatBlock::new();
+        attacker.status_effect_stats.bleed.duration_increased = 1.0;
+        let longer = calculate_skill_dps_breakdown(&attacker, &skill, &registry);
+        assert!((longer.dot_dps - 10.0 * (1.0 + 7.0 * 0.5)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_spell_dps_uses_cast_speed() {
+        let skill = DamagePacketGenerator {
+            id: "spark".to_string(),
+            name: "Spark".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Lightning, 100.0, 100.0)],
+            weapon_effectiveness: 0.0,
+            tags: vec![SkillTag::Spell],
+            ..Default::default()
+        };
+
+        let attacker = StatBlock::new();
+        let base_dps = calculate_skill_dps(&attacker, &skill);
+
+        // Attack speed does not affect spells
+        let mut fast_attacker = StatBlock::new();
+        fast_attacker.attack_speed.add_increased(1.0);
+        assert!((calculate_skill_dps(&fast_attacker, &skill) - base_dps).abs() < 0.01);
+
+        // Cast speed does
+        let mut fast_caster = StatBlock::new();
+        fast_caster.cast_speed.add_increased(1.0);
+        assert!((calculate_skill_dps(&fast_caster, &skill) - base_dps * 2.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_spell_ignores_weapon_and_uses_spell_damage() {
+        let fireball = DamagePacketGenerator {
+            id: "fireball".to_string(),
+            name: "Fireball".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Fire, 100.0, 100.0)],
+            base_crit_chance: 6.0,
+            tags: vec![SkillTag::Spell],
+            ..Default::default()
+        };
+
+        let attacker = StatBlock::new();
+        let base_dps = calculate_skill_dps(&attacker, &fireball);
+        assert!(base_dps > 0.0);
+
+        // A high-damage, high-crit weapon changes nothing for the spell
+        let mut armed = StatBlock::new();
+        armed.weapon_physical_min = 200.0;
+        armed.weapon_physical_max = 300.0;
+        armed.weapon_fire_min = 50.0;
+        armed.weapon_fire_max = 80.0;
+        armed.weapon_crit_chance = 10.0;
+        assert!((calculate_skill_dps(&armed, &fireball) - base_dps).abs() < 0.01);
+
+        // Spell damage scales spells only
+        let mut caster = StatBlock::new();
+        caster.spell_damage_increased = 0.5;
+        assert!((calculate_skill_dps(&caster, &fireball) - base_dps * 1.5).abs() < 0.01);
+
+        let slash = DamagePacketGenerator {
+            id: "slash".to_string(),
+            name: "Slash".to_string(),
+            weapon_effectiveness: 1.0,
+            tags: vec![SkillTag::Attack],
+            ..Default::default()
+        };
+        armed.spell_damage_increased = 0.5;
+        let mut unbuffed = armed.clone();
+        unbuffed.spell_damage_increased = 0.0;
+        assert!(
+            (calculate_skill_dps(&armed, &slash) - calculate_skill_dps(&unbuffed, &slash)).abs()
+                < 0.01
+        );
+    }
+
+    #[test]
+    fn test_rolled_damage_within_recorded_range() {
+        let mut attacker = StatBlock::new();
+        attacker.weapon_physical_min = 40.0;
+        attacker.weapon_physical_max = 90.0;
+        attacker.global_physical_damage.add_increased(0.5);
+        attacker.critical_chance.flat = 50.0;
+
+        let mut skill = DamagePacketGenerator::basic_attack();
+        skill.base_damages = vec![BaseDamage::new(DamageType::Fire, 5.0, 15.0)];
+        skill.damage_conversions.physical_to_cold = 0.25;
+
+        let mut rng = make_test_rng();
+        for _ in 0..50 {
+            let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+            let total = packet.total_damage();
+            assert!(packet.total_min() <= total + 1e-9 && total <= packet.total_max() + 1e-9);
+            assert!(packet.total_min() < packet.total_max());
+            for damage in &packet.damages {
+                assert!(
+                    damage.min() <= damage.amount + 1e-9 && damage.amount <= damage.max() + 1e-9
+                );
+            }
+        }
+
+        // 40-90 physical at 50% increased, 25% converted to cold, no crit
+        attacker.critical_chance.flat = 0.0;
+        attacker.weapon_crit_chance = 0.0;
+        let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+        let physical = packet
+            .damages
+            .iter()
+            .find(|d| d.damage_type == DamageType::Physical)
+            .unwrap();
+        assert!((physical.min() - 40.0 * 0.75 * 1.5).abs() < 0.01);
+        assert!((physical.max() - 90.0 * 0.75 * 1.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_crit_seeds_stronger_ailments() {
+        let attacker = StatBlock::new();
+        let mut skill = DamagePacketGenerator {
+            id: "fireball".to_string(),
+            name: "Fireball".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Fire, 100.0, 100.0)],
+            tags: vec![SkillTag::Spell],
+            status_conversions: crate::damage::SkillStatusConversions {
+                fire_to_burn: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let burn_damage = |packet: &DamagePacket| {
+            packet
+                .status_effects_to_apply
+                .iter()
+                .find(|s| s.effect_type == StatusEffect::Burn)
+                .unwrap()
+                .status_damage
+        };
+
+        let mut rng = make_test_rng();
+        let normal = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+        assert!(!normal.is_critical);
+
+        skill.base_crit_chance = 100.0;
+        let crit = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+        assert!(crit.is_critical);
+        let crit_multiplier = attacker.computed_crit_multiplier();
+        assert!((burn_damage(&crit) - burn_damage(&normal) * crit_multiplier).abs() < 0.01);
+
+        // Without ailment crits the burn matches a normal hit
+        skill.ailments_can_crit = false;
+        let crit = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+        assert!(crit.is_critical);
+        assert!((burn_damage(&crit) - burn_damage(&normal)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_effect_duration_increased_and_cap() {
+        let mut attacker = StatBlock::new();
+        attacker.effect_duration_increased = 1.0;
+        let skill = DamagePacketGenerator {
+            id: "fireball".to_string(),
+            name: "Fireball".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Fire, 100.0, 100.0)],
+            tags: vec![SkillTag::Spell],
+            status_conversions: crate::damage::SkillStatusConversions {
+                fire_to_burn: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let burn_duration = |packet: &DamagePacket| {
+            packet
+                .status_effects_to_apply
+                .iter()
+                .find(|s| s.effect_type == StatusEffect::Burn)
+                .unwrap()
+                .duration
+        };
+
+        // +100% doubles the 4s burn
+        let mut rng = make_test_rng();
+        let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+        assert!((burn_duration(&packet) - 8.0).abs() < 0.01);
+
+        // A 6s cap clamps it
+        let mut registry = DotRegistry::with_defaults();
+        let mut burn = registry.get("burn").unwrap().clone();
+        burn.max_duration = Some(6.0);
+        registry.register(burn);
+        let packet = calculate_damage_with_registry(
+            &attacker,
+            &skill,
+            "player".to_string(),
+            &registry,
+            &mut rng,
+        );
+        assert!((burn_duration(&packet) - 6.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_skill_and_gear_penetration_combine() {
+        let mut attacker = StatBlock::new();
+        attacker.fire_penetration.base = 10.0;
+        let skill = DamagePacketGenerator {
+            id: "fireball".to_string(),
+            name: "Fireball".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Fire, 100.0, 100.0)],
+            tags: vec![SkillTag::Spell],
+            penetration: crate::types::Penetration {
+                fire: 15.0,
+                cold: 5.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut rng = make_test_rng();
+        let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+        assert!((packet.fire_pen - 25.0).abs() < f64::EPSILON);
+        assert!((packet.cold_pen - 5.0).abs() < f64::EPSILON);
+        assert!((packet.skill_penetration.get(DamageType::Fire) - 15.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_effective_dps_against_resistant_defender() {
+        let attacker = StatBlock::new();
+        let skill = DamagePacketGenerator {
+            id: "fireball".to_string(),
+            name: "Fireball".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Fire, 100.0, 100.0)],
+            tags: vec![SkillTag::Spell],
+            status_conversions: crate::damage::SkillStatusConversions {
+                fire_to_burn: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let registry = DotRegistry::shared_defaults();
+        let raw = calculate_skill_dps_breakdown(&attacker, &skill, registry);
+
+        // No defenses: effective matches raw
+        let mut defender = StatBlock::new();
+        defender.max_life.base = 10_000.0;
+        defender.current_life = 10_000.0;
+        let effective = calculate_effective_dps(&attacker, &skill, &defender, registry);
+        assert!((effective.hit_dps - raw.hit_dps).abs() < 0.01);
+        assert!((effective.dot_dps - raw.dot_dps).abs() < 0.01);
+
+        // 75% fire resistance quarters both hits and burn
+        defender.fire_resistance.base = 75.0;
+        let effective = calculate_effective_dps(&attacker, &skill, &defender, registry);
+        assert!((effective.hit_dps - raw.hit_dps * 0.25).abs() < 0.01);
+        assert!((effective.dot_dps - raw.dot_dps * 0.25).abs() < 0.01);
+        assert!((effective.total - raw.total * 0.25).abs() < 0.01);
+
+        // The defender was not damaged
+        assert!((defender.current_life - 10_000.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_effective_dps_against_evasion_chance_defender() {
+        let attacker = StatBlock::new();
+        let skill = DamagePacketGenerator {
+            id: "fireball".to_string(),
+            name: "Fireball".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Fire, 100.0, 100.0)],
+            tags: vec![SkillTag::Spell],
+            status_conversions: crate::damage::SkillStatusConversions {
+                fire_to_burn: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let registry = DotRegistry::shared_defaults();
+        let raw = calculate_skill_dps_breakdown(&attacker, &skill, registry);
+
+        // Evasion equal to the attacker's accuracy evades half of all hits
+        let mut defender = StatBlock::new();
+        defender.max_life.base = 10_000.0;
+        defender.current_life = 10_000.0;
+        defender.evasion_mode = crate::types::EvasionMode::Chance;
+        defender.evasion.base = attacker.accuracy.compute();
+
+        let effective = calculate_effective_dps(&attacker, &skill, &defender, registry);
+        assert!((effective.hit_dps - raw.hit_dps * 0.5).abs() < 0.01);
+        assert!((effective.dot_dps - raw.dot_dps * 0.5).abs() < 0.01);
+
+        // An expectation, not a roll: always the same
+        for _ in 0..10 {
+            let again = calculate_effective_dps(&attacker, &skill, &defender, registry);
+            assert!((again.total - effective.total).abs() < f64::EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_effective_dps_uses_given_constants() {
+        let attacker = StatBlock::new();
+        let skill = DamagePacketGenerator {
+            id: "stone".to_string(),
+            name: "Stone".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Physical, 100.0, 100.0)],
+            tags: vec![SkillTag::Spell],
+            ..Default::default()
+        };
+        let registry = DotRegistry::shared_defaults();
+        let raw = calculate_skill_dps_breakdown(&attacker, &skill, registry);
+
+        // Enough armour to hit the reduction cap either way
+        let mut defender = StatBlock::new();
+        defender.armour.base = 1_000_000.0;
+
+        let default_cap = calculate_effective_dps(&attacker, &skill, &defender, registry);
+        assert!((default_cap.hit_dps - raw.hit_dps * 0.10).abs() < 0.01);
+
+        let mut constants = GameConstants::default();
+        constants.armour.max_reduction = 50.0;
+        let lower_cap = calculate_effective_dps_with_constants(
+            &attacker, &skill, &defender, registry, &constants,
+        );
+        assert!((lower_cap.hit_dps - raw.hit_dps * 0.50).abs() < 0.01);
+    }
 }
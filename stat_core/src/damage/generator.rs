@@ -1,7 +1,8 @@
 //! DamagePacketGenerator - Skill/ability damage configuration
 
-use crate::types::SkillTag;
-use loot_core::types::{DamageType, StatusEffect};
+use crate::config::ConfigError;
+use crate::types::{Penetration, SkillTag, ALL_DAMAGE_TYPES};
+use loot_core::types::{DamageType, ItemClass, StatusEffect};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -18,6 +19,12 @@ pub struct DamagePacketGenerator {
     /// Base damage values by type
     #[serde(default)]
     pub base_damages: Vec<BaseDamage>,
+    /// Roll damage ranges twice and keep the higher result
+    #[serde(default)]
+    pub lucky_damage: bool,
+    /// Roll damage ranges twice and keep the lower result (cancels out with lucky)
+    #[serde(default)]
+    pub unlucky_damage: bool,
 
     // === Scaling ===
     /// How much weapon damage to use (0.0 = pure spell, 1.0 = full attack)
@@ -37,6 +44,12 @@ pub struct DamagePacketGenerator {
     /// Added to base crit multiplier
     #[serde(default)]
     pub crit_multiplier_bonus: f64,
+    /// Roll the crit check twice and crit if either succeeds
+    #[serde(default)]
+    pub lucky_crit: bool,
+    /// Roll the crit check twice and crit only if both succeed (cancels out with lucky)
+    #[serde(default)]
+    pub unlucky_crit: bool,
 
     // === Tags ===
     /// Skill tags for categorization and scaling
@@ -54,6 +67,10 @@ pub struct DamagePacketGenerator {
     /// Applied before damage scaling
     #[serde(default)]
     pub damage_conversions: DamageConversions,
+    /// Gain a share of one type's damage as extra damage of another type
+    /// (e.g., 50% of physical as extra lightning), applied after conversions
+    #[serde(default)]
+    pub gain_as_extra: GainAsExtra,
 
     // === Per-Type Effectiveness ===
     /// Damage effectiveness multiplier for each damage type
@@ -71,9 +88,72 @@ pub struct DamagePacketGenerator {
     /// Number of chains
     #[serde(default)]
     pub chain_count: u32,
+    /// Damage multiplier applied per chain jump (0.9 = 10% less per jump)
+    #[serde(default = "default_chain_damage_multiplier")]
+    pub chain_damage_multiplier: f64,
     /// Chance to pierce targets (0.0 to 1.0)
     #[serde(default)]
     pub pierce_chance: f64,
+    /// Percentage points of the target's physical damage reduction ignored
+    #[serde(default)]
+    pub overwhelm: f64,
+    /// Flat resistance penetration, added to the attacker's own
+    #[serde(default)]
+    pub penetration: Penetration,
+    /// The hit deals no damage and only seeds ailments from its would-be damage (DoT skills)
+    #[serde(default)]
+    pub hit_deals_no_damage: bool,
+    /// Whether a critical hit's multiplier also applies to the status damage seeding ailments
+    #[serde(default = "default_ailments_can_crit")]
+    pub ailments_can_crit: bool,
+    /// Damage falloff with distance for area skills (None = full damage everywhere)
+    #[serde(default)]
+    pub aoe_falloff: Option<AoeFalloff>,
+
+    // === Requirements ===
+    /// Main-hand weapon classes the skill can be used with (empty = any)
+    #[serde(default)]
+    pub required_weapon_classes: Vec<ItemClass>,
+
+    // === Cost ===
+    /// Mana spent per use (0 = free)
+    #[serde(default)]
+    pub mana_cost: f64,
+}
+
+/// Distance-based damage falloff for area skills
+///
+/// Targets within `inner_radius` take full damage. Between the inner and
+/// outer radius damage drops linearly to `edge_effectiveness`; targets
+/// beyond `outer_radius` are not hit.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AoeFalloff {
+    /// Radius of the full-damage core
+    pub inner_radius: f64,
+    /// Radius of the whole area
+    pub outer_radius: f64,
+    /// Damage multiplier at the outer radius (0.5 = 50%)
+    #[serde(default = "default_edge_effectiveness")]
+    pub edge_effectiveness: f64,
+}
+
+fn default_edge_effectiveness() -> f64 {
+    0.5
+}
+
+impl AoeFalloff {
+    /// Damage multiplier for a target at `distance` from the center
+    pub fn multiplier_at(&self, distance: f64) -> f64 {
+        let distance = distance.abs();
+        if distance <= self.inner_radius {
+            1.0
+        } else if distance > self.outer_radius {
+            0.0
+        } else {
+            let t = (distance - self.inner_radius) / (self.outer_radius - self.inner_radius);
+            1.0 + (self.edge_effectiveness - 1.0) * t
+        }
+    }
 }
 
 /// Skill-specific status effect conversions
@@ -118,6 +198,22 @@ pub struct SkillStatusConversions {
 }
 
 impl SkillStatusConversions {
+    /// All conversion values with their config field names
+    fn named_values(&self) -> [(&'static str, f64); 10] {
+        [
+            ("physical_to_poison", self.physical_to_poison),
+            ("chaos_to_poison", self.chaos_to_poison),
+            ("physical_to_bleed", self.physical_to_bleed),
+            ("fire_to_burn", self.fire_to_burn),
+            ("cold_to_freeze", self.cold_to_freeze),
+            ("cold_to_chill", self.cold_to_chill),
+            ("lightning_to_static", self.lightning_to_static),
+            ("chaos_to_fear", self.chaos_to_fear),
+            ("physical_to_slow", self.physical_to_slow),
+            ("cold_to_slow", self.cold_to_slow),
+        ]
+    }
+
     /// Get conversion percentage from a damage type to a status effect
     pub fn get_conversion(&self, from: DamageType, to: StatusEffect) -> f64 {
         use loot_core::types::StatusEffect::*;
@@ -186,6 +282,20 @@ pub struct DamageConversions {
 }
 
 impl DamageConversions {
+    /// All conversion values with their config field names
+    fn named_values(&self) -> [(&'static str, f64); 8] {
+        [
+            ("physical_to_fire", self.physical_to_fire),
+            ("physical_to_cold", self.physical_to_cold),
+            ("physical_to_lightning", self.physical_to_lightning),
+            ("physical_to_chaos", self.physical_to_chaos),
+            ("lightning_to_fire", self.lightning_to_fire),
+            ("lightning_to_cold", self.lightning_to_cold),
+            ("cold_to_fire", self.cold_to_fire),
+            ("fire_to_chaos", self.fire_to_chaos),
+        ]
+    }
+
     /// Get conversion percentage from one damage type to another
     pub fn get_conversion(&self, from: DamageType, to: DamageType) -> f64 {
         match (from, to) {
@@ -213,69 +323,223 @@ impl DamageConversions {
             || self.fire_to_chaos > 0.0
     }
 
+    /// Add another set of conversions into this one (e.g. gear into a player's total)
+    pub fn merge(&mut self, other: &DamageConversions) {
+        self.physical_to_fire += other.physical_to_fire;
+        self.physical_to_cold += other.physical_to_cold;
+        self.physical_to_lightning += other.physical_to_lightning;
+        self.physical_to_chaos += other.physical_to_chaos;
+        self.lightning_to_fire += other.lightning_to_fire;
+        self.lightning_to_cold += other.lightning_to_cold;
+        self.cold_to_fire += other.cold_to_fire;
+        self.fire_to_chaos += other.fire_to_chaos;
+    }
+
+    /// Skill conversions combined with the player's conversions
+    pub fn combined(&self, other: &DamageConversions) -> DamageConversions {
+        let mut combined = self.clone();
+        combined.merge(other);
+        combined
+    }
+
     /// Apply conversions to a damage map, returning new damage values
     /// Conversion order: Physical -> Lightning -> Cold -> Fire
     pub fn apply(&self, damages: &HashMap<DamageType, f64>) -> HashMap<DamageType, f64> {
-        let mut result: HashMap<DamageType, f64> = HashMap::new();
+        let mut result: HashMap<DamageType, f64> = self
+            .convert(damages, |_| 0.0)
+            .into_iter()
+            .map(|(dt, (amount, _))| (dt, amount))
+            .collect();
+
+        // Remove zero/negative entries
+        result.retain(|_, v| *v > 0.0);
+        result
+    }
+
+    /// Apply conversions and the player's increased damage
+    ///
+    /// `increased` gives the total increased for each damage type (0.40 =
+    /// 40%). Unconverted damage is scaled by its own type's increase;
+    /// converted damage adds together the increases of every type it passed
+    /// through and of the type it ended up as, so 50% physical converted to
+    /// fire with 100% increased physical and 100% increased fire deals
+    /// 1 + 1.0 + 1.0 = 3× (increases are additive).
+    pub fn apply_with_scaling(
+        &self,
+        damages: &HashMap<DamageType, f64>,
+        increased: impl Fn(DamageType) -> f64,
+    ) -> HashMap<DamageType, f64> {
+        let mut result: HashMap<DamageType, f64> = self
+            .convert(damages, &increased)
+            .into_iter()
+            .map(|(dt, (amount, carried))| (dt, amount * (1.0 + increased(dt)) + carried))
+            .collect();
+
+        result.retain(|_, v| *v > 0.0);
+        result
+    }
+
+    /// Run the conversion chain
+    ///
+    /// Tracks, per type, the converted amount and the extra damage that
+    /// amount gets from the increases of the types it was converted from
+    /// (`amount × Σ increased`). A type converting more than 100% in total
+    /// is scaled down proportionally.
+    fn convert(
+        &self,
+        damages: &HashMap<DamageType, f64>,
+        increased: impl Fn(DamageType) -> f64,
+    ) -> HashMap<DamageType, (f64, f64)> {
+        let mut result: HashMap<DamageType, (f64, f64)> = HashMap::new();
 
         // Start with original values
         for (dt, amt) in damages {
-            *result.entry(*dt).or_insert(0.0) += amt;
+            let entry = result.entry(*dt).or_insert((0.0, 0.0));
+            entry.0 += amt;
         }
 
-        // Convert Physical (first in order)
-        if let Some(&phys) = result.get(&DamageType::Physical) {
-            let to_fire = phys * self.physical_to_fire;
-            let to_cold = phys * self.physical_to_cold;
-            let to_lightning = phys * self.physical_to_lightning;
-            let to_chaos = phys * self.physical_to_chaos;
-            let total_converted = (to_fire + to_cold + to_lightning + to_chaos).min(phys);
-
-            if total_converted > 0.0 {
-                *result.entry(DamageType::Physical).or_insert(0.0) -= total_converted;
-                *result.entry(DamageType::Fire).or_insert(0.0) += to_fire;
-                *result.entry(DamageType::Cold).or_insert(0.0) += to_cold;
-                *result.entry(DamageType::Lightning).or_insert(0.0) += to_lightning;
-                *result.entry(DamageType::Chaos).or_insert(0.0) += to_chaos;
+        // Physical first, fire last (fire can only go to chaos)
+        let order: [(DamageType, &[DamageType]); 4] = [
+            (
+                DamageType::Physical,
+                &[
+                    DamageType::Fire,
+                    DamageType::Cold,
+                    DamageType::Lightning,
+                    DamageType::Chaos,
+                ],
+            ),
+            (DamageType::Lightning, &[DamageType::Fire, DamageType::Cold]),
+            (DamageType::Cold, &[DamageType::Fire]),
+            (DamageType::Fire, &[DamageType::Chaos]),
+        ];
+
+        for (from, targets) in order {
+            let Some(&(amount, carried)) = result.get(&from) else {
+                continue;
+            };
+            let total: f64 = targets
+                .iter()
+                .map(|to| self.get_conversion(from, *to))
+                .sum();
+            if total <= 0.0 || amount <= 0.0 {
+                continue;
             }
-        }
-
-        // Convert Lightning (second in order)
-        if let Some(&lightning) = result.get(&DamageType::Lightning) {
-            let to_fire = lightning * self.lightning_to_fire;
-            let to_cold = lightning * self.lightning_to_cold;
-            let total_converted = (to_fire + to_cold).min(lightning);
 
-            if total_converted > 0.0 {
-                *result.entry(DamageType::Lightning).or_insert(0.0) -= total_converted;
-                *result.entry(DamageType::Fire).or_insert(0.0) += to_fire;
-                *result.entry(DamageType::Cold).or_insert(0.0) += to_cold;
+            let scale = if total > 1.0 { 1.0 / total } else { 1.0 };
+            let remaining = 1.0 - total * scale;
+            result.insert(from, (amount * remaining, carried * remaining));
+
+            let from_increased = increased(from);
+            for to in targets {
+                let fraction = self.get_conversion(from, *to) * scale;
+                if fraction > 0.0 {
+                    let entry = result.entry(*to).or_insert((0.0, 0.0));
+                    entry.0 += amount * fraction;
+                    entry.1 += (carried + amount * from_increased) * fraction;
+                }
             }
         }
 
-        // Convert Cold (third in order)
-        if let Some(&cold) = result.get(&DamageType::Cold) {
-            let to_fire = cold * self.cold_to_fire;
+        result
+    }
+}
 
-            if to_fire > 0.0 {
-                *result.entry(DamageType::Cold).or_insert(0.0) -= to_fire;
-                *result.entry(DamageType::Fire).or_insert(0.0) += to_fire;
-            }
+/// "Gain as extra" damage configuration
+///
+/// Values are fractions (0.5 = 50%) of the source type's damage added as
+/// extra damage of the destination type. Unlike conversion, the source
+/// keeps all of its damage. Gains are taken from post-conversion damage and
+/// the extra damage only scales with the destination type's modifiers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GainAsExtra {
+    #[serde(default)]
+    pub physical_as_fire: f64,
+    #[serde(default)]
+    pub physical_as_cold: f64,
+    #[serde(default)]
+    pub physical_as_lightning: f64,
+    #[serde(default)]
+    pub physical_as_chaos: f64,
+    #[serde(default)]
+    pub lightning_as_fire: f64,
+    #[serde(default)]
+    pub lightning_as_cold: f64,
+    #[serde(default)]
+    pub cold_as_fire: f64,
+    #[serde(default)]
+    pub fire_as_chaos: f64,
+}
+
+impl GainAsExtra {
+    /// All gain values with their config field names
+    fn named_values(&self) -> [(&'static str, f64); 8] {
+        [
+            ("physical_as_fire", self.physical_as_fire),
+            ("physical_as_cold", self.physical_as_cold),
+            ("physical_as_lightning", self.physical_as_lightning),
+            ("physical_as_chaos", self.physical_as_chaos),
+            ("lightning_as_fire", self.lightning_as_fire),
+            ("lightning_as_cold", self.lightning_as_cold),
+            ("cold_as_fire", self.cold_as_fire),
+            ("fire_as_chaos", self.fire_as_chaos),
+        ]
+    }
+
+    /// Get the fraction of `from` damage gained as extra `to` damage
+    pub fn get_gain(&self, from: DamageType, to: DamageType) -> f64 {
+        match (from, to) {
+            (DamageType::Physical, DamageType::Fire) => self.physical_as_fire,
+            (DamageType::Physical, DamageType::Cold) => self.physical_as_cold,
+            (DamageType::Physical, DamageType::Lightning) => self.physical_as_lightning,
+            (DamageType::Physical, DamageType::Chaos) => self.physical_as_chaos,
+            (DamageType::Lightning, DamageType::Fire) => self.lightning_as_fire,
+            (DamageType::Lightning, DamageType::Cold) => self.lightning_as_cold,
+            (DamageType::Cold, DamageType::Fire) => self.cold_as_fire,
+            (DamageType::Fire, DamageType::Chaos) => self.fire_as_chaos,
+            _ => 0.0,
         }
+    }
+
+    /// Check if any extra damage is gained
+    pub fn has_gains(&self) -> bool {
+        self.named_values().iter().any(|(_, value)| *value > 0.0)
+    }
+
+    /// Add another set of gains into this one (e.g. gear into a player's total)
+    pub fn merge(&mut self, other: &GainAsExtra) {
+        self.physical_as_fire += other.physical_as_fire;
+        self.physical_as_cold += other.physical_as_cold;
+        self.physical_as_lightning += other.physical_as_lightning;
+        self.physical_as_chaos += other.physical_as_chaos;
+        self.lightning_as_fire += other.lightning_as_fire;
+        self.lightning_as_cold += other.lightning_as_cold;
+        self.cold_as_fire += other.cold_as_fire;
+        self.fire_as_chaos += other.fire_as_chaos;
+    }
 
-        // Convert Fire (last in order, can only go to chaos)
-        if let Some(&fire) = result.get(&DamageType::Fire) {
-            let to_chaos = fire * self.fire_to_chaos;
+    /// Skill gains combined with the player's gains
+    pub fn combined(&self, other: &GainAsExtra) -> GainAsExtra {
+        let mut combined = self.clone();
+        combined.merge(other);
+        combined
+    }
 
-            if to_chaos > 0.0 {
-                *result.entry(DamageType::Fire).or_insert(0.0) -= to_chaos;
-                *result.entry(DamageType::Chaos).or_insert(0.0) += to_chaos;
+    /// Extra damage gained from a damage map, keyed by destination type
+    ///
+    /// Every gain reads the original map, so extra damage is never gained
+    /// again from other extra damage.
+    pub fn extra_damages(&self, damages: &HashMap<DamageType, f64>) -> HashMap<DamageType, f64> {
+        let mut extra: HashMap<DamageType, f64> = HashMap::new();
+        for (from, amount) in damages {
+            for to in ALL_DAMAGE_TYPES {
+                let gain = self.get_gain(*from, to);
+                if gain > 0.0 && *amount > 0.0 {
+                    *extra.entry(to).or_insert(0.0) += amount * gain;
+                }
             }
         }
-
-        // Remove zero/negative entries
-        result.retain(|_, v| *v > 0.0);
-        result
+        extra
     }
 }
 
@@ -345,25 +609,46 @@ fn default_hits() -> u32 {
     1
 }
 
+fn default_chain_damage_multiplier() -> f64 {
+    1.0
+}
+
+fn default_ailments_can_crit() -> bool {
+    true
+}
+
 impl Default for DamagePacketGenerator {
     fn default() -> Self {
         DamagePacketGenerator {
             id: "default".to_string(),
             name: "Default Attack".to_string(),
             base_damages: vec![],
+            lucky_damage: false,
+            unlucky_damage: false,
             weapon_effectiveness: 1.0,
             damage_effectiveness: 1.0,
             attack_speed_modifier: 1.0,
             base_crit_chance: 0.0,
             crit_multiplier_bonus: 0.0,
+            lucky_crit: false,
+            unlucky_crit: false,
             tags: vec![SkillTag::Attack],
             status_conversions: SkillStatusConversions::default(),
             damage_conversions: DamageConversions::default(),
+            gain_as_extra: GainAsExtra::default(),
             type_effectiveness: DamageTypeEffectiveness::default(),
             hits_per_attack: 1,
             can_chain: false,
             chain_count: 0,
+            chain_damage_multiplier: 1.0,
             pierce_chance: 0.0,
+            overwhelm: 0.0,
+            penetration: Penetration::default(),
+            hit_deals_no_damage: false,
+            ailments_can_crit: true,
+            aoe_falloff: None,
+            required_weapon_classes: Vec::new(),
+            mana_cost: 0.0,
         }
     }
 }
@@ -375,22 +660,102 @@ impl DamagePacketGenerator {
             id: "basic_attack".to_string(),
             name: "Basic Attack".to_string(),
             base_damages: vec![],
+            lucky_damage: false,
+            unlucky_damage: false,
             weapon_effectiveness: 1.0,
             damage_effectiveness: 1.0,
             attack_speed_modifier: 1.0,
             base_crit_chance: 0.0,
             crit_multiplier_bonus: 0.0,
+            lucky_crit: false,
+            unlucky_crit: false,
             tags: vec![SkillTag::Attack, SkillTag::Melee],
             status_conversions: SkillStatusConversions::default(),
             damage_conversions: DamageConversions::default(),
+            gain_as_extra: GainAsExtra::default(),
             type_effectiveness: DamageTypeEffectiveness::default(),
             hits_per_attack: 1,
             can_chain: false,
             chain_count: 0,
+            chain_damage_multiplier: 1.0,
             pierce_chance: 0.0,
+            overwhelm: 0.0,
+            penetration: Penetration::default(),
+            hit_deals_no_damage: false,
+            ailments_can_crit: true,
+            aoe_falloff: None,
+            required_weapon_classes: Vec::new(),
+            mana_cost: 0.0,
         }
     }
 
+    /// Check the skill for values that would produce meaningless damage
+    ///
+    /// Effectiveness, crit chance, chain multiplier, mana cost and gains as
+    /// extra must be non-negative, `hits_per_attack` at least 1, every damage
+    /// or status conversion within 0.0..=1.0, and any AoE falloff well-formed.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |message: String| {
+            Err(ConfigError::ValidationError(format!(
+                "Skill '{}': {}",
+                self.id, message
+            )))
+        };
+
+        for (field, value) in [
+            ("weapon_effectiveness", self.weapon_effectiveness),
+            ("damage_effectiveness", self.damage_effectiveness),
+            ("base_crit_chance", self.base_crit_chance),
+            ("chain_damage_multiplier", self.chain_damage_multiplier),
+            ("mana_cost", self.mana_cost),
+        ] {
+            if value < 0.0 {
+                return invalid(format!("{} must be non-negative, got {}", field, value));
+            }
+        }
+        if self.hits_per_attack < 1 {
+            return invalid("hits_per_attack must be at least 1, got 0".to_string());
+        }
+
+        let conversions = self
+            .damage_conversions
+            .named_values()
+            .into_iter()
+            .chain(self.status_conversions.named_values());
+        for (field, value) in conversions {
+            if !(0.0..=1.0).contains(&value) {
+                return invalid(format!(
+                    "conversion {} must be between 0.0 and 1.0, got {}",
+                    field, value
+                ));
+            }
+        }
+        for (field, value) in self.gain_as_extra.named_values() {
+            if value < 0.0 {
+                return invalid(format!(
+                    "gain_as_extra {} must be non-negative, got {}",
+                    field, value
+                ));
+            }
+        }
+
+        if let Some(falloff) = &self.aoe_falloff {
+            if falloff.inner_radius < 0.0 || falloff.outer_radius < falloff.inner_radius {
+                return invalid(format!(
+                    "aoe_falloff radii must satisfy 0 <= inner <= outer, got {} and {}",
+                    falloff.inner_radius, falloff.outer_radius
+                ));
+            }
+            if !(0.0..=1.0).contains(&falloff.edge_effectiveness) {
+                return invalid(format!(
+                    "aoe_falloff edge_effectiveness must be between 0.0 and 1.0, got {}",
+                    falloff.edge_effectiveness
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// Check if this skill is an attack (uses weapon)
     pub fn is_attack(&self) -> bool {
         self.tags.contains(&SkillTag::Attack)
@@ -492,6 +857,72 @@ mod tests {
         assert!((attack.weapon_effectiveness - 1.0).abs() < f64::EPSILON);
     }
 
+    fn validation_error(skill: &DamagePacketGenerator) -> String {
+        match skill.validate() {
+            Err(ConfigError::ValidationError(message)) => message,
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_accepts_defaults() {
+        assert!(DamagePacketGenerator::basic_attack().validate().is_ok());
+        assert!(DamagePacketGenerator::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_negative_effectiveness() {
+        let skill = DamagePacketGenerator {
+            id: "broken".to_string(),
+            weapon_effectiveness: -0.5,
+            ..Default::default()
+        };
+        assert_eq!(
+            validation_error(&skill),
+            "Skill 'broken': weapon_effectiveness must be non-negative, got -0.5"
+        );
+    }
+
+    #[test]
+    fn test_validate_zero_hits() {
+        let skill = DamagePacketGenerator {
+            hits_per_attack: 0,
+            ..Default::default()
+        };
+        assert!(validation_error(&skill).contains("hits_per_attack must be at least 1"));
+    }
+
+    #[test]
+    fn test_validate_conversion_range() {
+        let skill = DamagePacketGenerator {
+            damage_conversions: DamageConversions {
+                physical_to_fire: 1.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(validation_error(&skill)
+            .contains("conversion physical_to_fire must be between 0.0 and 1.0, got 1.5"));
+
+        let skill = DamagePacketGenerator {
+            status_conversions: SkillStatusConversions {
+                physical_to_bleed: -0.1,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(validation_error(&skill).contains("conversion physical_to_bleed"));
+    }
+
+    #[test]
+    fn test_validate_negative_crit_chance() {
+        let skill = DamagePacketGenerator {
+            base_crit_chance: -5.0,
+            ..Default::default()
+        };
+        assert!(validation_error(&skill).contains("base_crit_chance must be non-negative, got -5"));
+    }
+
     #[test]
     fn test_base_damage_average() {
         let damage = BaseDamage::new(DamageType::Physical, 10.0, 20.0);
@@ -564,6 +995,73 @@ mod tests {
         assert!((result.get(&DamageType::Fire).unwrap_or(&0.0) - 12.5).abs() < 0.001);
     }
 
+    #[test]
+    fn test_damage_conversion_scaling_uses_both_types() {
+        let conv = DamageConversions {
+            physical_to_fire: 0.5,
+            ..Default::default()
+        };
+
+        let mut input = HashMap::new();
+        input.insert(DamageType::Physical, 100.0);
+
+        // +100% physical and +100% fire
+        let result = conv.apply_with_scaling(&input, |dt| match dt {
+            DamageType::Physical | DamageType::Fire => 1.0,
+            _ => 0.0,
+        });
+
+        // Unconverted physical: 50 * 2; converted fire: 50 * (1 + 1 + 1), not 50 * 2 * 2
+        assert!((result.get(&DamageType::Physical).unwrap_or(&0.0) - 100.0).abs() < 0.001);
+        assert!((result.get(&DamageType::Fire).unwrap_or(&0.0) - 150.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_combined_conversions_cap_at_full() {
+        let skill = DamageConversions {
+            physical_to_fire: 0.75,
+            ..Default::default()
+        };
+        let player = DamageConversions {
+            physical_to_cold: 0.75,
+            ..Default::default()
+        };
+
+        let mut input = HashMap::new();
+        input.insert(DamageType::Physical, 100.0);
+
+        // 150% total is scaled down to 100%, split evenly
+        let result = skill.combined(&player).apply(&input);
+        assert!(!result.contains_key(&DamageType::Physical));
+        assert!((result.get(&DamageType::Fire).unwrap_or(&0.0) - 50.0).abs() < 0.001);
+        assert!((result.get(&DamageType::Cold).unwrap_or(&0.0) - 50.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_aoe_falloff_multiplier() {
+        let falloff = AoeFalloff {
+            inner_radius: 2.0,
+            outer_radius: 6.0,
+            edge_effectiveness: 0.5,
+        };
+
+        assert!((falloff.multiplier_at(0.0) - 1.0).abs() < f64::EPSILON);
+        assert!((falloff.multiplier_at(2.0) - 1.0).abs() < f64::EPSILON);
+        assert!((falloff.multiplier_at(4.0) - 0.75).abs() < 0.001);
+        assert!((falloff.multiplier_at(6.0) - 0.5).abs() < 0.001);
+        assert!(falloff.multiplier_at(6.1).abs() < f64::EPSILON);
+
+        let skill = DamagePacketGenerator {
+            aoe_falloff: Some(AoeFalloff {
+                inner_radius: 3.0,
+                outer_radius: 1.0,
+                edge_effectiveness: 0.5,
+            }),
+            ..Default::default()
+        };
+        assert!(validation_error(&skill).contains("aoe_falloff radii"));
+    }
+
     #[test]
     fn test_type_effectiveness() {
         let eff = DamageTypeEffectiveness {
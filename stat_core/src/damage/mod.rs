@@ -3,7 +3,19 @@
 mod calculation;
 mod generator;
 mod packet;
+mod profile;
 
-pub use calculation::{calculate_damage, calculate_skill_dps};
-pub use generator::{BaseDamage, DamagePacketGenerator, DotApplication, SkillStatusConversions};
-pub use packet::{DamagePacket, FinalDamage, PendingDoT, PendingStatusEffect};
+pub use calculation::{
+    calculate_damage, calculate_damage_per_hit, calculate_damage_with_constants,
+    calculate_damage_with_registry, calculate_effective_crit_chance, calculate_effective_dps,
+    calculate_effective_dps_with_constants, calculate_expected_damage, calculate_skill_dps,
+    calculate_skill_dps_breakdown, DpsBreakdown,
+};
+pub use generator::{
+    AoeFalloff, BaseDamage, DamageConversions, DamagePacketGenerator, DotApplication, GainAsExtra,
+    SkillStatusConversions,
+};
+pub use packet::{
+    DamagePacket, DamageProvenance, FinalDamage, PendingDoT, PendingStatusEffect, TypeProvenance,
+};
+pub use profile::DamageProfile;
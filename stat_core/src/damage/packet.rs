@@ -1,5 +1,7 @@
 //! DamagePacket - The output of damage calculation
 
+use super::AoeFalloff;
+use crate::types::{Penetration, ResistanceReduction};
 use loot_core::types::{DamageType, StatusEffect};
 use serde::{Deserialize, Serialize};
 
@@ -23,10 +25,35 @@ pub struct DamagePacket {
     pub crit_multiplier: f64,
 
     // === Penetration ===
+    /// Total flat penetration per element (attacker + skill)
     pub fire_pen: f64,
     pub cold_pen: f64,
     pub lightning_pen: f64,
     pub chaos_pen: f64,
+    /// Fraction of the target's positive resistance ignored, before flat penetration (0.25 = 25%)
+    #[serde(default)]
+    pub fire_pen_percent: f64,
+    #[serde(default)]
+    pub cold_pen_percent: f64,
+    #[serde(default)]
+    pub lightning_pen_percent: f64,
+    #[serde(default)]
+    pub chaos_pen_percent: f64,
+    /// The skill's share of the flat penetration above, for display
+    #[serde(default)]
+    pub skill_penetration: Penetration,
+
+    // === Resistance Reduction ===
+    /// Resistance reduction applied to the target on hit
+    /// (lingers on the target, unlike penetration)
+    #[serde(default)]
+    pub resistance_reduction_applied: ResistanceReduction,
+    /// Percentage points of the target's physical damage reduction ignored
+    #[serde(default)]
+    pub overwhelm: f64,
+    /// Targets left below this fraction of max life are killed (0 = off)
+    #[serde(default)]
+    pub culling_strike_threshold: f64,
 
     // === DoT Effects to Apply ===
     /// DoTs that should be applied from this hit
@@ -39,14 +66,43 @@ pub struct DamagePacket {
     // === Accuracy ===
     /// Attacker's accuracy rating (used vs defender's evasion)
     pub accuracy: f64,
+    /// Chance for the hit to land (0.0 to 1.0), rolled before anything else on resolution
+    #[serde(default = "default_hit_chance")]
+    pub hit_chance: f64,
 
     // === Metadata ===
-    /// For multi-hit tracking
+    /// Whether this damage comes from a spell
+    #[serde(default)]
+    pub is_spell: bool,
+    /// Number of hits, each dealing the packet's full damage
     pub hit_count: u32,
     /// Whether this hit can trigger leech
     pub can_leech: bool,
+    /// Fraction of damage dealt leeched as life by the attacker (0.02 = 2%)
+    #[serde(default)]
+    pub life_leech: f64,
+    /// Fraction of damage dealt leeched as mana by the attacker
+    #[serde(default)]
+    pub mana_leech: f64,
+    /// Life the attacker gains per hit that deals damage
+    #[serde(default)]
+    pub life_on_hit: f64,
     /// Whether this hit can trigger on-hit effects
     pub can_apply_on_hit: bool,
+    /// Distance falloff of the skill that produced this packet (area skills)
+    #[serde(default)]
+    pub aoe_falloff: Option<AoeFalloff>,
+    /// Number of times this hit chains to further targets (0 = no chaining)
+    #[serde(default)]
+    pub chain_count: u32,
+    /// Damage multiplier applied per chain jump
+    #[serde(default = "default_chain_damage_multiplier")]
+    pub chain_damage_multiplier: f64,
+
+    // === Provenance ===
+    /// Multipliers that produced the damages, for display
+    #[serde(default)]
+    pub provenance: Option<DamageProvenance>,
 }
 
 impl Default for DamagePacket {
@@ -61,16 +117,41 @@ impl Default for DamagePacket {
             cold_pen: 0.0,
             lightning_pen: 0.0,
             chaos_pen: 0.0,
+            fire_pen_percent: 0.0,
+            cold_pen_percent: 0.0,
+            lightning_pen_percent: 0.0,
+            chaos_pen_percent: 0.0,
+            skill_penetration: Penetration::default(),
+            resistance_reduction_applied: ResistanceReduction::default(),
+            overwhelm: 0.0,
+            culling_strike_threshold: 0.0,
             dots_to_apply: Vec::new(),
             status_effects_to_apply: Vec::new(),
             accuracy: 1000.0, // Default accuracy
+            hit_chance: 1.0,
+            is_spell: false,
             hit_count: 1,
             can_leech: true,
+            life_leech: 0.0,
+            mana_leech: 0.0,
+            life_on_hit: 0.0,
             can_apply_on_hit: true,
+            aoe_falloff: None,
+            chain_count: 0,
+            chain_damage_multiplier: 1.0,
+            provenance: None,
         }
     }
 }
 
+fn default_hit_chance() -> f64 {
+    1.0
+}
+
+fn default_chain_damage_multiplier() -> f64 {
+    1.0
+}
+
 impl DamagePacket {
     /// Create a new empty damage packet
     pub fn new(source_id: String, skill_id: String) -> Self {
@@ -86,6 +167,16 @@ impl DamagePacket {
         self.damages.iter().map(|d| d.amount).sum()
     }
 
+    /// Get the lowest total damage the packet's rolls could have produced
+    pub fn total_min(&self) -> f64 {
+        self.damages.iter().map(|d| d.min()).sum()
+    }
+
+    /// Get the highest total damage the packet's rolls could have produced
+    pub fn total_max(&self) -> f64 {
+        self.damages.iter().map(|d| d.max()).sum()
+    }
+
     /// Get damage for a specific type
     pub fn damage_of_type(&self, damage_type: DamageType) -> f64 {
         self.damages
@@ -100,17 +191,68 @@ impl DamagePacket {
         if let Some(existing) = self.damages.iter_mut().find(|d| d.damage_type == damage_type) {
             existing.amount += amount;
         } else {
-            self.damages.push(FinalDamage {
-                damage_type,
-                amount,
-            });
+            self.damages.push(FinalDamage::new(damage_type, amount));
+        }
+    }
+
+    /// Copy of this packet as received by a target `distance` from the center
+    ///
+    /// Damages, status damage and DoT DPS are scaled by the AoE falloff
+    /// curve. Packets without falloff are returned unchanged; targets beyond
+    /// the outer radius get a packet with no damage or ailments.
+    pub fn at_distance(&self, distance: f64) -> DamagePacket {
+        match self.aoe_falloff {
+            Some(falloff) => self.scaled(falloff.multiplier_at(distance)),
+            None => self.clone(),
+        }
+    }
+
+    /// Copy of this packet as received by the target hit on chain jump `jump_index`
+    ///
+    /// Jump 0 is the initial target and takes full damage; each later jump
+    /// multiplies damage by `chain_damage_multiplier` again
+    /// (`multiplier^jump_index`). Jumps past `chain_count` never land and get
+    /// a packet with no damage or ailments.
+    pub fn after_chain(&self, jump_index: u32) -> DamagePacket {
+        if jump_index > self.chain_count {
+            return self.scaled(0.0);
         }
+        self.scaled(self.chain_damage_multiplier.powi(jump_index as i32))
+    }
+
+    /// Copy with damages, status damage and DoT DPS scaled by `multiplier`
+    ///
+    /// A zero multiplier strips all damage and ailments.
+    fn scaled(&self, multiplier: f64) -> DamagePacket {
+        let mut packet = self.clone();
+        if multiplier <= 0.0 {
+            packet.damages.clear();
+            packet.dots_to_apply.clear();
+            packet.status_effects_to_apply.clear();
+            packet.can_leech = false;
+            packet.can_apply_on_hit = false;
+            return packet;
+        }
+
+        for damage in &mut packet.damages {
+            damage.scale(multiplier);
+        }
+        for dot in &mut packet.dots_to_apply {
+            dot.damage_per_second *= multiplier;
+        }
+        for status in &mut packet.status_effects_to_apply {
+            status.status_damage *= multiplier;
+            status.dot_dps *= multiplier;
+        }
+        packet
     }
 
     /// Get penetration for a damage type
+    ///
+    /// Physical returns overwhelm, which ignores physical damage reduction.
     pub fn penetration(&self, damage_type: DamageType) -> f64 {
         match damage_type {
-            DamageType::Physical => 0.0, // Physical doesn't penetrate
+            DamageType::Physical => self.overwhelm,
             DamageType::Fire => self.fire_pen,
             DamageType::Cold => self.cold_pen,
             DamageType::Lightning => self.lightning_pen,
@@ -118,6 +260,29 @@ impl DamagePacket {
         }
     }
 
+    /// Get the fraction of resistance ignored for a damage type
+    pub fn penetration_percent(&self, damage_type: DamageType) -> f64 {
+        match damage_type {
+            DamageType::Physical => 0.0,
+            DamageType::Fire => self.fire_pen_percent,
+            DamageType::Cold => self.cold_pen_percent,
+            DamageType::Lightning => self.lightning_pen_percent,
+            DamageType::Chaos => self.chaos_pen_percent,
+        }
+    }
+
+    /// Get the chance that at least one pending status effect applies
+    ///
+    /// Each status rolls independently, so this is `1 - Π(1 - chance)`.
+    pub fn any_ailment_chance(&self, target_max_life: f64) -> f64 {
+        let none_apply: f64 = self
+            .status_effects_to_apply
+            .iter()
+            .map(|s| 1.0 - s.calculate_apply_chance(target_max_life))
+            .product();
+        1.0 - none_apply
+    }
+
     /// Check if this packet has any damage
     pub fn has_damage(&self) -> bool {
         self.total_damage() > 0.0
@@ -133,11 +298,60 @@ impl DamagePacket {
     }
 }
 
+/// Record of the multipliers that produced a packet's damages
+///
+/// For each type, `amount = base × increased × more × effectiveness × crit_multiplier`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DamageProvenance {
+    /// Per-type multipliers
+    pub types: Vec<TypeProvenance>,
+    /// Critical multiplier applied to all types (1.0 if not a crit)
+    pub crit_multiplier: f64,
+}
+
+impl DamageProvenance {
+    /// Get the provenance for a specific damage type
+    pub fn get(&self, damage_type: DamageType) -> Option<&TypeProvenance> {
+        self.types.iter().find(|t| t.damage_type == damage_type)
+    }
+
+    /// Reconstruct the final amount for a damage type from its multipliers
+    pub fn final_amount(&self, damage_type: DamageType) -> f64 {
+        self.get(damage_type)
+            .map(|t| t.scaled_amount() * self.crit_multiplier)
+            .unwrap_or(0.0)
+    }
+}
+
+/// Multipliers applied to a single damage type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypeProvenance {
+    pub damage_type: DamageType,
+    /// Base damage after rolling and conversion, before scaling
+    pub base: f64,
+    /// Total increased multiplier (1 + sum of increased%)
+    pub increased_multiplier: f64,
+    /// Total more multiplier (product of more multipliers)
+    pub more_multiplier: f64,
+    /// Skill damage effectiveness × type effectiveness
+    pub effectiveness: f64,
+}
+
+impl TypeProvenance {
+    /// Amount after scaling, before crit
+    pub fn scaled_amount(&self) -> f64 {
+        self.base * self.increased_multiplier * self.more_multiplier * self.effectiveness
+    }
+}
+
 /// Final damage value for a single damage type
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FinalDamage {
     pub damage_type: DamageType,
     pub amount: f64,
+    /// Lowest and highest amount the roll could have produced (None = not rolled)
+    #[serde(default)]
+    pub range: Option<(f64, f64)>,
 }
 
 impl FinalDamage {
@@ -145,6 +359,26 @@ impl FinalDamage {
         FinalDamage {
             damage_type,
             amount,
+            range: None,
+        }
+    }
+
+    /// Lowest possible amount (the amount itself if not rolled)
+    pub fn min(&self) -> f64 {
+        self.range.map_or(self.amount, |(min, _)| min)
+    }
+
+    /// Highest possible amount (the amount itself if not rolled)
+    pub fn max(&self) -> f64 {
+        self.range.map_or(self.amount, |(_, max)| max)
+    }
+
+    /// Multiply the amount and its range
+    pub fn scale(&mut self, multiplier: f64) {
+        self.amount *= multiplier;
+        if let Some((min, max)) = &mut self.range {
+            *min *= multiplier;
+            *max *= multiplier;
         }
     }
 }
@@ -269,6 +503,70 @@ mod tests {
         assert!((packet.damage_of_type(DamageType::Physical) - 0.0).abs() < f64::EPSILON);
     }
 
+    fn aoe_packet() -> DamagePacket {
+        let mut packet = DamagePacket::new("player".to_string(), "fireball".to_string());
+        packet.add_damage(DamageType::Fire, 100.0);
+        packet
+            .status_effects_to_apply
+            .push(PendingStatusEffect::new_with_dot(
+                StatusEffect::Burn,
+                40.0,
+                4.0,
+                1.0,
+                10.0,
+            ));
+        packet.aoe_falloff = Some(AoeFalloff {
+            inner_radius: 1.0,
+            outer_radius: 5.0,
+            edge_effectiveness: 0.5,
+        });
+        packet
+    }
+
+    #[test]
+    fn test_at_distance_center_is_full_damage() {
+        let packet = aoe_packet().at_distance(0.5);
+        assert!((packet.total_damage() - 100.0).abs() < f64::EPSILON);
+        assert!((packet.status_effects_to_apply[0].dot_dps - 10.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_at_distance_edge_is_reduced() {
+        let packet = aoe_packet().at_distance(5.0);
+        assert!((packet.total_damage() - 50.0).abs() < 0.001);
+        assert!((packet.status_effects_to_apply[0].status_damage - 20.0).abs() < 0.001);
+        assert!((packet.status_effects_to_apply[0].dot_dps - 5.0).abs() < 0.001);
+
+        // Halfway through the falloff band
+        assert!((aoe_packet().at_distance(3.0).total_damage() - 75.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_at_distance_beyond_radius_misses() {
+        let packet = aoe_packet().at_distance(5.5);
+        assert!(packet.damages.is_empty());
+        assert!(packet.status_effects_to_apply.is_empty());
+
+        // No falloff means full damage at any range
+        let mut single_target = aoe_packet();
+        single_target.aoe_falloff = None;
+        assert!((single_target.at_distance(50.0).total_damage() - 100.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_third_chain_jump_is_reduced() {
+        let mut packet = DamagePacket::new("player".to_string(), "arc".to_string());
+        packet.add_damage(DamageType::Lightning, 100.0);
+        packet.chain_count = 3;
+        packet.chain_damage_multiplier = 0.9;
+
+        assert!((packet.after_chain(0).total_damage() - 100.0).abs() < f64::EPSILON);
+        // 100 * 0.9^3
+        assert!((packet.after_chain(3).total_damage() - 72.9).abs() < 0.001);
+        // The chain ends after three jumps
+        assert!(packet.after_chain(4).damages.is_empty());
+    }
+
     #[test]
     fn test_damage_packet_add_same_type() {
         let mut packet = DamagePacket::new("player".to_string(), "fireball".to_string());
@@ -279,6 +577,30 @@ mod tests {
         assert_eq!(packet.damages.len(), 1);
     }
 
+    #[test]
+    fn test_any_ailment_chance() {
+        let mut packet = DamagePacket::new("player".to_string(), "rend".to_string());
+        // 50 status damage vs 100 max life = 50% each
+        packet
+            .status_effects_to_apply
+            .push(PendingStatusEffect::new(
+                StatusEffect::Poison,
+                50.0,
+                2.0,
+                1.0,
+            ));
+        packet
+            .status_effects_to_apply
+            .push(PendingStatusEffect::new(
+                StatusEffect::Bleed,
+                50.0,
+                5.0,
+                1.0,
+            ));
+
+        assert!((packet.any_ailment_chance(100.0) - 0.75).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn test_pending_dot_total() {
         let dot = PendingDoT::new("ignite".to_string(), 25.0, 4.0);
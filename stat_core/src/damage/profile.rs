@@ -0,0 +1,111 @@
+//! DamageProfile - Describes incoming damage without a full skill setup
+
+use super::DamagePacket;
+use crate::combat::CombatResult;
+use crate::stat_block::StatBlock;
+use loot_core::types::DamageType;
+use serde::{Deserialize, Serialize};
+
+/// A simple description of an incoming hit, for "what if" defensive queries
+///
+/// ```rust,ignore
+/// let profile = DamageProfile::spell(DamageType::Fire, 500.0);
+/// let result = player.receive_profile(&profile);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DamageProfile {
+    /// Damage per type in the hit
+    #[serde(default)]
+    pub hits: Vec<(DamageType, f64)>,
+    /// Whether the hit is a spell
+    #[serde(default)]
+    pub is_spell: bool,
+    /// Whether the hit is a critical strike (amounts already include the crit)
+    #[serde(default)]
+    pub is_crit: bool,
+}
+
+impl DamageProfile {
+    /// Create an empty profile
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a single-type attack hit
+    pub fn attack(damage_type: DamageType, amount: f64) -> Self {
+        Self::new().with_hit(damage_type, amount)
+    }
+
+    /// Create a single-type spell hit
+    pub fn spell(damage_type: DamageType, amount: f64) -> Self {
+        Self::attack(damage_type, amount).with_spell(true)
+    }
+
+    /// Add damage of a type to the hit
+    pub fn with_hit(mut self, damage_type: DamageType, amount: f64) -> Self {
+        self.hits.push((damage_type, amount));
+        self
+    }
+
+    /// Set whether the hit is a spell
+    pub fn with_spell(mut self, is_spell: bool) -> Self {
+        self.is_spell = is_spell;
+        self
+    }
+
+    /// Set whether the hit is a critical strike
+    pub fn with_crit(mut self, is_crit: bool) -> Self {
+        self.is_crit = is_crit;
+        self
+    }
+
+    /// Total damage in the profile
+    pub fn total_damage(&self) -> f64 {
+        self.hits.iter().map(|(_, amount)| amount).sum()
+    }
+
+    /// Build a synthetic damage packet for this profile
+    ///
+    /// The packet has no penetration, ailments or on-hit effects.
+    pub fn to_packet(&self, source_id: impl Into<String>) -> DamagePacket {
+        let mut packet = DamagePacket::new(source_id.into(), "damage_profile".to_string());
+        for &(damage_type, amount) in &self.hits {
+            packet.add_damage(damage_type, amount);
+        }
+        packet.is_spell = self.is_spell;
+        packet.is_critical = self.is_crit;
+        packet.crit_multiplier = 1.0;
+        packet.can_leech = false;
+        packet.can_apply_on_hit = false;
+        packet
+    }
+}
+
+impl StatBlock {
+    /// Resolve a damage profile against this block without changing it
+    pub fn receive_profile(&self, profile: &DamageProfile) -> CombatResult {
+        let packet = profile.to_packet("damage_profile");
+        let (_, result) = self.receive_damage(&packet);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::defense::calculate_resistance_mitigation;
+
+    #[test]
+    fn test_fire_profile_against_fire_resistance() {
+        let mut block = StatBlock::new();
+        block.fire_resistance.base = 40.0;
+
+        let profile = DamageProfile::spell(DamageType::Fire, 500.0);
+        let result = block.receive_profile(&profile);
+
+        let expected = calculate_resistance_mitigation(500.0, 40.0, 0.0);
+        assert!((expected - 300.0).abs() < 0.01);
+        assert!((result.damage_taken[0].final_amount - expected).abs() < 0.01);
+        assert!((result.damage_reduced_by_resists - 200.0).abs() < 0.01);
+    }
+}
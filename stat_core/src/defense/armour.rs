@@ -1,6 +1,6 @@
 //! Armour - Physical damage reduction with diminishing returns
 
-use super::constants::ARMOUR_CONSTANT;
+use super::constants::{ARMOUR_CONSTANT, MAX_PHYS_REDUCTION};
 
 /// Calculate physical damage reduction from armour
 ///
@@ -8,7 +8,8 @@ use super::constants::ARMOUR_CONSTANT;
 /// `Reduction = Armour / (Armour + CONSTANT * Damage)`
 ///
 /// This makes armour more effective against many small hits
-/// and less effective against large hits.
+/// and less effective against large hits. The reduction is capped at
+/// `MAX_PHYS_REDUCTION`, so even enormous armour lets some damage through.
 ///
 /// # Arguments
 /// * `armour` - The defender's armour value
@@ -17,34 +18,54 @@ use super::constants::ARMOUR_CONSTANT;
 /// # Returns
 /// The damage after armour reduction
 pub fn calculate_armour_reduction(armour: f64, damage: f64) -> f64 {
+    calculate_armour_reduction_with_cap(armour, damage, MAX_PHYS_REDUCTION)
+}
+
+/// Calculate physical damage after armour with a custom reduction cap (percent)
+pub fn calculate_armour_reduction_with_cap(
+    armour: f64,
+    damage: f64,
+    max_reduction_percent: f64,
+) -> f64 {
     if damage <= 0.0 {
         return 0.0;
     }
-    if armour <= 0.0 {
-        return damage;
-    }
 
-    let reduction_percent = armour / (armour + ARMOUR_CONSTANT * damage);
-    let reduced = damage * (1.0 - reduction_percent);
-
-    reduced.max(0.0)
+    let reduction =
+        armour_reduction_percent_with_cap(armour, damage, max_reduction_percent) / 100.0;
+    (damage * (1.0 - reduction)).max(0.0)
 }
 
 /// Calculate the damage reduction percentage for a given armour and damage value
+///
+/// This is the share of a hit of `damage` that armour removes, capped at
+/// `MAX_PHYS_REDUCTION`; use it to display armour mitigation.
 pub fn armour_reduction_percent(armour: f64, damage: f64) -> f64 {
+    armour_reduction_percent_with_cap(armour, damage, MAX_PHYS_REDUCTION)
+}
+
+/// Calculate the armour reduction percentage with a custom cap (percent)
+pub fn armour_reduction_percent_with_cap(
+    armour: f64,
+    damage: f64,
+    max_reduction_percent: f64,
+) -> f64 {
     if damage <= 0.0 || armour <= 0.0 {
         return 0.0;
     }
 
-    (armour / (armour + ARMOUR_CONSTANT * damage) * 100.0).clamp(0.0, 100.0)
+    let cap = max_reduction_percent.clamp(0.0, 100.0);
+    (armour / (armour + ARMOUR_CONSTANT * damage) * 100.0).clamp(0.0, cap)
 }
 
 /// Calculate how much armour is needed to reduce damage by a target percentage
+///
+/// Returns infinity for targets above `MAX_PHYS_REDUCTION`.
 pub fn armour_needed_for_reduction(damage: f64, target_reduction_percent: f64) -> f64 {
     if target_reduction_percent <= 0.0 {
         return 0.0;
     }
-    if target_reduction_percent >= 100.0 {
+    if target_reduction_percent > MAX_PHYS_REDUCTION || target_reduction_percent >= 100.0 {
         return f64::INFINITY;
     }
 
@@ -120,8 +141,27 @@ mod tests {
     fn test_high_armour() {
         // Very high armour vs small hit
         let result = calculate_armour_reduction(10000.0, 10.0);
-        // 10000 / (10000 + 50) = 99.5% reduction
-        // Damage = 10 * 0.005 = 0.05
-        assert!(result < 1.0);
+        // 10000 / (10000 + 50) = 99.5% reduction, capped at 90%
+        // Damage = 10 * 0.1 = 1.0
+        assert!((result - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_reduction_cap_only_engages_at_the_top() {
+        // Low: 100 / (100 + 500) = 16.67%
+        assert!((armour_reduction_percent(100.0, 100.0) - 16.67).abs() < 0.01);
+        // Medium: 1000 / (1000 + 500) = 66.67%
+        assert!((armour_reduction_percent(1000.0, 100.0) - 66.67).abs() < 0.01);
+        // Extreme: 100000 / (100000 + 500) = 99.5%, capped
+        assert!(
+            (armour_reduction_percent(100_000.0, 100.0) - MAX_PHYS_REDUCTION).abs() < f64::EPSILON
+        );
+        assert!((calculate_armour_reduction(100_000.0, 100.0) - 10.0).abs() < 0.01);
+
+        // A custom cap
+        assert!((calculate_armour_reduction_with_cap(100_000.0, 100.0, 50.0) - 50.0).abs() < 0.01);
+        assert!((calculate_armour_reduction_with_cap(100.0, 100.0, 50.0) - 83.33).abs() < 0.01);
+
+        assert_eq!(armour_needed_for_reduction(100.0, 95.0), f64::INFINITY);
     }
 }
@@ -15,8 +15,17 @@
 //! - Accuracy is an offensive stat that counters evasion
 //! - Evasion provides diminishing-returns protection against big hits
 //! - High evasion protects against one-shots from low-accuracy attackers
+//!
+//! Entities using `EvasionMode::Chance` instead evade whole hits with
+//! chance `evasion / (evasion + accuracy)`, capped at `MAX_EVADE_CHANCE`.
+//!
+//! Independently of either mode, `calculate_hit_chance` gives a flat to-hit
+//! chance from accuracy vs a defense rating, rolled before mitigation when
+//! `HitConstants::roll_accuracy` is enabled.
 
-use super::constants::EVASION_SCALE_FACTOR;
+use super::constants::{
+    EVASION_SCALE_FACTOR, HIT_CHANCE_EXPONENT, HIT_CHANCE_SCALE, MAX_EVADE_CHANCE, MIN_HIT_CHANCE,
+};
 
 /// Calculate the damage cap based on accuracy vs evasion
 ///
@@ -72,6 +81,32 @@ pub fn evasion_needed_for_cap(accuracy: f64, target_cap: f64) -> f64 {
     EVASION_SCALE_FACTOR * (accuracy / target_cap - 1.0)
 }
 
+/// Calculate the chance (0.0 to 1.0) to evade a hit entirely
+///
+/// Equal accuracy and evasion gives a 50% chance; no accuracy is always evaded
+/// (up to `MAX_EVADE_CHANCE`).
+pub fn calculate_evade_chance(accuracy: f64, evasion: f64) -> f64 {
+    if evasion <= 0.0 {
+        return 0.0;
+    }
+    (evasion / (evasion + accuracy.max(0.0))).min(MAX_EVADE_CHANCE)
+}
+
+/// Calculate the chance (0.0 to 1.0) for a hit to land from accuracy vs defense rating
+///
+/// `HIT_CHANCE_SCALE * accuracy / (accuracy + defense_rating^HIT_CHANCE_EXPONENT)`,
+/// clamped to `MIN_HIT_CHANCE..=1.0`. The exponent gives defense diminishing
+/// returns, so accuracy well above the defense rating always hits.
+pub fn calculate_hit_chance(accuracy: f64, defense_rating: f64) -> f64 {
+    if defense_rating <= 0.0 {
+        return 1.0;
+    }
+    let accuracy = accuracy.max(0.0);
+    let chance =
+        HIT_CHANCE_SCALE * accuracy / (accuracy + defense_rating.powf(HIT_CHANCE_EXPONENT));
+    chance.clamp(MIN_HIT_CHANCE, 1.0)
+}
+
 /// Calculate what percentage of incoming damage was evaded
 pub fn evasion_effectiveness(accuracy: f64, evasion: f64, damage: f64) -> f64 {
     if damage <= 0.0 {
@@ -201,4 +236,35 @@ mod tests {
         let needed = evasion_needed_for_cap(2000.0, 500.0);
         assert!((needed - 3000.0).abs() < f64::EPSILON);
     }
+
+    #[test]
+    fn test_evade_chance() {
+        assert!(calculate_evade_chance(1000.0, 0.0).abs() < f64::EPSILON);
+        assert!((calculate_evade_chance(1000.0, 1000.0) - 0.5).abs() < f64::EPSILON);
+        assert!((calculate_evade_chance(1000.0, 3000.0) - 0.75).abs() < f64::EPSILON);
+        // Capped so hits can always land
+        assert!((calculate_evade_chance(0.0, 1000.0) - MAX_EVADE_CHANCE).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_hit_chance() {
+        // No defense rating: always hit
+        assert!((calculate_hit_chance(100.0, 0.0) - 1.0).abs() < f64::EPSILON);
+        // High accuracy vs modest defense: capped at 100%
+        assert!((calculate_hit_chance(4000.0, 1000.0) - 1.0).abs() < f64::EPSILON);
+        // Low accuracy vs high defense: 1.25 * 200 / (200 + 5000^0.9) ≈ 10.7%
+        let chance = calculate_hit_chance(200.0, 5000.0);
+        assert!((chance - 0.107).abs() < 0.001);
+        // No accuracy still lands the minimum
+        assert!((calculate_hit_chance(0.0, 5000.0) - MIN_HIT_CHANCE).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_hit_chance_diminishing_returns() {
+        // Doubling defense less than halves hit chance
+        let base = calculate_hit_chance(500.0, 4000.0);
+        let doubled = calculate_hit_chance(500.0, 8000.0);
+        assert!(doubled < base);
+        assert!(doubled > base / 2.0);
+    }
 }
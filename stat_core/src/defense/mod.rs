@@ -1,18 +1,31 @@
-//! Defense system - Armour, Evasion, Resistances
+//! Defense system - Armour, Evasion, Resistances, Damage taken as
 
 mod armour;
 mod evasion;
 mod resistance;
+mod taken_as;
 
-pub use armour::calculate_armour_reduction;
-pub use evasion::{apply_evasion_cap, calculate_damage_cap};
-pub use resistance::calculate_resistance_mitigation;
+pub use armour::{
+    armour_reduction_percent, armour_reduction_percent_with_cap, calculate_armour_reduction,
+    calculate_armour_reduction_with_cap,
+};
+pub use evasion::{
+    apply_evasion_cap, calculate_damage_cap, calculate_evade_chance, calculate_hit_chance,
+};
+pub use resistance::{
+    calculate_resistance_mitigation, calculate_resistance_mitigation_with_cap,
+    calculate_resistance_mitigation_with_percent_pen, cap_penetration,
+};
+pub use taken_as::{DamageReroute, TakenAsConversions};
 
 /// Defense calculation constants
 pub mod constants {
     /// Maximum resistance cap (100% = immunity)
     pub const MAX_RESISTANCE: f64 = 100.0;
 
+    /// Default per-element maximum resistance, raised by gear up to `MAX_RESISTANCE`
+    pub const DEFAULT_MAX_RESISTANCE: f64 = 75.0;
+
     /// Minimum resistance (can go negative)
     pub const MIN_RESISTANCE: f64 = -200.0;
 
@@ -22,7 +35,25 @@ pub mod constants {
     /// Armour formula constant (higher = armour less effective vs big hits)
     pub const ARMOUR_CONSTANT: f64 = 5.0;
 
+    /// Maximum physical damage reduction from armour in percent
+    pub const MAX_PHYS_REDUCTION: f64 = 90.0;
+
     /// Evasion scaling factor (controls diminishing returns)
     /// Formula: damage_cap = accuracy / (1 + evasion / SCALE_FACTOR)
     pub const EVASION_SCALE_FACTOR: f64 = 1000.0;
+
+    /// Maximum chance to evade a hit in chance mode (hits always land 5% of the time)
+    pub const MAX_EVADE_CHANCE: f64 = 0.95;
+
+    /// Hit chance scaling: hit = SCALE * accuracy / (accuracy + defense^EXPONENT)
+    pub const HIT_CHANCE_SCALE: f64 = 1.25;
+
+    /// Exponent applied to defense rating in the hit chance formula (< 1 = diminishing returns)
+    pub const HIT_CHANCE_EXPONENT: f64 = 0.9;
+
+    /// Minimum chance for an accuracy-rolled hit to land
+    pub const MIN_HIT_CHANCE: f64 = 0.05;
+
+    /// Maximum block chance in percent
+    pub const MAX_BLOCK_CHANCE: f64 = 75.0;
 }
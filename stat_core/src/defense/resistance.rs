@@ -4,8 +4,11 @@
 //! Penetration has reduced effectiveness vs capped resistance.
 //!
 //! Formula:
-//! - If resistance >= cap: effective_resist = cap - (penetration * 0.5)
-//! - Otherwise: effective_resist = resistance - penetration
+//! - resist = min(resistance, cap)
+//! - Percent penetration ignores a share of positive resistance:
+//!   resist = resist * (1 - percent_pen)
+//! - If resist is still >= cap: effective_resist = cap - (penetration * 0.5)
+//! - Otherwise: effective_resist = resist - penetration
 //! - damage_taken = damage * (1 - effective_resist / 100)
 
 use super::constants::{MAX_RESISTANCE, MIN_RESISTANCE, PENETRATION_VS_CAPPED};
@@ -20,11 +23,50 @@ use super::constants::{MAX_RESISTANCE, MIN_RESISTANCE, PENETRATION_VS_CAPPED};
 /// # Returns
 /// The damage after resistance mitigation
 pub fn calculate_resistance_mitigation(damage: f64, resistance: f64, penetration: f64) -> f64 {
+    calculate_resistance_mitigation_with_cap(damage, resistance, penetration, MAX_RESISTANCE)
+}
+
+/// Calculate damage after resistance mitigation against a per-element cap
+///
+/// `max_resistance` replaces the global `MAX_RESISTANCE` (it cannot exceed it).
+/// Resistance above the cap is treated as capped, and penetration can still
+/// push effective resistance below zero.
+pub fn calculate_resistance_mitigation_with_cap(
+    damage: f64,
+    resistance: f64,
+    penetration: f64,
+    max_resistance: f64,
+) -> f64 {
+    calculate_resistance_mitigation_with_percent_pen(
+        damage,
+        resistance,
+        0.0,
+        penetration,
+        max_resistance,
+    )
+}
+
+/// Calculate damage after resistance mitigation with percent and flat penetration
+///
+/// `percent_penetration` (0.25 = 25% of resistance ignored) applies before
+/// the flat `penetration`; see the module docs for the full formula.
+pub fn calculate_resistance_mitigation_with_percent_pen(
+    damage: f64,
+    resistance: f64,
+    percent_penetration: f64,
+    penetration: f64,
+    max_resistance: f64,
+) -> f64 {
     if damage <= 0.0 {
         return 0.0;
     }
 
-    let effective_resist = calculate_effective_resistance(resistance, penetration);
+    let effective_resist = calculate_effective_resistance_with_percent_pen(
+        resistance,
+        percent_penetration,
+        penetration,
+        max_resistance,
+    );
     let mitigation = effective_resist / 100.0;
 
     // Damage multiplier: 1.0 = full damage, 0.0 = no damage, >1.0 = extra damage
@@ -33,21 +75,54 @@ pub fn calculate_resistance_mitigation(damage: f64, resistance: f64, penetration
     (damage * damage_mult).max(0.0)
 }
 
+/// Limit total flat penetration to an optional cap (None = uncapped)
+pub fn cap_penetration(penetration: f64, max_penetration: Option<f64>) -> f64 {
+    match max_penetration {
+        Some(max) => penetration.min(max),
+        None => penetration,
+    }
+}
+
 /// Calculate effective resistance after penetration
 ///
 /// Penetration has 50% effectiveness vs capped resistance.
 pub fn calculate_effective_resistance(resistance: f64, penetration: f64) -> f64 {
-    let clamped_resist = resistance.clamp(MIN_RESISTANCE, MAX_RESISTANCE);
+    calculate_effective_resistance_with_cap(resistance, penetration, MAX_RESISTANCE)
+}
+
+/// Calculate effective resistance after penetration against a per-element cap
+pub fn calculate_effective_resistance_with_cap(
+    resistance: f64,
+    penetration: f64,
+    max_resistance: f64,
+) -> f64 {
+    calculate_effective_resistance_with_percent_pen(resistance, 0.0, penetration, max_resistance)
+}
 
-    let effective = if clamped_resist >= MAX_RESISTANCE {
+/// Calculate effective resistance after percent and flat penetration
+pub fn calculate_effective_resistance_with_percent_pen(
+    resistance: f64,
+    percent_penetration: f64,
+    penetration: f64,
+    max_resistance: f64,
+) -> f64 {
+    let cap = max_resistance.clamp(MIN_RESISTANCE, MAX_RESISTANCE);
+    let mut clamped_resist = resistance.clamp(MIN_RESISTANCE, cap);
+
+    // Percent penetration only ignores positive resistance
+    if clamped_resist > 0.0 && percent_penetration > 0.0 {
+        clamped_resist *= 1.0 - percent_penetration.min(1.0);
+    }
+
+    let effective = if clamped_resist >= cap {
         // Capped: penetration is half as effective
-        MAX_RESISTANCE - (penetration * PENETRATION_VS_CAPPED)
+        cap - (penetration * PENETRATION_VS_CAPPED)
     } else {
         // Not capped: full penetration
         clamped_resist - penetration
     };
 
-    effective.clamp(MIN_RESISTANCE, MAX_RESISTANCE)
+    effective.clamp(MIN_RESISTANCE, cap)
 }
 
 /// Calculate the resistance needed to achieve a target damage reduction
@@ -107,6 +182,13 @@ mod tests {
         assert!((result - 50.0).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_cap_penetration() {
+        assert!((cap_penetration(40.0, None) - 40.0).abs() < f64::EPSILON);
+        assert!((cap_penetration(40.0, Some(25.0)) - 25.0).abs() < f64::EPSILON);
+        assert!((cap_penetration(10.0, Some(25.0)) - 10.0).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn test_penetration_vs_capped() {
         // 100% resistance (capped), 30% penetration
@@ -156,6 +238,28 @@ mod tests {
         assert!((damage - 15.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_raised_cap_mitigates_up_to_cap() {
+        // 80% resistance under a 90% cap mitigates 80%
+        let result = calculate_resistance_mitigation_with_cap(100.0, 80.0, 0.0, 90.0);
+        assert!((result - 20.0).abs() < 0.01);
+
+        // Overcapped resistance is limited to the element's cap
+        let result = calculate_resistance_mitigation_with_cap(100.0, 95.0, 0.0, 90.0);
+        assert!((result - 10.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_penetration_vs_element_cap() {
+        // At a 90% cap, 40% pen is half effective: 90 - 20 = 70
+        let effective = calculate_effective_resistance_with_cap(95.0, 40.0, 90.0);
+        assert!((effective - 70.0).abs() < f64::EPSILON);
+
+        // Below the cap, pen applies fully and can go below zero
+        let effective = calculate_effective_resistance_with_cap(20.0, 50.0, 90.0);
+        assert!((effective - -30.0).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn test_penetration_needed_capped() {
         // Need to reduce 100% resist to 50% resist
@@ -171,4 +275,26 @@ mod tests {
         let needed = penetration_needed(75.0, 50.0);
         assert!((needed - 25.0).abs() < f64::EPSILON);
     }
+
+    #[test]
+    fn test_percent_pen_before_flat_pen() {
+        // 75% capped resistance, 20% of it ignored = 60%
+        let effective = calculate_effective_resistance_with_percent_pen(90.0, 0.20, 0.0, 75.0);
+        assert!((effective - 60.0).abs() < 0.001);
+
+        // Then 10 flat pen at full effectiveness, since 60% is below the cap
+        let effective = calculate_effective_resistance_with_percent_pen(90.0, 0.20, 10.0, 75.0);
+        assert!((effective - 50.0).abs() < 0.001);
+        let damage =
+            calculate_resistance_mitigation_with_percent_pen(100.0, 90.0, 0.20, 10.0, 75.0);
+        assert!((damage - 50.0).abs() < 0.001);
+
+        // Without percent pen, flat pen against the cap is halved
+        let effective = calculate_effective_resistance_with_percent_pen(90.0, 0.0, 10.0, 75.0);
+        assert!((effective - 70.0).abs() < 0.001);
+
+        // Negative resistance is not raised
+        let effective = calculate_effective_resistance_with_percent_pen(-30.0, 0.50, 0.0, 75.0);
+        assert!((effective + 30.0).abs() < 0.001);
+    }
 }
@@ -0,0 +1,173 @@
+//! Damage taken as - Defender-side rerouting of incoming hit damage into other types
+
+use crate::types::ALL_DAMAGE_TYPES;
+use loot_core::types::DamageType;
+use serde::{Deserialize, Serialize};
+
+/// Fractions of incoming hit damage taken as another type (0.20 = 20%)
+///
+/// Applied before any mitigation, so each rerouted portion is mitigated by
+/// the defenses of its new type. Rerouting happens once: damage taken as
+/// fire is not then taken as chaos. If a type's fractions sum past 100%,
+/// they are scaled down to share it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TakenAsConversions {
+    #[serde(default)]
+    pub physical_as_fire: f64,
+    #[serde(default)]
+    pub physical_as_cold: f64,
+    #[serde(default)]
+    pub physical_as_lightning: f64,
+    #[serde(default)]
+    pub physical_as_chaos: f64,
+    #[serde(default)]
+    pub fire_as_chaos: f64,
+    #[serde(default)]
+    pub cold_as_chaos: f64,
+    #[serde(default)]
+    pub lightning_as_chaos: f64,
+}
+
+/// Incoming damage moved from one type to another before mitigation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DamageReroute {
+    pub from: DamageType,
+    pub to: DamageType,
+    /// Raw damage moved, before mitigation
+    pub amount: f64,
+}
+
+impl TakenAsConversions {
+    /// Get the fraction of `from` damage taken as `to`
+    pub fn get(&self, from: DamageType, to: DamageType) -> f64 {
+        match (from, to) {
+            (DamageType::Physical, DamageType::Fire) => self.physical_as_fire,
+            (DamageType::Physical, DamageType::Cold) => self.physical_as_cold,
+            (DamageType::Physical, DamageType::Lightning) => self.physical_as_lightning,
+            (DamageType::Physical, DamageType::Chaos) => self.physical_as_chaos,
+            (DamageType::Fire, DamageType::Chaos) => self.fire_as_chaos,
+            (DamageType::Cold, DamageType::Chaos) => self.cold_as_chaos,
+            (DamageType::Lightning, DamageType::Chaos) => self.lightning_as_chaos,
+            _ => 0.0,
+        }
+    }
+
+    /// Check if any damage is rerouted
+    pub fn is_empty(&self) -> bool {
+        ALL_DAMAGE_TYPES
+            .iter()
+            .all(|&from| ALL_DAMAGE_TYPES.iter().all(|&to| self.get(from, to) <= 0.0))
+    }
+
+    /// Add another set of conversions into this one (e.g. gear into a player's total)
+    pub fn merge(&mut self, other: &TakenAsConversions) {
+        self.physical_as_fire += other.physical_as_fire;
+        self.physical_as_cold += other.physical_as_cold;
+        self.physical_as_lightning += other.physical_as_lightning;
+        self.physical_as_chaos += other.physical_as_chaos;
+        self.fire_as_chaos += other.fire_as_chaos;
+        self.cold_as_chaos += other.cold_as_chaos;
+        self.lightning_as_chaos += other.lightning_as_chaos;
+    }
+
+    /// Reroute incoming damage amounts, merging them per type
+    ///
+    /// Types keep their incoming order, with newly introduced types
+    /// appended. Returns the damage to mitigate and what was moved.
+    pub fn reroute(
+        &self,
+        damages: &[(DamageType, f64)],
+    ) -> (Vec<(DamageType, f64)>, Vec<DamageReroute>) {
+        fn add(result: &mut Vec<(DamageType, f64)>, damage_type: DamageType, amount: f64) {
+            match result.iter_mut().find(|(t, _)| *t == damage_type) {
+                Some((_, existing)) => *existing += amount,
+                None => result.push((damage_type, amount)),
+            }
+        }
+
+        let mut result: Vec<(DamageType, f64)> = Vec::with_capacity(damages.len());
+        let mut reroutes: Vec<DamageReroute> = Vec::new();
+        for &(from, amount) in damages {
+            let total: f64 = ALL_DAMAGE_TYPES
+                .iter()
+                .map(|&to| self.get(from, to).max(0.0))
+                .sum();
+            if amount <= 0.0 || total <= 0.0 {
+                add(&mut result, from, amount);
+                continue;
+            }
+
+            let scale = if total > 1.0 { 1.0 / total } else { 1.0 };
+            let start = reroutes.len();
+            let mut remaining = amount;
+            for to in ALL_DAMAGE_TYPES {
+                let moved = amount * self.get(from, to).max(0.0) * scale;
+                if moved > 0.0 {
+                    remaining -= moved;
+                    reroutes.push(DamageReroute {
+                        from,
+                        to,
+                        amount: moved,
+                    });
+                }
+            }
+            add(&mut result, from, remaining.max(0.0));
+            for reroute in &reroutes[start..] {
+                add(&mut result, reroute.to, reroute.amount);
+            }
+        }
+
+        (result, reroutes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reroute_splits_and_merges() {
+        let taken_as = TakenAsConversions {
+            physical_as_fire: 0.5,
+            ..Default::default()
+        };
+        let (damages, reroutes) =
+            taken_as.reroute(&[(DamageType::Physical, 100.0), (DamageType::Fire, 20.0)]);
+
+        assert_eq!(damages.len(), 2);
+        assert_eq!(damages[0].0, DamageType::Physical);
+        assert!((damages[0].1 - 50.0).abs() < f64::EPSILON);
+        assert_eq!(damages[1].0, DamageType::Fire);
+        assert!((damages[1].1 - 70.0).abs() < f64::EPSILON);
+        assert_eq!(
+            reroutes,
+            vec![DamageReroute {
+                from: DamageType::Physical,
+                to: DamageType::Fire,
+                amount: 50.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_reroute_over_full_is_scaled() {
+        let taken_as = TakenAsConversions {
+            physical_as_fire: 0.75,
+            physical_as_cold: 0.75,
+            ..Default::default()
+        };
+        let (damages, _) = taken_as.reroute(&[(DamageType::Physical, 100.0)]);
+
+        let amount = |t| {
+            damages
+                .iter()
+                .find(|(d, _)| *d == t)
+                .map_or(0.0, |(_, a)| *a)
+        };
+        assert!(amount(DamageType::Physical).abs() < 0.01);
+        assert!((amount(DamageType::Fire) - 50.0).abs() < 0.01);
+        assert!((amount(DamageType::Cold) - 50.0).abs() < 0.01);
+        assert!(TakenAsConversions::default().is_empty());
+        assert!(!taken_as.is_empty());
+    }
+}
@@ -6,13 +6,17 @@ mod types;
 
 pub use active::ActiveDoT;
 pub use tick::apply_dot;
-pub use types::{DotConfig, DotStacking};
+pub use types::{DotConfig, DotStacking, StackDecision};
 
-use loot_core::types::DamageType;
+use crate::config::{read_dot_configs, ConfigError};
+use loot_core::types::{DamageType, StatusEffect};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
+use std::sync::OnceLock;
 
 /// DoT type registry
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DotRegistry {
     /// Mapping from DoT type ID to configuration
     configs: HashMap<String, DotConfig>,
@@ -36,6 +40,14 @@ impl DotRegistry {
         self.configs.get(id)
     }
 
+    /// Shared registry of the default DoT types
+    ///
+    /// Used when applying ailments without an explicit registry.
+    pub fn shared_defaults() -> &'static DotRegistry {
+        static DEFAULTS: OnceLock<DotRegistry> = OnceLock::new();
+        DEFAULTS.get_or_init(DotRegistry::with_defaults)
+    }
+
     /// Load default DoT types
     pub fn with_defaults() -> Self {
         let mut registry = Self::new();
@@ -52,6 +64,7 @@ impl DotRegistry {
             max_stacks: 1,
             stack_effectiveness: 1.0,
             moving_multiplier: 1.0,
+            max_duration: None,
         });
 
         // Poison - chaos DoT, unlimited stacking
@@ -66,6 +79,7 @@ impl DotRegistry {
             max_stacks: 999,
             stack_effectiveness: 1.0,
             moving_multiplier: 1.0,
+            max_duration: None,
         });
 
         // Bleed - physical DoT, limited stacking
@@ -83,6 +97,7 @@ impl DotRegistry {
             max_stacks: 8,
             stack_effectiveness: 0.5,
             moving_multiplier: 2.0, // Bleed deals double damage while moving
+            max_duration: None,
         });
 
         // Freeze - cold status, no DoT damage
@@ -97,6 +112,7 @@ impl DotRegistry {
             max_stacks: 1,
             stack_effectiveness: 1.0,
             moving_multiplier: 1.0,
+            max_duration: None,
         });
 
         // Chill - cold status, no DoT damage
@@ -111,6 +127,7 @@ impl DotRegistry {
             max_stacks: 1,
             stack_effectiveness: 1.0,
             moving_multiplier: 1.0,
+            max_duration: None,
         });
 
         // Static - lightning status, no DoT damage
@@ -128,6 +145,7 @@ impl DotRegistry {
             max_stacks: 3,
             stack_effectiveness: 1.0,
             moving_multiplier: 1.0,
+            max_duration: None,
         });
 
         // Fear - chaos status, no DoT damage
@@ -142,6 +160,7 @@ impl DotRegistry {
             max_stacks: 1,
             stack_effectiveness: 1.0,
             moving_multiplier: 1.0,
+            max_duration: None,
         });
 
         // Slow - physical/cold status, no DoT damage
@@ -156,40 +175,147 @@ impl DotRegistry {
             max_stacks: 1,
             stack_effectiveness: 1.0,
             moving_multiplier: 1.0,
+            max_duration: None,
         });
 
         registry
     }
 
+    /// Load DoT types from a config directory on top of the defaults
+    ///
+    /// Reads `dots.toml` and every `.toml` file in a `dots/` subfolder
+    /// (in file name order), each holding `[[dot_types]]` entries. A loaded
+    /// entry replaces the default with the same id; standard ids missing
+    /// from the files keep their defaults. Fails if neither `dots.toml` nor
+    /// `dots/` exists.
+    pub fn load_from_dir(dir: &Path) -> Result<Self, ConfigError> {
+        let mut files = Vec::new();
+        let single = dir.join("dots.toml");
+        if single.is_file() {
+            files.push(single);
+        }
+        let folder = dir.join("dots");
+        if folder.is_dir() {
+            let mut entries = Vec::new();
+            for entry in std::fs::read_dir(&folder)? {
+                let path = entry?.path();
+                if path.extension().is_some_and(|ext| ext == "toml") {
+                    entries.push(path);
+                }
+            }
+            entries.sort();
+            files.extend(entries);
+        }
+        if files.is_empty() {
+            return Err(ConfigError::IoError(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("no dots.toml or dots/ in {}", dir.display()),
+            )));
+        }
+
+        let mut registry = Self::with_defaults();
+        for file in files {
+            for config in read_dot_configs(&file)? {
+                registry.register(config);
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Get the DoT configuration for a status effect
+    pub fn config_for(&self, status: StatusEffect) -> Option<&DotConfig> {
+        self.get(status_id(status))
+    }
+
     /// Get the base damage percent for a status effect
-    pub fn get_base_damage_percent(&self, status: loot_core::types::StatusEffect) -> f64 {
-        use loot_core::types::StatusEffect;
-        let id = match status {
-            StatusEffect::Poison => "poison",
-            StatusEffect::Bleed => "bleed",
-            StatusEffect::Burn => "burn",
-            StatusEffect::Freeze => "freeze",
-            StatusEffect::Chill => "chill",
-            StatusEffect::Static => "static",
-            StatusEffect::Fear => "fear",
-            StatusEffect::Slow => "slow",
-        };
-        self.get(id).map(|c| c.base_damage_percent).unwrap_or(0.0)
+    pub fn get_base_damage_percent(&self, status: StatusEffect) -> f64 {
+        self.config_for(status)
+            .map(|c| c.base_damage_percent)
+            .unwrap_or(0.0)
     }
 
     /// Get the base duration for a status effect
-    pub fn get_base_duration(&self, status: loot_core::types::StatusEffect) -> f64 {
-        use loot_core::types::StatusEffect;
-        let id = match status {
-            StatusEffect::Poison => "poison",
-            StatusEffect::Bleed => "bleed",
-            StatusEffect::Burn => "burn",
-            StatusEffect::Freeze => "freeze",
-            StatusEffect::Chill => "chill",
-            StatusEffect::Static => "static",
-            StatusEffect::Fear => "fear",
-            StatusEffect::Slow => "slow",
-        };
-        self.get(id).map(|c| c.base_duration).unwrap_or(2.0)
+    pub fn get_base_duration(&self, status: StatusEffect) -> f64 {
+        self.config_for(status)
+            .map(|c| c.base_duration)
+            .unwrap_or(2.0)
+    }
+}
+
+/// Status effect for a DoT registry ID (the inverse of the config lookup)
+///
+/// Accepts the legacy "ignite" ID as an alias for burn.
+pub fn status_for_id(id: &str) -> Option<StatusEffect> {
+    match id {
+        "poison" => Some(StatusEffect::Poison),
+        "bleed" => Some(StatusEffect::Bleed),
+        "burn" | "ignite" => Some(StatusEffect::Burn),
+        "freeze" => Some(StatusEffect::Freeze),
+        "chill" => Some(StatusEffect::Chill),
+        "static" => Some(StatusEffect::Static),
+        "fear" => Some(StatusEffect::Fear),
+        "slow" => Some(StatusEffect::Slow),
+        _ => None,
+    }
+}
+
+/// Registry ID used for a status effect's DoT configuration
+fn status_id(status: StatusEffect) -> &'static str {
+    match status {
+        StatusEffect::Poison => "poison",
+        StatusEffect::Bleed => "bleed",
+        StatusEffect::Burn => "burn",
+        StatusEffect::Freeze => "freeze",
+        StatusEffect::Chill => "chill",
+        StatusEffect::Static => "static",
+        StatusEffect::Fear => "fear",
+        StatusEffect::Slow => "slow",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_from_dir_overrides_poison() {
+        let dir = std::env::temp_dir().join(format!("stat_core_dots_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("dots.toml"),
+            r#"
+[[dot_types]]
+id = "poison"
+name = "Poison"
+damage_type = "chaos"
+base_duration = 2.0
+tick_rate = 0.33
+base_damage_percent = 0.35
+
+[dot_types.stacking]
+type = "unlimited"
+"#,
+        )
+        .unwrap();
+
+        let registry = DotRegistry::load_from_dir(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        let registry = registry.unwrap();
+
+        assert!(
+            (registry.get_base_damage_percent(StatusEffect::Poison) - 0.35).abs() < f64::EPSILON
+        );
+        // Standard ids missing from the file keep their defaults
+        assert!((registry.get_base_damage_percent(StatusEffect::Burn) - 0.25).abs() < f64::EPSILON);
+        assert!(registry.config_for(StatusEffect::Slow).is_some());
+    }
+
+    #[test]
+    fn test_load_from_dir_requires_config() {
+        let dir = std::env::temp_dir().join(format!("stat_core_no_dots_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let result = DotRegistry::load_from_dir(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(matches!(result, Err(ConfigError::IoError(_))));
     }
 }
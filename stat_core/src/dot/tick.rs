@@ -1,6 +1,7 @@
 //! DoT tick processing
 
-use super::{ActiveDoT, DotConfig, DotStacking};
+use super::{ActiveDoT, DotConfig, DotStacking, StackDecision};
+use crate::stat_block::StatBlock;
 use loot_core::types::DamageType;
 
 /// Result of processing DoT ticks
@@ -27,11 +28,25 @@ impl DotTickResult {
         }
         self.total_damage += amount;
     }
+
+    /// Mitigate each damage type by the target's resistance to it and its
+    /// reduced DoT damage taken, as `StatBlock::tick_effects` does
+    /// (see `StatBlock::dot_resistance_multiplier` and `dot_damage_taken_multiplier`)
+    pub fn mitigate(&mut self, target: &StatBlock) {
+        let taken_mult = target.dot_damage_taken_multiplier();
+        for (damage_type, amount) in &mut self.damage_by_type {
+            *amount *= target.dot_resistance_multiplier(*damage_type) * taken_mult;
+        }
+        self.total_damage = self.damage_by_type.iter().map(|(_, amount)| amount).sum();
+    }
 }
 
 /// Process a single tick for all active DoTs
 ///
-/// Returns the total damage dealt and updates the DoT list.
+/// Returns the total damage dealt and updates the DoT list. Damage is raw;
+/// mitigate it with `DotTickResult::mitigate` against the target.
+/// `is_moving` applies each DoT's config `moving_multiplier`; stat blocks
+/// track this per target in `StatBlock::is_moving`.
 pub fn process_dot_tick(
     dots: &mut Vec<ActiveDoT>,
     delta_time: f64,
@@ -78,53 +93,71 @@ pub fn process_dot_tick(
 }
 
 /// Apply a new DoT to a list of active DoTs, respecting stacking rules
-pub fn apply_dot(
-    dots: &mut Vec<ActiveDoT>,
-    new_dot: ActiveDoT,
-    config: &DotConfig,
-) {
-    match &config.stacking {
-        DotStacking::StrongestOnly => {
-            // Find existing DoT of same type
-            if let Some(existing) = dots.iter_mut().find(|d| d.dot_type == new_dot.dot_type) {
-                // Refresh if new one is stronger
-                if new_dot.damage_per_tick >= existing.damage_per_tick {
-                    existing.refresh(new_dot.total_duration, new_dot.damage_per_tick);
+pub fn apply_dot(dots: &mut Vec<ActiveDoT>, new_dot: ActiveDoT, config: &DotConfig) {
+    let existing_count = dots
+        .iter()
+        .filter(|d| d.dot_type == new_dot.dot_type)
+        .count() as u32;
+    let new_is_stronger = dots
+        .iter()
+        .filter(|d| d.dot_type == new_dot.dot_type)
+        .all(|d| new_dot.damage_per_tick >= d.damage_per_tick);
+
+    match config
+        .stacking
+        .decide(config.max_stacks, existing_count, new_is_stronger)
+    {
+        StackDecision::Add => {
+            // Additional limited stacks deal reduced damage
+            let mut dot_to_add = new_dot;
+            if let DotStacking::Limited {
+                stack_effectiveness,
+                ..
+            } = &config.stacking
+            {
+                if existing_count > 0 {
+                    dot_to_add.effectiveness = *stack_effectiveness;
+                    dot_to_add.is_strongest = false;
                 }
-            } else {
-                dots.push(new_dot);
             }
+            dots.push(dot_to_add);
         }
-        DotStacking::Unlimited => {
-            // Just add the new DoT
+        StackDecision::AddAndRefreshAll => {
+            refresh_durations(dots, &new_dot.dot_type, new_dot.total_duration);
             dots.push(new_dot);
         }
-        DotStacking::Limited { max_stacks, stack_effectiveness } => {
-            // Count existing stacks of this type
-            let existing_count = dots.iter().filter(|d| d.dot_type == new_dot.dot_type).count();
-
-            if existing_count < *max_stacks as usize {
-                // Add with appropriate effectiveness
-                let mut dot_to_add = new_dot;
-                if existing_count > 0 {
-                    dot_to_add.effectiveness = *stack_effectiveness;
-                    dot_to_add.is_strongest = false;
-                }
-                dots.push(dot_to_add);
-            } else {
-                // At max stacks - refresh the weakest or oldest
-                // For simplicity, refresh the oldest (first found)
-                if let Some(oldest) = dots.iter_mut().find(|d| d.dot_type == new_dot.dot_type && !d.is_strongest) {
-                    oldest.refresh(new_dot.total_duration, new_dot.damage_per_tick);
-                }
+        StackDecision::RefreshAll => {
+            refresh_durations(dots, &new_dot.dot_type, new_dot.total_duration);
+        }
+        StackDecision::Replace => {
+            if let Some(existing) = dots.iter_mut().find(|d| d.dot_type == new_dot.dot_type) {
+                existing.refresh(new_dot.total_duration, new_dot.damage_per_tick);
             }
         }
+        StackDecision::ReplaceOldest => {
+            // For simplicity, refresh the oldest (first found) non-strongest stack
+            if let Some(oldest) = dots
+                .iter_mut()
+                .find(|d| d.dot_type == new_dot.dot_type && !d.is_strongest)
+            {
+                oldest.refresh(new_dot.total_duration, new_dot.damage_per_tick);
+            }
+        }
+        StackDecision::Ignore => {}
     }
 
     // Recalculate which is strongest for limited stacking
     recalculate_strongest(dots);
 }
 
+/// Reset the duration of every DoT of a type
+fn refresh_durations(dots: &mut [ActiveDoT], dot_type: &str, duration: f64) {
+    for dot in dots.iter_mut().filter(|d| d.dot_type == dot_type) {
+        dot.duration_remaining = duration;
+        dot.total_duration = duration;
+    }
+}
+
 /// Recalculate which DoT is the "strongest" for each type
 fn recalculate_strongest(dots: &mut [ActiveDoT]) {
     // Group by type
@@ -178,6 +211,7 @@ pub fn dot_dps_by_type(dots: &[ActiveDoT]) -> Vec<(DamageType, f64)> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::stat_block::StatValue;
     use std::collections::HashMap;
 
     fn make_ignite_config() -> DotConfig {
@@ -192,6 +226,7 @@ mod tests {
             max_stacks: 1,
             stack_effectiveness: 1.0,
             moving_multiplier: 1.0,
+            max_duration: None,
         }
     }
 
@@ -210,6 +245,7 @@ mod tests {
             max_stacks: 8,
             stack_effectiveness: 0.5,
             moving_multiplier: 2.0,
+            max_duration: None,
         }
     }
 
@@ -301,6 +337,31 @@ mod tests {
         assert!((result.total_damage - 50.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_tick_mitigated_by_target_resistance() {
+        let mut dots = vec![ActiveDoT::new(
+            "poison".to_string(),
+            "player".to_string(),
+            DamageType::Chaos,
+            40.0,
+            1.0,
+            3.0,
+        )];
+
+        let mut target = StatBlock::new();
+        target.chaos_resistance = StatValue::with_base(50.0);
+
+        let mut result = process_dot_tick(&mut dots, 1.0, false, &HashMap::new());
+        result.mitigate(&target);
+        assert!((result.total_damage - 20.0).abs() < 0.01);
+
+        // Reduced DoT damage taken stacks with resistance
+        target.dot_damage_taken_reduced = 0.25;
+        let mut result = process_dot_tick(&mut dots, 1.0, false, &HashMap::new());
+        result.mitigate(&target);
+        assert!((result.total_damage - 15.0).abs() < 0.01);
+    }
+
     #[test]
     fn test_bleed_moving_multiplier() {
         let mut dots = vec![ActiveDoT::new(
@@ -17,6 +17,79 @@ pub enum DotStacking {
         /// Effectiveness of additional stacks (e.g., 0.5 = 50%)
         stack_effectiveness: f64,
     },
+    /// Stacks up to the config's `max_stacks`; every application refreshes
+    /// the duration of all existing stacks
+    TimedRefresh,
+}
+
+/// What to do with a new application of a DoT or ailment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StackDecision {
+    /// Add a new instance (or stack)
+    Add,
+    /// Add a new stack and refresh the duration of all existing stacks
+    AddAndRefreshAll,
+    /// Refresh the duration of all existing stacks without adding one
+    RefreshAll,
+    /// Replace the existing instance with the new one
+    Replace,
+    /// At the stack cap: the new application replaces the oldest stack
+    ReplaceOldest,
+    /// Discard the new application
+    Ignore,
+}
+
+impl DotStacking {
+    /// Decide how a new application interacts with existing instances
+    ///
+    /// `max_stacks` is the config-level cap used by `TimedRefresh`; `Limited`
+    /// uses its own cap and `Unlimited` is never capped. `new_is_stronger`
+    /// is whether the new application is at least as strong as every
+    /// existing instance.
+    pub fn decide(
+        &self,
+        max_stacks: u32,
+        existing_stacks: u32,
+        new_is_stronger: bool,
+    ) -> StackDecision {
+        if existing_stacks == 0 {
+            return StackDecision::Add;
+        }
+        match self {
+            DotStacking::StrongestOnly => {
+                if new_is_stronger {
+                    StackDecision::Replace
+                } else {
+                    StackDecision::Ignore
+                }
+            }
+            DotStacking::Unlimited => StackDecision::Add,
+            DotStacking::Limited { max_stacks, .. } => {
+                if existing_stacks < *max_stacks {
+                    StackDecision::Add
+                } else {
+                    StackDecision::ReplaceOldest
+                }
+            }
+            DotStacking::TimedRefresh => {
+                if existing_stacks < max_stacks {
+                    StackDecision::AddAndRefreshAll
+                } else {
+                    StackDecision::RefreshAll
+                }
+            }
+        }
+    }
+
+    /// Stack cap for this stacking mode (`None` if uncapped)
+    pub fn stack_cap(&self, max_stacks: u32) -> Option<u32> {
+        match self {
+            DotStacking::StrongestOnly => Some(1),
+            DotStacking::Unlimited => None,
+            DotStacking::Limited { max_stacks, .. } => Some(*max_stacks),
+            DotStacking::TimedRefresh => Some(max_stacks),
+        }
+    }
 }
 
 /// Configuration for a DoT type
@@ -44,9 +117,14 @@ pub struct DotConfig {
     /// Effectiveness of additional stacks
     #[serde(default = "default_stack_effectiveness")]
     pub stack_effectiveness: f64,
-    /// Damage multiplier while target is moving (for bleed)
+    /// Damage multiplier while the target is moving (`StatBlock::is_moving`)
+    ///
+    /// Only bleed has a multiplier other than 1.0 by default (2.0).
     #[serde(default = "default_moving_multiplier")]
     pub moving_multiplier: f64,
+    /// Upper bound on the scaled duration in seconds (None = uncapped)
+    #[serde(default)]
+    pub max_duration: Option<f64>,
 }
 
 fn default_max_stacks() -> u32 {
@@ -71,6 +149,48 @@ impl DotConfig {
     pub fn actual_duration(&self) -> f64 {
         self.base_tick_count() as f64 * self.tick_rate
     }
+
+    /// Scale the base duration by total increased duration, clamped to `max_duration`
+    pub fn scaled_duration(&self, increased: f64) -> f64 {
+        let duration = self.base_duration * (1.0 + increased);
+        match self.max_duration {
+            Some(max) => duration.min(max),
+            None => duration,
+        }
+    }
+
+    /// Expected DPS when re-applied continuously
+    ///
+    /// With `applications_per_second` applications each lasting `duration`,
+    /// about `rate × duration` instances overlap. Stacking decides how many
+    /// of them deal damage: all (`Unlimited`), one (`StrongestOnly`), the
+    /// first in full plus capped extras at `stack_effectiveness` (`Limited`),
+    /// or up to `max_stacks` in full (`TimedRefresh`). Below one overlapping
+    /// instance the result is scaled by uptime.
+    pub fn sustained_dps(
+        &self,
+        dps_per_application: f64,
+        applications_per_second: f64,
+        duration: f64,
+    ) -> f64 {
+        let overlapping = (applications_per_second * duration).max(0.0);
+        let effective_stacks = match &self.stacking {
+            DotStacking::Unlimited => overlapping,
+            DotStacking::StrongestOnly => overlapping.min(1.0),
+            DotStacking::Limited {
+                max_stacks,
+                stack_effectiveness,
+            } => {
+                if overlapping <= 1.0 {
+                    overlapping
+                } else {
+                    1.0 + (overlapping.min(*max_stacks as f64) - 1.0).max(0.0) * stack_effectiveness
+                }
+            }
+            DotStacking::TimedRefresh => overlapping.min(self.max_stacks.max(1) as f64),
+        };
+        dps_per_application * effective_stacks
+    }
 }
 
 #[cfg(test)]
@@ -87,6 +207,69 @@ mod tests {
         assert!(json.contains("limited"));
     }
 
+    #[test]
+    fn test_stack_decisions_at_cap() {
+        let limited = DotStacking::Limited {
+            max_stacks: 3,
+            stack_effectiveness: 0.5,
+        };
+        assert_eq!(limited.decide(1, 2, false), StackDecision::Add);
+        assert_eq!(limited.decide(1, 3, true), StackDecision::ReplaceOldest);
+
+        assert_eq!(
+            DotStacking::TimedRefresh.decide(2, 1, false),
+            StackDecision::AddAndRefreshAll
+        );
+        assert_eq!(
+            DotStacking::TimedRefresh.decide(2, 2, false),
+            StackDecision::RefreshAll
+        );
+
+        assert_eq!(
+            DotStacking::Unlimited.decide(1, 50, false),
+            StackDecision::Add
+        );
+        assert_eq!(
+            DotStacking::StrongestOnly.decide(1, 1, false),
+            StackDecision::Ignore
+        );
+        assert_eq!(
+            DotStacking::StrongestOnly.decide(1, 1, true),
+            StackDecision::Replace
+        );
+    }
+
+    #[test]
+    fn test_sustained_dps_by_stacking() {
+        let mut config = DotConfig {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            damage_type: DamageType::Physical,
+            stacking: DotStacking::Unlimited,
+            base_duration: 2.0,
+            tick_rate: 0.5,
+            base_damage_percent: 0.2,
+            max_stacks: 1,
+            stack_effectiveness: 1.0,
+            moving_multiplier: 1.0,
+            max_duration: None,
+        };
+
+        // 2 applications/s lasting 2s: 4 overlapping instances
+        assert!((config.sustained_dps(10.0, 2.0, 2.0) - 40.0).abs() < 1e-9);
+
+        config.stacking = DotStacking::StrongestOnly;
+        assert!((config.sustained_dps(10.0, 2.0, 2.0) - 10.0).abs() < 1e-9);
+        // Half uptime
+        assert!((config.sustained_dps(10.0, 0.25, 2.0) - 5.0).abs() < 1e-9);
+
+        config.stacking = DotStacking::Limited {
+            max_stacks: 3,
+            stack_effectiveness: 0.5,
+        };
+        assert!((config.sustained_dps(10.0, 2.0, 2.0) - 20.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_dot_config_tick_count() {
         let config = DotConfig {
@@ -100,6 +283,7 @@ mod tests {
             max_stacks: 1,
             stack_effectiveness: 1.0,
             moving_multiplier: 1.0,
+            max_duration: None,
         };
 
         // 4.0 / 0.5 = 8 ticks
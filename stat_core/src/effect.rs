@@ -0,0 +1,275 @@
+//! Effect application - Refresh vs stack decisions for ailments
+//!
+//! Ailments on a `StatBlock` are unified `Effect`s. How a new application
+//! interacts with existing instances is decided by the ailment's
+//! `DotConfig` stacking mode (see `DotStacking::decide`), the same rule
+//! `dot::apply_dot` uses for `ActiveDoT` lists. Ailments with no config
+//! fall back to the effect's own `AilmentStacking`.
+
+use crate::dot::{DotConfig, DotRegistry, DotStacking, StackDecision};
+use crate::stat_block::StatBlock;
+use crate::types::{AilmentStacking, Effect, EffectType};
+
+/// Apply an ailment effect using its config from `registry`
+///
+/// If the registry has no config for the ailment, the effect's own
+/// `AilmentStacking` and `max_stacks` decide instead. This is the path
+/// `StatBlock::add_effect` (with the default registry) and combat
+/// resolution both use. Non-ailment effects are passed to
+/// `StatBlock::add_effect`.
+pub fn apply_ailment_from(target: &mut StatBlock, effect: Effect, registry: &DotRegistry) {
+    let Some(status) = effect.status() else {
+        target.add_effect(effect);
+        return;
+    };
+
+    match registry.config_for(status) {
+        Some(config) => apply_ailment(target, effect, config),
+        None => {
+            let stacking = match &effect.effect_type {
+                EffectType::Ailment { stacking, .. } => dot_stacking(stacking, effect.max_stacks),
+                _ => DotStacking::StrongestOnly,
+            };
+            let max_stacks = effect.max_stacks;
+            apply_with_stacking(target, effect, &stacking, max_stacks);
+        }
+    }
+}
+
+/// Apply an ailment effect to a target, honoring stacking mode and max stacks
+///
+/// - `StrongestOnly`: a single instance; a stronger application replaces it
+/// - `Unlimited`: every application is a separate effect
+/// - `Limited`: stacks on one effect up to the mode's `max_stacks`; at the
+///   cap, new applications only refresh the duration
+/// - `TimedRefresh`: stacks up to the config's `max_stacks`, and every
+///   application refreshes the whole stack's duration
+///
+/// Non-ailment effects are passed to `StatBlock::add_effect`.
+pub fn apply_ailment(target: &mut StatBlock, effect: Effect, config: &DotConfig) {
+    apply_with_stacking(target, effect, &config.stacking, config.max_stacks);
+}
+
+/// Apply an ailment with the given stacking mode and config-level stack cap
+fn apply_with_stacking(
+    target: &mut StatBlock,
+    mut effect: Effect,
+    stacking: &DotStacking,
+    max_stacks: u32,
+) {
+    let Some(status) = effect.status() else {
+        target.add_effect(effect);
+        return;
+    };
+
+    let cap = stacking.stack_cap(max_stacks);
+    if let Some(cap) = cap {
+        effect.max_stacks = cap;
+    }
+
+    let existing_stacks = match stacking {
+        DotStacking::Unlimited => target
+            .effects
+            .iter()
+            .filter(|e| e.status() == Some(status))
+            .count() as u32,
+        _ => target
+            .effects
+            .iter()
+            .find(|e| e.status() == Some(status))
+            .map(|e| e.stacks)
+            .unwrap_or(0),
+    };
+    let new_strength = strength(&effect);
+    let new_is_stronger = target
+        .effects
+        .iter()
+        .filter(|e| e.status() == Some(status))
+        .all(|e| new_strength >= strength(e));
+
+    let decision = stacking.decide(max_stacks, existing_stacks, new_is_stronger);
+    if decision == StackDecision::Ignore {
+        return;
+    }
+    if existing_stacks == 0 || *stacking == DotStacking::Unlimited {
+        target.effects.push(effect);
+        return;
+    }
+
+    let Some(existing) = target
+        .effects
+        .iter_mut()
+        .find(|e| e.status() == Some(status))
+    else {
+        return;
+    };
+    if let Some(cap) = cap {
+        existing.max_stacks = cap;
+    }
+
+    match decision {
+        StackDecision::Add | StackDecision::AddAndRefreshAll => {
+            existing.add_stack();
+            existing.refresh(effect.duration_remaining);
+        }
+        StackDecision::RefreshAll | StackDecision::ReplaceOldest => {
+            existing.refresh(effect.duration_remaining);
+        }
+        StackDecision::Replace => {
+            existing.refresh(effect.duration_remaining);
+            if let (
+                EffectType::Ailment {
+                    dot_dps, magnitude, ..
+                },
+                EffectType::Ailment {
+                    dot_dps: new_dps,
+                    magnitude: new_magnitude,
+                    ..
+                },
+            ) = (&mut existing.effect_type, &effect.effect_type)
+            {
+                *dot_dps = dot_dps.max(*new_dps);
+                *magnitude = magnitude.max(*new_magnitude);
+            }
+        }
+        StackDecision::Ignore => {}
+    }
+}
+
+/// Stacking mode equivalent to an effect's own `AilmentStacking`
+fn dot_stacking(stacking: &AilmentStacking, max_stacks: u32) -> DotStacking {
+    match stacking {
+        AilmentStacking::StrongestOnly => DotStacking::StrongestOnly,
+        AilmentStacking::Unlimited => DotStacking::Unlimited,
+        AilmentStacking::Limited {
+            stack_effectiveness,
+        } => DotStacking::Limited {
+            max_stacks,
+            stack_effectiveness: *stack_effectiveness,
+        },
+    }
+}
+
+/// Per-stack strength of an ailment (DPS for damaging ailments, else magnitude)
+fn strength(effect: &Effect) -> f64 {
+    match &effect.effect_type {
+        EffectType::Ailment {
+            dot_dps,
+            magnitude,
+            effectiveness,
+            ..
+        } => {
+            if *dot_dps > 0.0 {
+                dot_dps * effectiveness
+            } else {
+                *magnitude
+            }
+        }
+        _ => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use loot_core::types::StatusEffect;
+
+    fn config_for(status: StatusEffect) -> DotConfig {
+        DotRegistry::with_defaults()
+            .config_for(status)
+            .unwrap()
+            .clone()
+    }
+
+    fn count(block: &StatBlock, status: StatusEffect) -> usize {
+        block
+            .effects
+            .iter()
+            .filter(|e| e.status() == Some(status))
+            .count()
+    }
+
+    #[test]
+    fn test_strongest_only_keeps_single_strongest() {
+        let mut block = StatBlock::new();
+        let config = config_for(StatusEffect::Burn);
+
+        apply_ailment(&mut block, Effect::burn(50.0, "a"), &config);
+        apply_ailment(&mut block, Effect::burn(30.0, "a"), &config);
+        apply_ailment(&mut block, Effect::burn(70.0, "a"), &config);
+
+        assert_eq!(count(&block, StatusEffect::Burn), 1);
+        assert_eq!(block.effects[0].stacks, 1);
+        assert!((block.effects[0].dps() - 70.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_unlimited_ignores_max_stacks() {
+        let mut block = StatBlock::new();
+        let mut config = config_for(StatusEffect::Poison);
+        config.max_stacks = 2;
+
+        for _ in 0..5 {
+            apply_ailment(&mut block, Effect::poison(10.0, "a"), &config);
+        }
+
+        assert_eq!(count(&block, StatusEffect::Poison), 5);
+    }
+
+    #[test]
+    fn test_limited_stops_at_max_stacks() {
+        let mut block = StatBlock::new();
+        let config = config_for(StatusEffect::Bleed);
+
+        for _ in 0..8 {
+            apply_ailment(&mut block, Effect::bleed(10.0, "a"), &config);
+        }
+        assert_eq!(block.effects[0].stacks, 8);
+
+        // Past the cap: still 8 stacks, duration refreshed
+        block.effects[0].duration_remaining = 1.0;
+        apply_ailment(&mut block, Effect::bleed(10.0, "a"), &config);
+        assert_eq!(count(&block, StatusEffect::Bleed), 1);
+        assert_eq!(block.effects[0].stacks, 8);
+        assert!((block.effects[0].duration_remaining - 5.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_timed_refresh_stacks_to_config_max() {
+        let mut block = StatBlock::new();
+        let mut config = config_for(StatusEffect::Static);
+        config.stacking = DotStacking::TimedRefresh;
+        config.max_stacks = 2;
+
+        apply_ailment(&mut block, Effect::shock(10.0, "a"), &config);
+        block.effects[0].duration_remaining = 0.2;
+        apply_ailment(&mut block, Effect::shock(10.0, "a"), &config);
+        assert_eq!(block.effects[0].stacks, 2);
+        assert!((block.effects[0].duration_remaining - 1.0).abs() < 0.01);
+
+        block.effects[0].duration_remaining = 0.2;
+        apply_ailment(&mut block, Effect::shock(10.0, "a"), &config);
+        assert_eq!(count(&block, StatusEffect::Static), 1);
+        assert_eq!(block.effects[0].stacks, 2);
+        assert!((block.effects[0].duration_remaining - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_missing_config_uses_effect_stacking() {
+        let mut block = StatBlock::new();
+        let registry = DotRegistry::new();
+
+        // Static is Limited to 3 stacks on the effect itself
+        for _ in 0..5 {
+            apply_ailment_from(&mut block, Effect::shock(0.10, "a"), &registry);
+        }
+        assert_eq!(count(&block, StatusEffect::Static), 1);
+        assert_eq!(block.effects[0].stacks, 3);
+
+        // Chill is strongest-only: a weaker application is ignored
+        apply_ailment_from(&mut block, Effect::chill(0.30, "a"), &registry);
+        apply_ailment_from(&mut block, Effect::chill(0.10, "a"), &registry);
+        assert_eq!(count(&block, StatusEffect::Chill), 1);
+        assert!((block.action_speed_reduction() - 0.30).abs() < 0.01);
+    }
+}
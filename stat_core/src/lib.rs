@@ -5,6 +5,7 @@
 //! - DamagePacketGenerator: Skill/ability damage configuration
 //! - DamagePacket: Calculated damage output
 //! - Damage Resolution: Processing incoming damage against defenses
+//! - Simulation: Time-stepped fights for kill-time estimates
 //!
 //! # Quick Start
 //!
@@ -15,7 +16,7 @@
 //! // Create player and equip items
 //! let mut player = StatBlock::with_id("player");
 //! let generator = Generator::new(Config::load("config/").unwrap());
-//! player.equip(EquipmentSlot::MainHand, generator.generate("iron_sword", 12345).unwrap());
+//! player.equip(EquipmentSlot::MainHand, generator.generate("iron_sword", 12345).unwrap()).unwrap();
 //!
 //! // Combat
 //! let skills = default_skills();
@@ -32,23 +33,44 @@ pub mod config;
 pub mod damage;
 pub mod defense;
 pub mod dot;
+pub mod effect;
 pub mod prelude;
+pub mod simulation;
 pub mod source;
 pub mod stat_block;
 pub mod types;
 
 // Re-export core types for convenience
-pub use combat::{CombatResult, DamageTaken};
-pub use defense::calculate_damage_cap;
+pub use combat::{CombatEvent, CombatResult, DamageTaken, MitigationKind};
+pub use config::{default_skills, Scenario};
 pub use damage::{
-    BaseDamage, DamagePacket, DamagePacketGenerator, DotApplication, FinalDamage, PendingDoT,
-    PendingStatusEffect,
+    AoeFalloff, BaseDamage, DamageConversions, DamagePacket, DamagePacketGenerator, DamageProfile,
+    DamageProvenance, DotApplication, DpsBreakdown, FinalDamage, GainAsExtra, PendingDoT,
+    PendingStatusEffect, TypeProvenance,
+};
+pub use defense::{
+    calculate_damage_cap, calculate_evade_chance, calculate_hit_chance, DamageReroute,
+    TakenAsConversions,
+};
+pub use dot::{ActiveDoT, DotConfig, DotStacking, StackDecision};
+pub use effect::{apply_ailment, apply_ailment_from};
+pub use simulation::{simulate_combat, simulate_combat_with_rng, CombatSimulation};
+pub use source::{
+    effective_weapon_damage, AuraSource, BaseStatsSource, BuffExpiry, BuffSource, BuffStacking,
+    Condition, ConditionalSource, FlaskSource, GearSource, SkillTreeSource, SourceContext,
+    StatSource,
+};
+pub use stat_block::{
+    StatAccumulator, StatBlock, StatBlockBuilder, StatBreakdown, StatContribution, StatDiff,
+    StatValue, StatusConversions, StatusEffectData, StatusEffectStats, WeaponStats,
+    DUAL_WIELD_ATTACK_SPEED_BONUS, MAX_MOVEMENT_SPEED_INCREASED, MIN_ACTION_SPEED,
+};
+pub use types::{
+    is_two_handed, ActiveBuff, ActiveStatusEffect, AilmentImmunities, AilmentStacking, DamagePool,
+    Effect, EffectType, EquipError, EquipmentSlot, EvasionMode, Penetration, RequirementMode,
+    RequirementShortfall, ResistanceReduction, ResourceKind, SkillError, SkillNodeId, SkillTag,
+    StatMod, TickResult, ALL_DAMAGE_TYPES,
 };
-pub use dot::{ActiveDoT, DotConfig, DotStacking};
-pub use source::{BaseStatsSource, BuffSource, GearSource, SkillTreeSource, StatSource};
-pub use stat_block::{StatAccumulator, StatBlock, StatValue, StatusConversions, StatusEffectStats, StatusEffectData};
-pub use types::{ActiveBuff, ActiveStatusEffect, AilmentStacking, Effect, EffectType, EquipmentSlot, SkillNodeId, SkillTag, StatMod, TickResult};
-pub use config::default_skills;
 
 // Re-export loot_core types for convenience
 pub use loot_core::types::{Attribute, DamageType, DefenseType, ItemClass, Rarity, StatType, StatusEffect};
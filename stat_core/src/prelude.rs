@@ -5,20 +5,23 @@
 //! ```
 
 // Core types
-pub use crate::stat_block::{StatBlock, StatValue};
-pub use crate::types::{ActiveBuff, ActiveStatusEffect, AilmentStacking, Effect, EffectType, EquipmentSlot, SkillTag, StatMod, TickResult};
+pub use crate::stat_block::{StatBlock, StatBlockBuilder, StatValue};
+pub use crate::types::{
+    ActiveBuff, ActiveStatusEffect, AilmentStacking, Effect, EffectType, EquipmentSlot,
+    ResourceKind, SkillTag, StatMod, TickResult, ALL_DAMAGE_TYPES,
+};
 
 // Damage system
 pub use crate::damage::{DamagePacket, DamagePacketGenerator, BaseDamage};
 
 // Combat
-pub use crate::combat::{CombatResult, DamageTaken};
+pub use crate::combat::{CombatEvent, CombatResult, DamageTaken};
 
 // DoT system
 pub use crate::dot::{DotRegistry, ActiveDoT, DotConfig};
 
 // Sources
-pub use crate::source::{BuffSource, GearSource, StatSource};
+pub use crate::source::{AuraSource, BuffSource, GearSource, StatSource};
 
 // Config
 pub use crate::config::default_skills;
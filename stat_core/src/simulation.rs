@@ -0,0 +1,231 @@
+//! Combat simulation - Time-stepped fights for kill-time and sustained DPS estimates
+
+use crate::combat::resolve_damage_multi_with_rng;
+use crate::damage::DamagePacketGenerator;
+use crate::dot::DotRegistry;
+use crate::stat_block::StatBlock;
+use rand::Rng;
+
+/// Outcome of a simulated fight
+#[derive(Debug, Clone, PartialEq)]
+pub struct CombatSimulation {
+    /// Seconds until the defender died (None if it survived `max_time`)
+    pub time_to_kill: Option<f64>,
+    /// Damage dealt by hits after mitigation
+    pub total_hit_damage: f64,
+    /// Damage dealt by DoT ticks after mitigation
+    pub total_dot_damage: f64,
+    /// Number of times the skill was used
+    pub attacks: u32,
+    /// Whether the defender was still alive at the end
+    pub target_survived: bool,
+    /// Seconds simulated
+    pub duration: f64,
+}
+
+impl CombatSimulation {
+    /// Average damage per second over the simulated time
+    pub fn sustained_dps(&self) -> f64 {
+        if self.duration <= 0.0 {
+            return 0.0;
+        }
+        (self.total_hit_damage + self.total_dot_damage) / self.duration
+    }
+}
+
+/// Simulate `attacker` using `skill` on `defender` until it dies or `max_time` passes
+///
+/// Uses the thread RNG; see [`simulate_combat_with_rng`].
+pub fn simulate_combat(
+    attacker: &StatBlock,
+    defender: &StatBlock,
+    skill: &DamagePacketGenerator,
+    dot_registry: &DotRegistry,
+    dt: f64,
+    max_time: f64,
+) -> CombatSimulation {
+    simulate_combat_with_rng(
+        attacker,
+        defender,
+        skill,
+        dot_registry,
+        dt,
+        max_time,
+        &mut rand::thread_rng(),
+    )
+}
+
+/// Simulate a fight with a provided RNG (for deterministic results)
+///
+/// The attacker uses the skill at time 0 and then every
+/// `1 / computed_skill_speed` seconds; attacks due within a step land at the
+/// start of it. Each step of `dt` seconds then ticks the defender's DoTs
+/// and life/mana regeneration. The attacker's state (mana, buffs) is not
+/// changed, so mana costs are ignored.
+pub fn simulate_combat_with_rng(
+    attacker: &StatBlock,
+    defender: &StatBlock,
+    skill: &DamagePacketGenerator,
+    dot_registry: &DotRegistry,
+    dt: f64,
+    max_time: f64,
+    rng: &mut impl Rng,
+) -> CombatSimulation {
+    let mut target = defender.clone();
+    let mut simulation = CombatSimulation {
+        time_to_kill: None,
+        total_hit_damage: 0.0,
+        total_dot_damage: 0.0,
+        attacks: 0,
+        target_survived: target.is_alive(),
+        duration: 0.0,
+    };
+    if dt <= 0.0 || !target.is_alive() {
+        return simulation;
+    }
+
+    let speed = attacker.computed_skill_speed(skill);
+    let interval = if speed > 0.0 {
+        1.0 / speed
+    } else {
+        f64::INFINITY
+    };
+    let mut next_attack = 0.0;
+    let mut step: u32 = 0;
+
+    loop {
+        let time = step as f64 * dt;
+        if time >= max_time {
+            simulation.duration = max_time;
+            break;
+        }
+
+        // Attacks due by now
+        while next_attack <= time {
+            let packet = attacker.attack_seeded(skill, dot_registry, rng);
+            let results =
+                resolve_damage_multi_with_rng(&mut [&mut target], &packet, dot_registry, rng);
+            simulation.attacks += 1;
+            simulation.total_hit_damage += results[0].total_damage;
+            next_attack += interval;
+            if results[0].is_killing_blow {
+                simulation.time_to_kill = Some(time);
+                break;
+            }
+        }
+        if simulation.time_to_kill.is_some() {
+            simulation.duration = time;
+            break;
+        }
+
+        // DoTs and regeneration over the step
+        let (next, tick) = target.tick_effects(dt);
+        target = next;
+        simulation.total_dot_damage += tick.dot_damage;
+        if tick.is_dead {
+            simulation.time_to_kill = Some(time + dt);
+            simulation.duration = time + dt;
+            break;
+        }
+        target.tick_regen(dt);
+        step += 1;
+    }
+
+    simulation.target_survived = target.is_alive();
+    simulation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::damage::{BaseDamage, SkillStatusConversions};
+    use crate::types::SkillTag;
+    use loot_core::types::DamageType;
+    use rand::SeedableRng;
+
+    fn make_defender(life: f64) -> StatBlock {
+        let mut defender = StatBlock::new();
+        defender.max_life.base = life;
+        defender.current_life = life;
+        defender
+    }
+
+    #[test]
+    fn test_pure_hit_kill() {
+        let attacker = StatBlock::new();
+        let skill = DamagePacketGenerator {
+            id: "firebolt".to_string(),
+            name: "Firebolt".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Fire, 100.0, 100.0)],
+            tags: vec![SkillTag::Spell],
+            ..Default::default()
+        };
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let simulation = simulate_combat_with_rng(
+            &attacker,
+            &make_defender(250.0),
+            &skill,
+            DotRegistry::shared_defaults(),
+            0.1,
+            10.0,
+            &mut rng,
+        );
+
+        // Casts at 0s, 1s and 2s; the third kills
+        assert_eq!(simulation.attacks, 3);
+        assert!((simulation.time_to_kill.unwrap() - 2.0).abs() < 0.01);
+        assert!(!simulation.target_survived);
+        assert!(simulation.total_hit_damage >= 250.0);
+        assert!(simulation.total_dot_damage.abs() < f64::EPSILON);
+
+        // Not enough time: the target survives
+        let simulation = simulate_combat_with_rng(
+            &attacker,
+            &make_defender(250.0),
+            &skill,
+            DotRegistry::shared_defaults(),
+            0.1,
+            1.5,
+            &mut rng,
+        );
+        assert!(simulation.target_survived);
+        assert_eq!(simulation.time_to_kill, None);
+        assert!((simulation.sustained_dps() - 200.0 / 1.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_dot_dominant_kill() {
+        let attacker = StatBlock::new();
+        let skill = DamagePacketGenerator {
+            id: "caustic_cloud".to_string(),
+            name: "Caustic Cloud".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Chaos, 200.0, 200.0)],
+            tags: vec![SkillTag::Spell],
+            status_conversions: SkillStatusConversions {
+                chaos_to_poison: 1.0,
+                ..Default::default()
+            },
+            hit_deals_no_damage: true,
+            ..Default::default()
+        };
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(2);
+        let simulation = simulate_combat_with_rng(
+            &attacker,
+            &make_defender(100.0),
+            &skill,
+            DotRegistry::shared_defaults(),
+            0.1,
+            10.0,
+            &mut rng,
+        );
+
+        // Hits deal nothing; stacking poison (40 DPS per cast) does the killing
+        assert!(!simulation.target_survived);
+        assert!(simulation.total_hit_damage.abs() < f64::EPSILON);
+        assert!(simulation.total_dot_damage >= 100.0 - 0.01);
+        let time_to_kill = simulation.time_to_kill.unwrap();
+        assert!(time_to_kill > 1.0 && time_to_kill < 3.0);
+    }
+}
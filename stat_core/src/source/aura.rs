@@ -0,0 +1,139 @@
+//! AuraSource - Persistent auras that reserve mana while active
+
+use crate::source::StatSource;
+use crate::stat_block::StatAccumulator;
+use loot_core::types::StatType;
+use serde::{Deserialize, Serialize};
+
+/// Persistent aura granting stats while active
+///
+/// Auras are toggled on and off rather than expiring. While active they
+/// reserve a percentage of maximum mana.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuraSource {
+    /// Aura identifier
+    pub aura_id: String,
+    /// Display name
+    pub name: String,
+    /// Stat modifiers granted while active
+    pub modifiers: Vec<(StatType, f64)>,
+    /// Percentage of maximum mana reserved while active (25.0 = 25%)
+    pub mana_reservation: f64,
+    /// Whether the aura is currently active
+    pub active: bool,
+}
+
+impl AuraSource {
+    /// Create a new active aura with no modifiers
+    pub fn new(aura_id: String, name: String) -> Self {
+        AuraSource {
+            aura_id,
+            name,
+            modifiers: Vec::new(),
+            mana_reservation: 0.0,
+            active: true,
+        }
+    }
+
+    /// Add a modifier to this aura
+    pub fn with_modifier(mut self, stat: StatType, value: f64) -> Self {
+        self.modifiers.push((stat, value));
+        self
+    }
+
+    /// Set the percentage of maximum mana reserved
+    pub fn with_mana_reservation(mut self, percent: f64) -> Self {
+        self.mana_reservation = percent;
+        self
+    }
+
+    /// Set whether the aura starts active
+    pub fn with_active(mut self, active: bool) -> Self {
+        self.active = active;
+        self
+    }
+
+    /// Turn the aura on or off (takes effect on the next rebuild)
+    pub fn set_active(&mut self, active: bool) {
+        self.active = active;
+    }
+}
+
+impl StatSource for AuraSource {
+    fn id(&self) -> &str {
+        &self.aura_id
+    }
+
+    fn priority(&self) -> i32 {
+        150 // Auras apply after skill tree, before buffs
+    }
+
+    fn apply(&self, stats: &mut StatAccumulator) {
+        if !self.active {
+            return;
+        }
+
+        for (stat, value) in &self.modifiers {
+            stats.apply_stat_type(*stat, *value);
+        }
+        stats.mana_reservation += self.mana_reservation / 100.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aura_apply() {
+        let aura = AuraSource::new("determination".to_string(), "Determination".to_string())
+            .with_modifier(StatType::IncreasedArmour, 50.0)
+            .with_mana_reservation(25.0);
+
+        let mut acc = StatAccumulator::new();
+        aura.apply(&mut acc);
+
+        assert!((acc.armour_increased - 0.50).abs() < 0.01);
+        assert!((acc.mana_reservation - 0.25).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_inactive_aura_contributes_nothing() {
+        let mut aura = AuraSource::new("anger".to_string(), "Anger".to_string())
+            .with_modifier(StatType::AddedFireDamage, 10.0)
+            .with_mana_reservation(50.0);
+        aura.active = false;
+
+        let mut acc = StatAccumulator::new();
+        aura.apply(&mut acc);
+
+        assert!((acc.fire_damage_flat - 0.0).abs() < f64::EPSILON);
+        assert!((acc.mana_reservation - 0.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_toggling_active_changes_rebuilt_stats() {
+        use crate::stat_block::StatBlock;
+
+        let rebuild = |aura: &AuraSource| {
+            let mut block = StatBlock::new();
+            let sources: Vec<Box<dyn StatSource>> = vec![Box::new(aura.clone())];
+            block.rebuild_from_sources(&sources);
+            block
+        };
+
+        let mut aura = AuraSource::new("anger".to_string(), "Anger".to_string())
+            .with_modifier(StatType::AddedFireDamage, 10.0)
+            .with_active(false);
+        assert!(rebuild(&aura).global_fire_damage.compute().abs() < f64::EPSILON);
+
+        aura.set_active(true);
+        assert!((rebuild(&aura).global_fire_damage.compute() - 10.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_aura_priority() {
+        let aura = AuraSource::new("anger".to_string(), "Anger".to_string());
+        assert_eq!(aura.priority(), 150);
+    }
+}
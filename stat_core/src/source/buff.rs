@@ -3,9 +3,46 @@
 use crate::source::StatSource;
 use crate::stat_block::StatAccumulator;
 use loot_core::types::StatType;
+use serde::{Deserialize, Serialize};
+
+/// How reapplying a buff with the same ID interacts with the existing one
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BuffStacking {
+    /// Refresh the duration without adding a stack
+    Refresh,
+    /// Refresh the duration and add a stack, up to `max_stacks`
+    Stack { max_stacks: u32 },
+    /// Keep whichever application has the larger magnitude
+    StrongestOnly,
+    /// Every application is a separate instance with its own duration
+    Independent,
+}
+
+impl Default for BuffStacking {
+    fn default() -> Self {
+        BuffStacking::Stack {
+            max_stacks: u32::MAX,
+        }
+    }
+}
+
+/// What happens when a buff's duration runs out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuffExpiry {
+    /// The whole buff is removed
+    #[default]
+    RemoveAll,
+    /// One stack is lost and the duration restarts; the last stack removes the buff
+    LoseStack,
+}
 
 /// Temporary buff/debuff source
-#[derive(Debug, Clone)]
+///
+/// Modifiers are defined per stack: a buff with N stacks contributes N times
+/// each modifier's value (capped at `max_stacks` for [`BuffStacking::Stack`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuffSource {
     /// Buff identifier
     pub buff_id: String,
@@ -13,16 +50,35 @@ pub struct BuffSource {
     pub name: String,
     /// Duration remaining in seconds
     pub duration_remaining: f64,
+    /// Full duration, restored when a stack expires under [`BuffExpiry::LoseStack`]
+    ///
+    /// Missing from older saves; [`StatBlock::post_load`](crate::stat_block::StatBlock::post_load)
+    /// falls back to `duration_remaining`.
+    #[serde(default)]
+    pub duration: f64,
     /// Current stack count
     pub stacks: u32,
     /// Whether this is a debuff
     pub is_debuff: bool,
+    /// How reapplication is handled
+    #[serde(default)]
+    pub stacking: BuffStacking,
+    /// What happens when the duration runs out
+    #[serde(default)]
+    pub expiry: BuffExpiry,
     /// Stat modifiers per stack
+    #[serde(default)]
     modifiers: Vec<BuffModifier>,
+    /// Fraction of total armour also added as evasion, per stack (0.10 = 10%)
+    #[serde(default)]
+    pub armour_as_evasion: f64,
+    /// Fraction of total evasion also added as armour, per stack (0.10 = 10%)
+    #[serde(default)]
+    pub evasion_as_armour: f64,
 }
 
 /// A stat modifier from a buff
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuffModifier {
     pub stat: StatType,
     /// Value per stack
@@ -38,9 +94,14 @@ impl BuffSource {
             buff_id,
             name,
             duration_remaining: duration,
+            duration,
             stacks: 1,
             is_debuff,
+            stacking: BuffStacking::default(),
+            expiry: BuffExpiry::default(),
             modifiers: Vec::new(),
+            armour_as_evasion: 0.0,
+            evasion_as_armour: 0.0,
         }
     }
 
@@ -54,12 +115,50 @@ impl BuffSource {
         self
     }
 
+    /// Add a fraction of total armour as evasion (e.g. a hybrid unique's item effect)
+    pub fn with_armour_as_evasion(mut self, fraction: f64) -> Self {
+        self.armour_as_evasion += fraction;
+        self
+    }
+
+    /// Add a fraction of total evasion as armour
+    pub fn with_evasion_as_armour(mut self, fraction: f64) -> Self {
+        self.evasion_as_armour += fraction;
+        self
+    }
+
     /// Set the number of stacks
     pub fn with_stacks(mut self, stacks: u32) -> Self {
         self.stacks = stacks;
         self
     }
 
+    /// Set how reapplication is handled
+    pub fn with_stacking(mut self, stacking: BuffStacking) -> Self {
+        self.stacking = stacking;
+        self
+    }
+
+    /// Set what happens when the duration runs out
+    pub fn with_expiry(mut self, expiry: BuffExpiry) -> Self {
+        self.expiry = expiry;
+        self
+    }
+
+    /// Stacks that contribute stats (current stacks, capped at the stacking limit)
+    pub fn effective_stacks(&self) -> u32 {
+        match self.stacking {
+            BuffStacking::Stack { max_stacks } => self.stacks.min(max_stacks),
+            _ => self.stacks,
+        }
+    }
+
+    /// Total strength of the buff across all stacks (sum of absolute modifier values)
+    pub fn magnitude(&self) -> f64 {
+        let per_stack: f64 = self.modifiers.iter().map(|m| m.value_per_stack.abs()).sum();
+        per_stack * self.effective_stacks() as f64
+    }
+
     /// Add a stack
     pub fn add_stack(&mut self) {
         self.stacks += 1;
@@ -75,10 +174,16 @@ impl BuffSource {
         self.duration_remaining = duration;
     }
 
-    /// Tick the buff duration
+    /// Tick the buff duration, expiring stacks per the buff's [`BuffExpiry`]
     /// Returns true if the buff is still active
     pub fn tick(&mut self, delta: f64) -> bool {
         self.duration_remaining -= delta;
+        if self.expiry == BuffExpiry::LoseStack && self.duration > 0.0 {
+            while self.duration_remaining <= 0.0 && self.stacks > 1 {
+                self.stacks -= 1;
+                self.duration_remaining += self.duration;
+            }
+        }
         self.duration_remaining > 0.0 && self.stacks > 0
     }
 
@@ -102,7 +207,7 @@ impl StatSource for BuffSource {
             return;
         }
 
-        let stack_mult = self.stacks as f64;
+        let stack_mult = self.effective_stacks() as f64;
 
         for modifier in &self.modifiers {
             let total_value = modifier.value_per_stack * stack_mult;
@@ -125,6 +230,9 @@ impl StatSource for BuffSource {
                 stats.apply_stat_type(modifier.stat, total_value);
             }
         }
+
+        stats.armour_as_evasion += self.armour_as_evasion * stack_mult;
+        stats.evasion_as_armour += self.evasion_as_armour * stack_mult;
     }
 }
 
@@ -205,4 +313,68 @@ mod tests {
         let buff = BuffSource::new("test".to_string(), "Test".to_string(), 5.0, false);
         assert_eq!(buff.priority(), 200);
     }
+
+    #[test]
+    fn test_magnitude() {
+        let buff = BuffSource::new("test".to_string(), "Test".to_string(), 5.0, true)
+            .with_modifier(StatType::IncreasedPhysicalDamage, -20.0, false)
+            .with_modifier(StatType::IncreasedAttackSpeed, 10.0, false)
+            .with_stacks(2);
+        assert!((buff.magnitude() - 60.0).abs() < 0.01);
+        assert_eq!(
+            buff.stacking,
+            BuffStacking::Stack {
+                max_stacks: u32::MAX
+            }
+        );
+    }
+
+    #[test]
+    fn test_buff_stacks_capped_by_max_stacks() {
+        let buff = BuffSource::new("test".to_string(), "Test".to_string(), 5.0, false)
+            .with_modifier(StatType::IncreasedPhysicalDamage, 10.0, false)
+            .with_stacking(BuffStacking::Stack { max_stacks: 2 })
+            .with_stacks(5);
+
+        let mut acc = StatAccumulator::new();
+        buff.apply(&mut acc);
+        assert!((acc.physical_damage_increased - 0.20).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_buff_expiry_lose_stack() {
+        let mut buff = BuffSource::new("test".to_string(), "Test".to_string(), 2.0, false)
+            .with_expiry(BuffExpiry::LoseStack)
+            .with_stacks(3);
+
+        assert!(buff.tick(2.5));
+        assert_eq!(buff.stacks, 2);
+        assert!((buff.duration_remaining - 1.5).abs() < 0.01);
+
+        assert!(buff.tick(2.0));
+        assert_eq!(buff.stacks, 1);
+        assert!(!buff.tick(2.0));
+
+        // Default policy drops every stack at once
+        let mut buff =
+            BuffSource::new("test".to_string(), "Test".to_string(), 2.0, false).with_stacks(3);
+        assert!(!buff.tick(2.5));
+    }
+
+    #[test]
+    fn test_reapplied_buff_stacks_on_stat_block() {
+        let mut block = crate::stat_block::StatBlock::new();
+        block.rebuild_from_sources(&[]);
+        let base_strength = block.strength.compute();
+
+        let might = BuffSource::new("might".to_string(), "Might".to_string(), 10.0, false)
+            .with_modifier(StatType::AddedStrength, 10.0, false);
+        for _ in 0..3 {
+            block.apply_buff(might.clone());
+        }
+
+        assert_eq!(block.active_buff_sources().len(), 1);
+        assert_eq!(block.active_buff_sources()[0].stacks, 3);
+        assert!((block.strength.compute() - base_strength - 30.0).abs() < f64::EPSILON);
+    }
 }
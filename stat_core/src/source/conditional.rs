@@ -0,0 +1,92 @@
+//! ConditionalSource - Stats that only apply while a condition holds
+
+use crate::source::{SourceContext, StatSource};
+use crate::stat_block::StatAccumulator;
+use loot_core::types::StatusEffect;
+use serde::{Deserialize, Serialize};
+
+/// A condition evaluated against the rebuild context
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Condition {
+    /// Life is at or below `threshold` of maximum (0.35 = 35%)
+    LowLife { threshold: f64 },
+    /// Life is at maximum
+    FullLife,
+    /// The current target is afflicted by the ailment
+    TargetHasAilment(StatusEffect),
+    /// The entity is moving
+    IsMoving,
+}
+
+impl Condition {
+    /// Check whether the condition holds in a context
+    pub fn holds(&self, context: &SourceContext) -> bool {
+        match self {
+            Condition::LowLife { threshold } => context.life_fraction <= *threshold,
+            Condition::FullLife => context.life_fraction >= 1.0,
+            Condition::TargetHasAilment(status) => context.target_ailments.contains(status),
+            Condition::IsMoving => context.is_moving,
+        }
+    }
+}
+
+/// Wraps another source and applies it only while a condition holds
+///
+/// Conditions are evaluated when stats are rebuilt, so callers rebuild
+/// when the relevant state changes (e.g. after taking damage).
+pub struct ConditionalSource {
+    /// The condition gating the inner source
+    pub condition: Condition,
+    inner: Box<dyn StatSource>,
+}
+
+impl ConditionalSource {
+    /// Create a conditional wrapper around a source
+    pub fn new(condition: Condition, inner: Box<dyn StatSource>) -> Self {
+        ConditionalSource { condition, inner }
+    }
+}
+
+impl StatSource for ConditionalSource {
+    fn id(&self) -> &str {
+        self.inner.id()
+    }
+
+    fn priority(&self) -> i32 {
+        self.inner.priority()
+    }
+
+    /// Without a context, the condition is checked against `SourceContext::default()`
+    fn apply(&self, stats: &mut StatAccumulator) {
+        self.apply_with_context(stats, &SourceContext::default());
+    }
+
+    fn apply_with_context(&self, stats: &mut StatAccumulator, context: &SourceContext) {
+        if self.condition.holds(context) {
+            self.inner.apply_with_context(stats, context);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conditions() {
+        let mut context = SourceContext::default();
+        assert!(Condition::FullLife.holds(&context));
+        assert!(!Condition::LowLife { threshold: 0.35 }.holds(&context));
+        assert!(!Condition::IsMoving.holds(&context));
+
+        context.life_fraction = 0.35;
+        context.is_moving = true;
+        context.target_ailments.push(StatusEffect::Freeze);
+        assert!(!Condition::FullLife.holds(&context));
+        assert!(Condition::LowLife { threshold: 0.35 }.holds(&context));
+        assert!(Condition::IsMoving.holds(&context));
+        assert!(Condition::TargetHasAilment(StatusEffect::Freeze).holds(&context));
+        assert!(!Condition::TargetHasAilment(StatusEffect::Poison).holds(&context));
+    }
+}
@@ -0,0 +1,139 @@
+//! FlaskSource - Reusable consumables with charges and a timed effect
+
+use crate::source::StatSource;
+use crate::stat_block::StatAccumulator;
+use loot_core::types::StatType;
+
+/// Flask/consumable source
+///
+/// A flask holds charges; each use spends `charges_per_use` and grants its
+/// modifiers for `duration` seconds. The flask held by the caller keeps the
+/// charges, while the copy pushed onto a `StatBlock` tracks the timer.
+#[derive(Debug, Clone)]
+pub struct FlaskSource {
+    /// Flask identifier
+    pub flask_id: String,
+    /// Display name
+    pub name: String,
+    /// Effect duration per use in seconds
+    pub duration: f64,
+    /// Duration remaining on the active effect in seconds
+    pub duration_remaining: f64,
+    /// Current charges
+    pub charges: u32,
+    /// Maximum charges the flask can hold
+    pub max_charges: u32,
+    /// Charges spent per use
+    pub charges_per_use: u32,
+    /// Stat modifiers granted while active
+    modifiers: Vec<(StatType, f64)>,
+}
+
+impl FlaskSource {
+    /// Create a new flask with full charges
+    pub fn new(
+        flask_id: String,
+        name: String,
+        duration: f64,
+        max_charges: u32,
+        charges_per_use: u32,
+    ) -> Self {
+        FlaskSource {
+            flask_id,
+            name,
+            duration,
+            duration_remaining: 0.0,
+            charges: max_charges,
+            max_charges,
+            charges_per_use,
+            modifiers: Vec::new(),
+        }
+    }
+
+    /// Add a stat modifier granted while the flask is active
+    pub fn with_modifier(mut self, stat: StatType, value: f64) -> Self {
+        self.modifiers.push((stat, value));
+        self
+    }
+
+    /// Set the current charge count (capped at max charges)
+    pub fn with_charges(mut self, charges: u32) -> Self {
+        self.charges = charges.min(self.max_charges);
+        self
+    }
+
+    /// Check if the flask has enough charges for one use
+    pub fn can_use(&self) -> bool {
+        self.charges >= self.charges_per_use
+    }
+
+    /// Spend charges for one use
+    /// Returns the active copy of the flask, or None if charges are insufficient
+    pub fn consume(&mut self) -> Option<FlaskSource> {
+        if !self.can_use() {
+            return None;
+        }
+        self.charges -= self.charges_per_use;
+
+        let mut active = self.clone();
+        active.duration_remaining = self.duration;
+        Some(active)
+    }
+
+    /// Gain charges (capped at max charges)
+    pub fn add_charges(&mut self, amount: u32) {
+        self.charges = self.charges.saturating_add(amount).min(self.max_charges);
+    }
+
+    /// Tick the effect duration
+    /// Returns true if the effect is still active
+    pub fn tick(&mut self, delta: f64) -> bool {
+        self.duration_remaining -= delta;
+        self.is_active()
+    }
+
+    /// Check if the effect is active
+    pub fn is_active(&self) -> bool {
+        self.duration_remaining > 0.0
+    }
+}
+
+impl StatSource for FlaskSource {
+    fn id(&self) -> &str {
+        &self.flask_id
+    }
+
+    fn priority(&self) -> i32 {
+        200 // Flasks apply alongside buffs
+    }
+
+    fn apply(&self, stats: &mut StatAccumulator) {
+        if !self.is_active() {
+            return;
+        }
+
+        for &(stat, value) in &self.modifiers {
+            stats.apply_stat_type(stat, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consume_spends_charges() {
+        let mut flask = FlaskSource::new("life".to_string(), "Life Flask".to_string(), 4.0, 30, 10);
+
+        for _ in 0..3 {
+            let active = flask.consume().unwrap();
+            assert!((active.duration_remaining - 4.0).abs() < 0.01);
+        }
+        assert_eq!(flask.charges, 0);
+        assert!(flask.consume().is_none());
+
+        flask.add_charges(100);
+        assert_eq!(flask.charges, 30);
+    }
+}
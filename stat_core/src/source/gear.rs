@@ -1,83 +1,114 @@
 //! GearSource - Stats from equipped items
 
-use crate::source::StatSource;
+use crate::source::{BuffSource, StatSource};
 use crate::stat_block::StatAccumulator;
 use crate::types::EquipmentSlot;
 use loot_core::item::Modifier;
 use loot_core::types::{AffixScope, DamageType, StatType};
 use loot_core::Item;
+use std::borrow::Cow;
 
 /// Stats from an equipped item
-pub struct GearSource {
+///
+/// The item is either owned or borrowed; rebuilds borrow the equipped items
+/// so they are not cloned on every stat recompute.
+pub struct GearSource<'a> {
     /// Which slot this item is in
     pub slot: EquipmentSlot,
     /// The equipped item
-    pub item: Item,
+    pub item: Cow<'a, Item>,
+    /// Persistent buff granted while the item is equipped, also used for
+    /// special item effects such as armour added as evasion
+    pub buff: Option<BuffSource>,
+    /// Multiplier on the item's own stat contributions (1.0 = full)
+    pub effectiveness: f64,
 }
 
-impl GearSource {
-    /// Create a new gear source
+impl GearSource<'static> {
+    /// Create a new gear source that owns its item
     pub fn new(slot: EquipmentSlot, item: Item) -> Self {
-        GearSource { slot, item }
+        GearSource::from_cow(slot, Cow::Owned(item))
+    }
+}
+
+impl<'a> GearSource<'a> {
+    /// Create a gear source that borrows an item instead of cloning it
+    pub fn borrowed(slot: EquipmentSlot, item: &'a Item) -> Self {
+        GearSource::from_cow(slot, Cow::Borrowed(item))
+    }
+
+    fn from_cow(slot: EquipmentSlot, item: Cow<'a, Item>) -> Self {
+        GearSource {
+            slot,
+            item,
+            buff: None,
+            effectiveness: 1.0,
+        }
+    }
+
+    /// Scale the item's own stat contributions (e.g., for unmet requirements)
+    pub fn with_effectiveness(mut self, effectiveness: f64) -> Self {
+        self.effectiveness = effectiveness;
+        self
+    }
+
+    /// Grant a persistent buff while this item is equipped
+    pub fn with_buff(mut self, buff: BuffSource) -> Self {
+        self.buff = Some(buff);
+        self
     }
 
     /// Apply a modifier, handling local scope for weapons
     fn apply_modifier(&self, stats: &mut StatAccumulator, modifier: &Modifier, is_weapon: bool) {
+        let eff = self.effectiveness;
+        let value = modifier.value as f64 * eff;
+
         // Local scope on weapons: add to weapon damage
         if is_weapon && modifier.scope == AffixScope::Local {
+            let min = value;
+            let max = modifier.value_max.unwrap_or(modifier.value) as f64 * eff;
             match modifier.stat {
                 StatType::AddedPhysicalDamage => {
-                    let min = modifier.value as f64;
-                    let max = modifier.value_max.unwrap_or(modifier.value) as f64;
                     stats.weapon_physical_min += min;
                     stats.weapon_physical_max += max;
                 }
                 StatType::AddedFireDamage => {
-                    let min = modifier.value as f64;
-                    let max = modifier.value_max.unwrap_or(modifier.value) as f64;
-                    stats.weapon_elemental_damages.push((DamageType::Fire, min, max));
+                    stats
+                        .weapon_elemental_damages
+                        .push((DamageType::Fire, min, max));
                 }
                 StatType::AddedColdDamage => {
-                    let min = modifier.value as f64;
-                    let max = modifier.value_max.unwrap_or(modifier.value) as f64;
-                    stats.weapon_elemental_damages.push((DamageType::Cold, min, max));
+                    stats
+                        .weapon_elemental_damages
+                        .push((DamageType::Cold, min, max));
                 }
                 StatType::AddedLightningDamage => {
-                    let min = modifier.value as f64;
-                    let max = modifier.value_max.unwrap_or(modifier.value) as f64;
-                    stats.weapon_elemental_damages.push((DamageType::Lightning, min, max));
+                    stats
+                        .weapon_elemental_damages
+                        .push((DamageType::Lightning, min, max));
                 }
                 StatType::AddedChaosDamage => {
-                    let min = modifier.value as f64;
-                    let max = modifier.value_max.unwrap_or(modifier.value) as f64;
-                    stats.weapon_elemental_damages.push((DamageType::Chaos, min, max));
+                    stats
+                        .weapon_elemental_damages
+                        .push((DamageType::Chaos, min, max));
                 }
                 StatType::IncreasedPhysicalDamage => {
-                    stats.weapon_physical_increased += modifier.value as f64 / 100.0;
+                    stats.weapon_physical_increased += value / 100.0;
                 }
                 // Other local stats fall through to global handling
                 _ => {
-                    stats.apply_stat_type(modifier.stat, modifier.value as f64);
+                    stats.apply_stat_type(modifier.stat, value);
                 }
             }
         } else {
             // Global scope or non-weapon: apply as character stat
-            stats.apply_stat_type(modifier.stat, modifier.value as f64);
+            stats.apply_stat_type(modifier.stat, value);
         }
     }
-}
 
-impl StatSource for GearSource {
-    fn id(&self) -> &str {
-        &self.item.base_type_id
-    }
-
-    fn priority(&self) -> i32 {
-        0 // Gear applies at default priority
-    }
-
-    fn apply(&self, stats: &mut StatAccumulator) {
-        let is_weapon = self.item.damage.is_some() && matches!(self.slot, EquipmentSlot::MainHand);
+    /// Apply the item's own modifiers, defenses and weapon damage
+    fn apply_item(&self, stats: &mut StatAccumulator) {
+        let is_weapon = self.item.damage.is_some() && self.is_weapon_slot();
 
         // Apply implicit modifier
         if let Some(ref implicit) = self.item.implicit {
@@ -96,30 +127,31 @@ impl StatSource for GearSource {
 
         // Apply base defenses
         if let Some(armour) = self.item.defenses.armour {
-            stats.armour_flat += armour as f64;
+            stats.armour_flat += armour as f64 * self.effectiveness;
         }
         if let Some(evasion) = self.item.defenses.evasion {
-            stats.evasion_flat += evasion as f64;
+            stats.evasion_flat += evasion as f64 * self.effectiveness;
         }
         if let Some(es) = self.item.defenses.energy_shield {
-            stats.energy_shield_flat += es as f64;
+            stats.energy_shield_flat += es as f64 * self.effectiveness;
         }
 
         // Apply weapon damage (if weapon)
         if let Some(ref damage) = self.item.damage {
-            // Only apply if this is the main hand weapon
-            if matches!(self.slot, EquipmentSlot::MainHand) {
+            // Only apply if the weapon is held in a hand
+            if self.is_weapon_slot() {
                 for entry in &damage.damages {
                     match entry.damage_type {
+                        // Added to any local flat physical applied above
                         DamageType::Physical => {
-                            stats.weapon_physical_min = entry.min as f64;
-                            stats.weapon_physical_max = entry.max as f64;
+                            stats.weapon_physical_min += entry.min as f64 * self.effectiveness;
+                            stats.weapon_physical_max += entry.max as f64 * self.effectiveness;
                         }
                         _ => {
                             stats.weapon_elemental_damages.push((
                                 entry.damage_type,
-                                entry.min as f64,
-                                entry.max as f64,
+                                entry.min as f64 * self.effectiveness,
+                                entry.max as f64 * self.effectiveness,
                             ));
                         }
                     }
@@ -129,16 +161,61 @@ impl StatSource for GearSource {
             }
         }
     }
+
+    /// Check if this slot holds weapons (main hand or off hand)
+    fn is_weapon_slot(&self) -> bool {
+        matches!(self.slot, EquipmentSlot::MainHand | EquipmentSlot::OffHand)
+    }
+}
+
+/// Damage ranges of a weapon with its local modifiers applied
+///
+/// Base damage plus local flat added damage, with local increased physical
+/// damage applied to physical. Uses the same rules as [`GearSource`], so
+/// displays built on this match what equipping the weapon grants. Returns
+/// an empty list for items without weapon damage.
+pub fn effective_weapon_damage(item: &Item) -> Vec<(DamageType, f64, f64)> {
+    if item.damage.is_none() {
+        return Vec::new();
+    }
+    let mut stats = StatAccumulator::new();
+    GearSource::borrowed(EquipmentSlot::MainHand, item).apply_item(&mut stats);
+    stats.take_weapon().local_damages()
+}
+
+impl StatSource for GearSource<'_> {
+    fn id(&self) -> &str {
+        &self.item.base_type_id
+    }
+
+    fn priority(&self) -> i32 {
+        0 // Gear applies at default priority
+    }
+
+    fn apply(&self, stats: &mut StatAccumulator) {
+        // An off-hand weapon is kept separate so it can be blended with the main hand
+        if self.item.damage.is_some() && matches!(self.slot, EquipmentSlot::OffHand) {
+            let mut hand = StatAccumulator::new();
+            self.apply_item(&mut hand);
+            stats.off_hand_weapon = Some(hand.take_weapon());
+            stats.merge(&hand);
+        } else {
+            self.apply_item(stats);
+        }
+
+        // Apply item-granted buff
+        if let Some(ref buff) = self.buff {
+            buff.apply(stats);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_gear_source_id() {
-        // Create a minimal item for testing
-        let item = Item {
+    fn test_item() -> Item {
+        Item {
             seed: 12345,
             operations: vec![],
             base_type_id: "test_sword".to_string(),
@@ -153,9 +230,104 @@ mod tests {
             suffixes: vec![],
             defenses: loot_core::item::Defenses::default(),
             damage: None,
-        };
+        }
+    }
 
-        let source = GearSource::new(EquipmentSlot::MainHand, item);
+    fn local_modifier(stat: StatType, value: i32, value_max: Option<i32>) -> Modifier {
+        Modifier {
+            affix_id: format!("local_{:?}", stat),
+            name: format!("{:?}", stat),
+            stat,
+            scope: AffixScope::Local,
+            tier: 1,
+            value,
+            value_max,
+        }
+    }
+
+    fn test_sword() -> Item {
+        Item {
+            damage: Some(loot_core::item::WeaponDamage {
+                damages: vec![loot_core::item::DamageRange {
+                    damage_type: DamageType::Physical,
+                    min: 10,
+                    max: 20,
+                }],
+                attack_speed: 1.2,
+                critical_chance: 5.0,
+            }),
+            ..test_item()
+        }
+    }
+
+    #[test]
+    fn test_gear_source_id() {
+        let source = GearSource::new(EquipmentSlot::MainHand, test_item());
         assert_eq!(source.id(), "test_sword");
     }
+
+    #[test]
+    fn test_borrowed_gear_source_does_not_clone() {
+        let mut item = test_item();
+        item.defenses.armour = Some(120);
+
+        let source = GearSource::borrowed(EquipmentSlot::Helmet, &item);
+        assert!(matches!(source.item, Cow::Borrowed(_)));
+        assert!(std::ptr::eq(&*source.item, &item));
+
+        let mut acc = StatAccumulator::new();
+        source.apply(&mut acc);
+        assert!((acc.armour_flat - 120.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_effective_weapon_damage_without_weapon_is_empty() {
+        assert!(effective_weapon_damage(&test_item()).is_empty());
+    }
+
+    #[test]
+    fn test_effective_weapon_damage_applies_local_modifiers() {
+        // 10-20 base physical, 50% local increased physical, 5-10 local added fire
+        let mut sword = test_sword();
+        sword.prefixes = vec![
+            local_modifier(StatType::AddedFireDamage, 5, Some(10)),
+            local_modifier(StatType::IncreasedPhysicalDamage, 50, None),
+        ];
+
+        let damages = effective_weapon_damage(&sword);
+        assert_eq!(damages.len(), 2);
+        assert_eq!(damages[0].0, DamageType::Physical);
+        assert!((damages[0].1 - 15.0).abs() < 0.01);
+        assert!((damages[0].2 - 30.0).abs() < 0.01);
+        assert_eq!(damages[1].0, DamageType::Fire);
+        assert!((damages[1].1 - 5.0).abs() < 0.01);
+        assert!((damages[1].2 - 10.0).abs() < 0.01);
+
+        // Matches what equipping the sword grants
+        let mut stats = StatAccumulator::new();
+        GearSource::new(EquipmentSlot::MainHand, sword).apply(&mut stats);
+        assert_eq!(stats.take_weapon().local_damages(), damages);
+    }
+
+    #[test]
+    fn test_local_weapon_damages() {
+        // 10-20 base physical, 50% local increased physical, 5-10 local added fire twice
+        let weapon = crate::stat_block::WeaponStats {
+            physical_min: 10.0,
+            physical_max: 20.0,
+            physical_increased: 0.5,
+            elemental_damages: vec![(DamageType::Fire, 5.0, 10.0), (DamageType::Fire, 5.0, 10.0)],
+            attack_speed: 1.2,
+            crit_chance: 5.0,
+        };
+
+        let damages = weapon.local_damages();
+        assert_eq!(damages.len(), 2);
+        assert_eq!(damages[0].0, DamageType::Physical);
+        assert!((damages[0].1 - 15.0).abs() < 0.01);
+        assert!((damages[0].2 - 30.0).abs() < 0.01);
+        assert_eq!(damages[1].0, DamageType::Fire);
+        assert!((damages[1].1 - 10.0).abs() < 0.01);
+        assert!((damages[1].2 - 20.0).abs() < 0.01);
+    }
 }
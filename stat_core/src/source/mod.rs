@@ -1,16 +1,55 @@
 //! StatSource - Trait and implementations for stat providers
 
+mod aura;
 mod base_stats;
 mod buff;
+mod conditional;
+mod flask;
 mod gear;
 mod skill_tree;
 
+pub use aura::AuraSource;
 pub use base_stats::BaseStatsSource;
-pub use buff::BuffSource;
-pub use gear::GearSource;
-pub use skill_tree::SkillTreeSource;
+pub use buff::{BuffExpiry, BuffSource, BuffStacking};
+pub use conditional::{Condition, ConditionalSource};
+pub use flask::FlaskSource;
+pub use gear::{effective_weapon_damage, GearSource};
+pub use skill_tree::{NodeModifier, SkillTreeSource};
 
-use crate::stat_block::StatAccumulator;
+use crate::stat_block::{StatAccumulator, StatBlock};
+use loot_core::types::StatusEffect;
+
+/// State that conditional sources are evaluated against during a rebuild
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceContext {
+    /// Current life as a fraction of maximum (1.0 = full)
+    pub life_fraction: f64,
+    /// Whether the entity is moving
+    pub is_moving: bool,
+    /// Ailments on the entity's current target
+    pub target_ailments: Vec<StatusEffect>,
+}
+
+impl Default for SourceContext {
+    fn default() -> Self {
+        SourceContext {
+            life_fraction: 1.0,
+            is_moving: false,
+            target_ailments: Vec::new(),
+        }
+    }
+}
+
+impl SourceContext {
+    /// Capture the entity's own state from a stat block (no target)
+    pub fn from_block(block: &StatBlock) -> Self {
+        SourceContext {
+            life_fraction: block.life_percent() / 100.0,
+            is_moving: block.is_moving,
+            ..Default::default()
+        }
+    }
+}
 
 /// Trait for anything that contributes stats to a StatBlock
 pub trait StatSource: Send + Sync {
@@ -23,11 +62,19 @@ pub trait StatSource: Send + Sync {
     /// - Base stats: -100
     /// - Gear: 0
     /// - Skill tree: 100
-    /// - Buffs: 200
+    /// - Auras: 150
+    /// - Buffs and flasks: 200
     fn priority(&self) -> i32 {
         0
     }
 
     /// Apply this source's stats to the accumulator
     fn apply(&self, stats: &mut StatAccumulator);
+
+    /// Apply this source's stats given the rebuild context
+    ///
+    /// Unconditional sources ignore the context; the default calls `apply`.
+    fn apply_with_context(&self, stats: &mut StatAccumulator, _context: &SourceContext) {
+        self.apply(stats);
+    }
 }
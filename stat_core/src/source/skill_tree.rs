@@ -4,6 +4,7 @@ use crate::source::StatSource;
 use crate::stat_block::StatAccumulator;
 use crate::types::SkillNodeId;
 use loot_core::types::StatType;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Stats from skill tree nodes
@@ -11,6 +12,8 @@ use std::collections::HashMap;
 /// This holds a flat list of allocated node IDs.
 /// Tree structure, connections, and path validation are handled elsewhere.
 /// stat_manager only cares about "what nodes give what stats".
+///
+/// Node definitions can be loaded from TOML with `config::load_skill_tree`.
 pub struct SkillTreeSource {
     /// List of allocated node IDs
     pub allocated_nodes: Vec<SkillNodeId>,
@@ -19,11 +22,12 @@ pub struct SkillTreeSource {
 }
 
 /// A stat modifier from a skill node
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NodeModifier {
     pub stat: StatType,
     pub value: f64,
     /// Whether this is a "more" multiplier instead of "increased"
+    #[serde(default)]
     pub is_more: bool,
 }
 
@@ -94,24 +98,9 @@ impl StatSource for SkillTreeSource {
 }
 
 fn apply_node_modifier(stats: &mut StatAccumulator, modifier: &NodeModifier) {
-    // For "more" multipliers, we need to track them separately
-    // For now, apply as "increased" (the StatAccumulator handles the distinction)
-    if modifier.is_more {
-        // More multipliers need special handling
-        match modifier.stat {
-            StatType::IncreasedPhysicalDamage => {
-                stats.physical_damage_more.push(modifier.value / 100.0);
-            }
-            StatType::IncreasedLife => {
-                stats.life_more.push(modifier.value / 100.0);
-            }
-            // Add more "more" multiplier cases as needed
-            _ => {
-                // Default: apply as increased for stats that don't have more tracking
-                stats.apply_stat_type(modifier.stat, modifier.value);
-            }
-        }
-    } else {
+    // "More" multipliers are tracked separately; stats without a "more"
+    // channel fall back to "increased"
+    if !(modifier.is_more && stats.apply_more_stat_type(modifier.stat, modifier.value)) {
         stats.apply_stat_type(modifier.stat, modifier.value);
     }
 }
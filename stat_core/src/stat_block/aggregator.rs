@@ -1,8 +1,12 @@
 //! StatAccumulator - Collects stat modifications before applying to StatBlock
 
+use crate::damage::{DamageConversions, GainAsExtra};
+use crate::defense::TakenAsConversions;
 use crate::stat_block::StatBlock;
+use crate::types::{AilmentImmunities, ResistanceReduction, SkillTag};
 use loot_core::types::{DamageType, StatType, StatusEffect};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Stats for a specific status effect type
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
@@ -45,6 +49,82 @@ impl StatusConversions {
     }
 }
 
+/// Attack speed bonus granted while dual wielding (0.10 = 10% more)
+pub const DUAL_WIELD_ATTACK_SPEED_BONUS: f64 = 0.10;
+
+/// Base stats of a single held weapon
+#[derive(Debug, Clone, Default)]
+pub struct WeaponStats {
+    pub physical_min: f64,
+    pub physical_max: f64,
+    /// Local increased physical damage on this weapon
+    pub physical_increased: f64,
+    pub elemental_damages: Vec<(DamageType, f64, f64)>,
+    pub attack_speed: f64,
+    pub crit_chance: f64,
+}
+
+impl WeaponStats {
+    /// Check if this holds a weapon (every weapon sets its attack speed)
+    pub fn is_weapon(&self) -> bool {
+        self.attack_speed > 0.0
+    }
+
+    /// Damage ranges per type after local modifiers
+    ///
+    /// Physical includes local increased physical damage; elemental ranges
+    /// of the same type are summed. Types without damage are omitted.
+    pub fn local_damages(&self) -> Vec<(DamageType, f64, f64)> {
+        let mut damages = Vec::new();
+        if self.physical_min > 0.0 || self.physical_max > 0.0 {
+            let phys_mult = 1.0 + self.physical_increased;
+            damages.push((
+                DamageType::Physical,
+                self.physical_min * phys_mult,
+                self.physical_max * phys_mult,
+            ));
+        }
+        for (damage_type, min, max) in &self.elemental_damages {
+            match damages.iter_mut().find(|(dt, _, _)| dt == damage_type) {
+                Some((_, total_min, total_max)) => {
+                    *total_min += min;
+                    *total_max += max;
+                }
+                None => damages.push((*damage_type, *min, *max)),
+            }
+        }
+        damages
+    }
+
+    /// Combine two one-handed weapons into a single dual-wield profile
+    ///
+    /// Attacks alternate between hands, so the combined weapon uses the
+    /// average of both: physical damage (after each weapon's local increased
+    /// damage), elemental damage, attack speed and crit chance. Attack speed
+    /// then gets [`DUAL_WIELD_ATTACK_SPEED_BONUS`].
+    pub fn dual_wield(&self, off_hand: &WeaponStats) -> WeaponStats {
+        let main_mult = 1.0 + self.physical_increased;
+        let off_mult = 1.0 + off_hand.physical_increased;
+        let elemental_damages = self
+            .elemental_damages
+            .iter()
+            .chain(&off_hand.elemental_damages)
+            .map(|(damage_type, min, max)| (*damage_type, min * 0.5, max * 0.5))
+            .collect();
+
+        WeaponStats {
+            physical_min: (self.physical_min * main_mult + off_hand.physical_min * off_mult) * 0.5,
+            physical_max: (self.physical_max * main_mult + off_hand.physical_max * off_mult) * 0.5,
+            physical_increased: 0.0,
+            elemental_damages,
+            attack_speed: (self.attack_speed + off_hand.attack_speed)
+                * 0.5
+                * (1.0 + DUAL_WIELD_ATTACK_SPEED_BONUS),
+            crit_chance: (self.crit_chance + off_hand.crit_chance) * 0.5,
+        }
+    }
+}
+
 /// Accumulates stat modifications from various sources
 ///
 /// This is used during stat rebuilding to collect all modifications
@@ -58,6 +138,8 @@ pub struct StatAccumulator {
     pub mana_flat: f64,
     pub mana_increased: f64,
     pub mana_more: Vec<f64>,
+    /// Fraction of maximum mana reserved (0.25 = 25%)
+    pub mana_reservation: f64,
 
     // === Attributes ===
     pub strength_flat: f64,
@@ -71,15 +153,49 @@ pub struct StatAccumulator {
     // === Defenses ===
     pub armour_flat: f64,
     pub armour_increased: f64,
+    pub armour_more: Vec<f64>,
     pub evasion_flat: f64,
     pub evasion_increased: f64,
+    pub evasion_more: Vec<f64>,
     pub energy_shield_flat: f64,
     pub energy_shield_increased: f64,
+    pub energy_shield_more: Vec<f64>,
+    /// Fraction of total armour also added as evasion
+    /// (0.10 = 10%, see `BuffSource::with_armour_as_evasion`)
+    pub armour_as_evasion: f64,
+    /// Fraction of total evasion also added as armour
+    /// (0.10 = 10%, see `BuffSource::with_evasion_as_armour`)
+    pub evasion_as_armour: f64,
     pub fire_resistance: f64,
     pub cold_resistance: f64,
     pub lightning_resistance: f64,
     pub chaos_resistance: f64,
     pub all_resistances: f64,
+    /// Maximum resistance modifiers in percentage points (set directly by sources)
+    pub max_fire_resistance: f64,
+    pub max_cold_resistance: f64,
+    pub max_lightning_resistance: f64,
+    pub max_chaos_resistance: f64,
+    /// Maximum elemental (fire/cold/lightning) resistance modifier
+    pub max_all_resistances: f64,
+    /// Reduced damage taken from DoTs (0.30 = 30% reduced)
+    pub dot_damage_taken_reduced: f64,
+    /// Less damage taken from hits and DoTs (set directly by sources, 0.10 = 10% less)
+    pub damage_taken_reduction: f64,
+    /// Additional physical damage reduction in percentage points (20.0 = 20%)
+    pub physical_damage_reduction: f64,
+    /// Damage taken as other types (set directly by sources)
+    pub taken_as: TakenAsConversions,
+    /// Chance to block hits, in percentage points (set directly by sources)
+    pub block_chance: f64,
+    /// Increased block chance (0.20 = 20% increased)
+    pub block_chance_increased: f64,
+    /// Percent of physical hit damage taken reflected to the attacker (10.0 = 10%)
+    pub physical_damage_reflected: f64,
+    /// Ailments the entity cannot be afflicted by
+    pub ailment_immunities: AilmentImmunities,
+    /// Minimum status damage, as a fraction of max life, for an ailment to apply
+    pub ailment_threshold: f64,
 
     // === Offense ===
     pub physical_damage_flat: f64,
@@ -98,10 +214,27 @@ pub struct StatAccumulator {
     pub chaos_damage_increased: f64,
     pub chaos_damage_more: Vec<f64>,
     pub elemental_damage_increased: f64,
+    /// Damage conversions (set directly by sources)
+    pub damage_conversions: DamageConversions,
+    /// Gain as extra damage (set directly by sources)
+    pub gain_as_extra: GainAsExtra,
     pub attack_speed_increased: f64,
+    pub attack_speed_more: Vec<f64>,
+    /// Increased cast speed (set directly by sources; there is no cast speed StatType)
     pub cast_speed_increased: f64,
+    /// More cast speed (set directly by sources; there is no cast speed StatType)
+    pub cast_speed_more: Vec<f64>,
+    /// Increased spell damage (set directly by sources, 0.40 = 40%)
+    pub spell_damage_increased: f64,
+    /// Increased duration of all status effects (set directly by sources, 0.50 = 50%)
+    pub effect_duration_increased: f64,
+    /// Increased damage for skills with a tag (set directly by sources, 0.20 = 20%)
+    pub tag_damage_increased: HashMap<SkillTag, f64>,
+    /// More damage multipliers for skills with a tag (set directly by sources)
+    pub tag_damage_more: HashMap<SkillTag, Vec<f64>>,
     pub critical_chance_flat: f64,
     pub critical_chance_increased: f64,
+    pub critical_chance_more: Vec<f64>,
     pub critical_multiplier_flat: f64,
 
     // === Penetration ===
@@ -109,6 +242,17 @@ pub struct StatAccumulator {
     pub cold_penetration: f64,
     pub lightning_penetration: f64,
     pub chaos_penetration: f64,
+    /// Fraction of enemy resistance ignored (set directly by sources, 0.25 = 25%)
+    pub fire_penetration_percent: f64,
+    pub cold_penetration_percent: f64,
+    pub lightning_penetration_percent: f64,
+    pub chaos_penetration_percent: f64,
+    /// Resistance reduction applied to enemies hit
+    pub enemy_resistance_reduction: ResistanceReduction,
+    /// Percentage points of enemy physical damage reduction ignored
+    pub overwhelm: f64,
+    /// Culling strike threshold (the strongest source applies)
+    pub culling_strike_threshold: f64,
 
     // === Recovery ===
     pub life_regen_flat: f64,
@@ -116,6 +260,8 @@ pub struct StatAccumulator {
     pub life_leech_percent: f64,
     pub mana_leech_percent: f64,
     pub life_on_hit: f64,
+    /// Fraction of life regeneration also applied to energy shield (0.5 = 50%)
+    pub es_regen_from_life_regen: f64,
 
     // === Accuracy ===
     pub accuracy_flat: f64,
@@ -133,6 +279,8 @@ pub struct StatAccumulator {
     pub weapon_elemental_damages: Vec<(DamageType, f64, f64)>,
     pub weapon_attack_speed: f64,
     pub weapon_crit_chance: f64,
+    /// Off-hand weapon when dual wielding, blended with the main hand in `apply_to`
+    pub off_hand_weapon: Option<WeaponStats>,
 
     // === Status Effect Stats ===
     // Poison
@@ -225,6 +373,238 @@ impl StatAccumulator {
         StatAccumulator::default()
     }
 
+    /// Add another accumulator's contributions to this one
+    ///
+    /// Equivalent to applying both accumulators' sources to a single
+    /// accumulator. Weapon base attack speed and crit chance are taken from
+    /// `other` when it sets them, matching how a main-hand `GearSource` writes them.
+    pub fn merge(&mut self, other: &StatAccumulator) {
+        self.life_flat += other.life_flat;
+        self.life_increased += other.life_increased;
+        self.life_more.extend_from_slice(&other.life_more);
+        self.mana_flat += other.mana_flat;
+        self.mana_increased += other.mana_increased;
+        self.mana_more.extend_from_slice(&other.mana_more);
+        self.mana_reservation += other.mana_reservation;
+        self.strength_flat += other.strength_flat;
+        self.dexterity_flat += other.dexterity_flat;
+        self.intelligence_flat += other.intelligence_flat;
+        self.constitution_flat += other.constitution_flat;
+        self.wisdom_flat += other.wisdom_flat;
+        self.charisma_flat += other.charisma_flat;
+        self.all_attributes_flat += other.all_attributes_flat;
+        self.armour_flat += other.armour_flat;
+        self.armour_increased += other.armour_increased;
+        self.armour_more.extend_from_slice(&other.armour_more);
+        self.evasion_flat += other.evasion_flat;
+        self.evasion_increased += other.evasion_increased;
+        self.evasion_more.extend_from_slice(&other.evasion_more);
+        self.energy_shield_flat += other.energy_shield_flat;
+        self.energy_shield_increased += other.energy_shield_increased;
+        self.energy_shield_more
+            .extend_from_slice(&other.energy_shield_more);
+        self.armour_as_evasion += other.armour_as_evasion;
+        self.evasion_as_armour += other.evasion_as_armour;
+        self.fire_resistance += other.fire_resistance;
+        self.cold_resistance += other.cold_resistance;
+        self.lightning_resistance += other.lightning_resistance;
+        self.chaos_resistance += other.chaos_resistance;
+        self.all_resistances += other.all_resistances;
+        self.max_fire_resistance += other.max_fire_resistance;
+        self.max_cold_resistance += other.max_cold_resistance;
+        self.max_lightning_resistance += other.max_lightning_resistance;
+        self.max_chaos_resistance += other.max_chaos_resistance;
+        self.max_all_resistances += other.max_all_resistances;
+        self.dot_damage_taken_reduced += other.dot_damage_taken_reduced;
+        self.damage_taken_reduction += other.damage_taken_reduction;
+        self.physical_damage_reduction += other.physical_damage_reduction;
+        self.taken_as.merge(&other.taken_as);
+        self.block_chance += other.block_chance;
+        self.block_chance_increased += other.block_chance_increased;
+        self.physical_damage_reflected += other.physical_damage_reflected;
+        self.ailment_immunities.merge(&other.ailment_immunities);
+        self.ailment_threshold += other.ailment_threshold;
+        self.physical_damage_flat += other.physical_damage_flat;
+        self.physical_damage_increased += other.physical_damage_increased;
+        self.physical_damage_more
+            .extend_from_slice(&other.physical_damage_more);
+        self.fire_damage_flat += other.fire_damage_flat;
+        self.fire_damage_increased += other.fire_damage_increased;
+        self.fire_damage_more
+            .extend_from_slice(&other.fire_damage_more);
+        self.cold_damage_flat += other.cold_damage_flat;
+        self.cold_damage_increased += other.cold_damage_increased;
+        self.cold_damage_more
+            .extend_from_slice(&other.cold_damage_more);
+        self.lightning_damage_flat += other.lightning_damage_flat;
+        self.lightning_damage_increased += other.lightning_damage_increased;
+        self.lightning_damage_more
+            .extend_from_slice(&other.lightning_damage_more);
+        self.chaos_damage_flat += other.chaos_damage_flat;
+        self.chaos_damage_increased += other.chaos_damage_increased;
+        self.chaos_damage_more
+            .extend_from_slice(&other.chaos_damage_more);
+        self.elemental_damage_increased += other.elemental_damage_increased;
+        self.damage_conversions.merge(&other.damage_conversions);
+        self.gain_as_extra.merge(&other.gain_as_extra);
+        self.attack_speed_increased += other.attack_speed_increased;
+        self.attack_speed_more
+            .extend_from_slice(&other.attack_speed_more);
+        self.cast_speed_increased += other.cast_speed_increased;
+        self.cast_speed_more
+            .extend_from_slice(&other.cast_speed_more);
+        self.spell_damage_increased += other.spell_damage_increased;
+        self.effect_duration_increased += other.effect_duration_increased;
+        for (tag, increased) in &other.tag_damage_increased {
+            *self.tag_damage_increased.entry(*tag).or_insert(0.0) += increased;
+        }
+        for (tag, more) in &other.tag_damage_more {
+            self.tag_damage_more.entry(*tag).or_default().extend(more);
+        }
+        self.critical_chance_flat += other.critical_chance_flat;
+        self.critical_chance_increased += other.critical_chance_increased;
+        self.critical_chance_more
+            .extend_from_slice(&other.critical_chance_more);
+        self.critical_multiplier_flat += other.critical_multiplier_flat;
+        self.fire_penetration += other.fire_penetration;
+        self.cold_penetration += other.cold_penetration;
+        self.lightning_penetration += other.lightning_penetration;
+        self.chaos_penetration += other.chaos_penetration;
+        self.fire_penetration_percent += other.fire_penetration_percent;
+        self.cold_penetration_percent += other.cold_penetration_percent;
+        self.lightning_penetration_percent += other.lightning_penetration_percent;
+        self.chaos_penetration_percent += other.chaos_penetration_percent;
+        self.enemy_resistance_reduction.fire += other.enemy_resistance_reduction.fire;
+        self.enemy_resistance_reduction.cold += other.enemy_resistance_reduction.cold;
+        self.enemy_resistance_reduction.lightning += other.enemy_resistance_reduction.lightning;
+        self.enemy_resistance_reduction.chaos += other.enemy_resistance_reduction.chaos;
+        self.overwhelm += other.overwhelm;
+        self.culling_strike_threshold = self
+            .culling_strike_threshold
+            .max(other.culling_strike_threshold);
+        self.life_regen_flat += other.life_regen_flat;
+        self.mana_regen_flat += other.mana_regen_flat;
+        self.life_leech_percent += other.life_leech_percent;
+        self.mana_leech_percent += other.mana_leech_percent;
+        self.life_on_hit += other.life_on_hit;
+        self.es_regen_from_life_regen += other.es_regen_from_life_regen;
+        self.accuracy_flat += other.accuracy_flat;
+        self.accuracy_increased += other.accuracy_increased;
+        self.movement_speed_increased += other.movement_speed_increased;
+        self.item_rarity_increased += other.item_rarity_increased;
+        self.item_quantity_increased += other.item_quantity_increased;
+        self.weapon_physical_min += other.weapon_physical_min;
+        self.weapon_physical_max += other.weapon_physical_max;
+        self.weapon_physical_increased += other.weapon_physical_increased;
+        self.weapon_elemental_damages
+            .extend_from_slice(&other.weapon_elemental_damages);
+        if other.weapon_attack_speed > 0.0 {
+            self.weapon_attack_speed = other.weapon_attack_speed;
+        }
+        if other.weapon_crit_chance > 0.0 {
+            self.weapon_crit_chance = other.weapon_crit_chance;
+        }
+        if other.off_hand_weapon.is_some() {
+            self.off_hand_weapon = other.off_hand_weapon.clone();
+        }
+        self.poison_dot_increased += other.poison_dot_increased;
+        self.poison_duration_increased += other.poison_duration_increased;
+        self.poison_magnitude += other.poison_magnitude;
+        self.poison_max_stacks += other.poison_max_stacks;
+        self.convert_physical_to_poison += other.convert_physical_to_poison;
+        self.convert_fire_to_poison += other.convert_fire_to_poison;
+        self.convert_cold_to_poison += other.convert_cold_to_poison;
+        self.convert_lightning_to_poison += other.convert_lightning_to_poison;
+        self.convert_chaos_to_poison += other.convert_chaos_to_poison;
+        self.bleed_dot_increased += other.bleed_dot_increased;
+        self.bleed_duration_increased += other.bleed_duration_increased;
+        self.bleed_magnitude += other.bleed_magnitude;
+        self.bleed_max_stacks += other.bleed_max_stacks;
+        self.convert_physical_to_bleed += other.convert_physical_to_bleed;
+        self.convert_fire_to_bleed += other.convert_fire_to_bleed;
+        self.convert_cold_to_bleed += other.convert_cold_to_bleed;
+        self.convert_lightning_to_bleed += other.convert_lightning_to_bleed;
+        self.convert_chaos_to_bleed += other.convert_chaos_to_bleed;
+        self.burn_dot_increased += other.burn_dot_increased;
+        self.burn_duration_increased += other.burn_duration_increased;
+        self.burn_magnitude += other.burn_magnitude;
+        self.burn_max_stacks += other.burn_max_stacks;
+        self.convert_physical_to_burn += other.convert_physical_to_burn;
+        self.convert_fire_to_burn += other.convert_fire_to_burn;
+        self.convert_cold_to_burn += other.convert_cold_to_burn;
+        self.convert_lightning_to_burn += other.convert_lightning_to_burn;
+        self.convert_chaos_to_burn += other.convert_chaos_to_burn;
+        self.freeze_duration_increased += other.freeze_duration_increased;
+        self.freeze_magnitude += other.freeze_magnitude;
+        self.freeze_max_stacks += other.freeze_max_stacks;
+        self.convert_physical_to_freeze += other.convert_physical_to_freeze;
+        self.convert_fire_to_freeze += other.convert_fire_to_freeze;
+        self.convert_cold_to_freeze += other.convert_cold_to_freeze;
+        self.convert_lightning_to_freeze += other.convert_lightning_to_freeze;
+        self.convert_chaos_to_freeze += other.convert_chaos_to_freeze;
+        self.chill_duration_increased += other.chill_duration_increased;
+        self.chill_magnitude += other.chill_magnitude;
+        self.chill_max_stacks += other.chill_max_stacks;
+        self.convert_physical_to_chill += other.convert_physical_to_chill;
+        self.convert_fire_to_chill += other.convert_fire_to_chill;
+        self.convert_cold_to_chill += other.convert_cold_to_chill;
+        self.convert_lightning_to_chill += other.convert_lightning_to_chill;
+        self.convert_chaos_to_chill += other.convert_chaos_to_chill;
+        self.static_duration_increased += other.static_duration_increased;
+        self.static_magnitude += other.static_magnitude;
+        self.static_max_stacks += other.static_max_stacks;
+        self.convert_physical_to_static += other.convert_physical_to_static;
+        self.convert_fire_to_static += other.convert_fire_to_static;
+        self.convert_cold_to_static += other.convert_cold_to_static;
+        self.convert_lightning_to_static += other.convert_lightning_to_static;
+        self.convert_chaos_to_static += other.convert_chaos_to_static;
+        self.fear_duration_increased += other.fear_duration_increased;
+        self.fear_magnitude += other.fear_magnitude;
+        self.fear_max_stacks += other.fear_max_stacks;
+        self.convert_physical_to_fear += other.convert_physical_to_fear;
+        self.convert_fire_to_fear += other.convert_fire_to_fear;
+        self.convert_cold_to_fear += other.convert_cold_to_fear;
+        self.convert_lightning_to_fear += other.convert_lightning_to_fear;
+        self.convert_chaos_to_fear += other.convert_chaos_to_fear;
+        self.slow_duration_increased += other.slow_duration_increased;
+        self.slow_magnitude += other.slow_magnitude;
+        self.slow_max_stacks += other.slow_max_stacks;
+        self.convert_physical_to_slow += other.convert_physical_to_slow;
+        self.convert_fire_to_slow += other.convert_fire_to_slow;
+        self.convert_cold_to_slow += other.convert_cold_to_slow;
+        self.convert_lightning_to_slow += other.convert_lightning_to_slow;
+        self.convert_chaos_to_slow += other.convert_chaos_to_slow;
+    }
+
+    /// Move the main-hand weapon stats out, leaving them at zero
+    pub fn take_weapon(&mut self) -> WeaponStats {
+        WeaponStats {
+            physical_min: std::mem::take(&mut self.weapon_physical_min),
+            physical_max: std::mem::take(&mut self.weapon_physical_max),
+            physical_increased: std::mem::take(&mut self.weapon_physical_increased),
+            elemental_damages: std::mem::take(&mut self.weapon_elemental_damages),
+            attack_speed: std::mem::take(&mut self.weapon_attack_speed),
+            crit_chance: std::mem::take(&mut self.weapon_crit_chance),
+        }
+    }
+
+    /// Get the weapon used for attacks, blending both hands when dual wielding
+    pub fn combined_weapon(&self) -> WeaponStats {
+        let main_hand = WeaponStats {
+            physical_min: self.weapon_physical_min,
+            physical_max: self.weapon_physical_max,
+            physical_increased: self.weapon_physical_increased,
+            elemental_damages: self.weapon_elemental_damages.clone(),
+            attack_speed: self.weapon_attack_speed,
+            crit_chance: self.weapon_crit_chance,
+        };
+        match &self.off_hand_weapon {
+            Some(off_hand) if main_hand.is_weapon() => main_hand.dual_wield(off_hand),
+            Some(off_hand) => off_hand.clone(),
+            None => main_hand,
+        }
+    }
+
     /// Apply a loot_core StatType modifier to this accumulator
     pub fn apply_stat_type(&mut self, stat: StatType, value: f64) {
         match stat {
@@ -376,6 +756,205 @@ impl StatAccumulator {
         }
     }
 
+    /// Get the accumulated value for a loot_core StatType
+    ///
+    /// Returned in the same units `apply_stat_type` accepts (percentages for
+    /// increased/conversion stats). "More" multipliers are reported separately
+    /// by [`StatAccumulator::more_multipliers`].
+    pub fn stat_value(&self, stat: StatType) -> f64 {
+        match stat {
+            // Flat damage additions
+            StatType::AddedPhysicalDamage => self.physical_damage_flat,
+            StatType::AddedFireDamage => self.fire_damage_flat,
+            StatType::AddedColdDamage => self.cold_damage_flat,
+            StatType::AddedLightningDamage => self.lightning_damage_flat,
+            StatType::AddedChaosDamage => self.chaos_damage_flat,
+
+            // Percentage increases (convert from decimal back to percentage)
+            StatType::IncreasedPhysicalDamage => self.physical_damage_increased * 100.0,
+            StatType::IncreasedFireDamage => self.fire_damage_increased * 100.0,
+            StatType::IncreasedColdDamage => self.cold_damage_increased * 100.0,
+            StatType::IncreasedLightningDamage => self.lightning_damage_increased * 100.0,
+            StatType::IncreasedElementalDamage => self.elemental_damage_increased * 100.0,
+            StatType::IncreasedChaosDamage => self.chaos_damage_increased * 100.0,
+            StatType::IncreasedAttackSpeed => self.attack_speed_increased * 100.0,
+            StatType::IncreasedCriticalChance => self.critical_chance_increased * 100.0,
+            StatType::IncreasedCriticalDamage => self.critical_multiplier_flat * 100.0,
+
+            // Defenses
+            StatType::AddedArmour => self.armour_flat,
+            StatType::AddedEvasion => self.evasion_flat,
+            StatType::AddedEnergyShield => self.energy_shield_flat,
+            StatType::IncreasedArmour => self.armour_increased * 100.0,
+            StatType::IncreasedEvasion => self.evasion_increased * 100.0,
+            StatType::IncreasedEnergyShield => self.energy_shield_increased * 100.0,
+
+            // Attributes
+            StatType::AddedStrength => self.strength_flat,
+            StatType::AddedDexterity => self.dexterity_flat,
+            StatType::AddedConstitution => self.constitution_flat,
+            StatType::AddedIntelligence => self.intelligence_flat,
+            StatType::AddedWisdom => self.wisdom_flat,
+            StatType::AddedCharisma => self.charisma_flat,
+            StatType::AddedAllAttributes => self.all_attributes_flat,
+
+            // Life and resources
+            StatType::AddedLife => self.life_flat,
+            StatType::AddedMana => self.mana_flat,
+            StatType::IncreasedLife => self.life_increased * 100.0,
+            StatType::IncreasedMana => self.mana_increased * 100.0,
+            StatType::LifeRegeneration => self.life_regen_flat,
+            StatType::ManaRegeneration => self.mana_regen_flat,
+            StatType::LifeOnHit => self.life_on_hit,
+            StatType::LifeLeech => self.life_leech_percent * 100.0,
+            StatType::ManaLeech => self.mana_leech_percent * 100.0,
+
+            // Resistances
+            StatType::FireResistance => self.fire_resistance,
+            StatType::ColdResistance => self.cold_resistance,
+            StatType::LightningResistance => self.lightning_resistance,
+            StatType::ChaosResistance => self.chaos_resistance,
+            StatType::AllResistances => self.all_resistances,
+
+            // Accuracy
+            StatType::AddedAccuracy => self.accuracy_flat,
+            StatType::IncreasedAccuracy => self.accuracy_increased * 100.0,
+
+            // Utility
+            StatType::IncreasedMovementSpeed => self.movement_speed_increased * 100.0,
+            StatType::IncreasedItemRarity => self.item_rarity_increased * 100.0,
+            StatType::IncreasedItemQuantity => self.item_quantity_increased * 100.0,
+
+            // === Status Effect Stats ===
+            // Poison
+            StatType::PoisonDamageOverTime => self.poison_dot_increased * 100.0,
+            StatType::IncreasedPoisonDuration => self.poison_duration_increased * 100.0,
+            StatType::PoisonMagnitude => self.poison_magnitude * 100.0,
+            StatType::PoisonMaxStacks => self.poison_max_stacks as f64,
+            StatType::ConvertPhysicalToPoison => self.convert_physical_to_poison * 100.0,
+            StatType::ConvertFireToPoison => self.convert_fire_to_poison * 100.0,
+            StatType::ConvertColdToPoison => self.convert_cold_to_poison * 100.0,
+            StatType::ConvertLightningToPoison => self.convert_lightning_to_poison * 100.0,
+            StatType::ConvertChaosToPoison => self.convert_chaos_to_poison * 100.0,
+
+            // Bleed
+            StatType::BleedDamageOverTime => self.bleed_dot_increased * 100.0,
+            StatType::IncreasedBleedDuration => self.bleed_duration_increased * 100.0,
+            StatType::BleedMagnitude => self.bleed_magnitude * 100.0,
+            StatType::BleedMaxStacks => self.bleed_max_stacks as f64,
+            StatType::ConvertPhysicalToBleed => self.convert_physical_to_bleed * 100.0,
+            StatType::ConvertFireToBleed => self.convert_fire_to_bleed * 100.0,
+            StatType::ConvertColdToBleed => self.convert_cold_to_bleed * 100.0,
+            StatType::ConvertLightningToBleed => self.convert_lightning_to_bleed * 100.0,
+            StatType::ConvertChaosToBleed => self.convert_chaos_to_bleed * 100.0,
+
+            // Burn
+            StatType::BurnDamageOverTime => self.burn_dot_increased * 100.0,
+            StatType::IncreasedBurnDuration => self.burn_duration_increased * 100.0,
+            StatType::BurnMagnitude => self.burn_magnitude * 100.0,
+            StatType::BurnMaxStacks => self.burn_max_stacks as f64,
+            StatType::ConvertPhysicalToBurn => self.convert_physical_to_burn * 100.0,
+            StatType::ConvertFireToBurn => self.convert_fire_to_burn * 100.0,
+            StatType::ConvertColdToBurn => self.convert_cold_to_burn * 100.0,
+            StatType::ConvertLightningToBurn => self.convert_lightning_to_burn * 100.0,
+            StatType::ConvertChaosToBurn => self.convert_chaos_to_burn * 100.0,
+
+            // Freeze
+            StatType::IncreasedFreezeDuration => self.freeze_duration_increased * 100.0,
+            StatType::FreezeMagnitude => self.freeze_magnitude * 100.0,
+            StatType::FreezeMaxStacks => self.freeze_max_stacks as f64,
+            StatType::ConvertPhysicalToFreeze => self.convert_physical_to_freeze * 100.0,
+            StatType::ConvertFireToFreeze => self.convert_fire_to_freeze * 100.0,
+            StatType::ConvertColdToFreeze => self.convert_cold_to_freeze * 100.0,
+            StatType::ConvertLightningToFreeze => self.convert_lightning_to_freeze * 100.0,
+            StatType::ConvertChaosToFreeze => self.convert_chaos_to_freeze * 100.0,
+
+            // Chill
+            StatType::IncreasedChillDuration => self.chill_duration_increased * 100.0,
+            StatType::ChillMagnitude => self.chill_magnitude * 100.0,
+            StatType::ChillMaxStacks => self.chill_max_stacks as f64,
+            StatType::ConvertPhysicalToChill => self.convert_physical_to_chill * 100.0,
+            StatType::ConvertFireToChill => self.convert_fire_to_chill * 100.0,
+            StatType::ConvertColdToChill => self.convert_cold_to_chill * 100.0,
+            StatType::ConvertLightningToChill => self.convert_lightning_to_chill * 100.0,
+            StatType::ConvertChaosToChill => self.convert_chaos_to_chill * 100.0,
+
+            // Static
+            StatType::IncreasedStaticDuration => self.static_duration_increased * 100.0,
+            StatType::StaticMagnitude => self.static_magnitude * 100.0,
+            StatType::StaticMaxStacks => self.static_max_stacks as f64,
+            StatType::ConvertPhysicalToStatic => self.convert_physical_to_static * 100.0,
+            StatType::ConvertFireToStatic => self.convert_fire_to_static * 100.0,
+            StatType::ConvertColdToStatic => self.convert_cold_to_static * 100.0,
+            StatType::ConvertLightningToStatic => self.convert_lightning_to_static * 100.0,
+            StatType::ConvertChaosToStatic => self.convert_chaos_to_static * 100.0,
+
+            // Fear
+            StatType::IncreasedFearDuration => self.fear_duration_increased * 100.0,
+            StatType::FearMagnitude => self.fear_magnitude * 100.0,
+            StatType::FearMaxStacks => self.fear_max_stacks as f64,
+            StatType::ConvertPhysicalToFear => self.convert_physical_to_fear * 100.0,
+            StatType::ConvertFireToFear => self.convert_fire_to_fear * 100.0,
+            StatType::ConvertColdToFear => self.convert_cold_to_fear * 100.0,
+            StatType::ConvertLightningToFear => self.convert_lightning_to_fear * 100.0,
+            StatType::ConvertChaosToFear => self.convert_chaos_to_fear * 100.0,
+
+            // Slow
+            StatType::IncreasedSlowDuration => self.slow_duration_increased * 100.0,
+            StatType::SlowMagnitude => self.slow_magnitude * 100.0,
+            StatType::SlowMaxStacks => self.slow_max_stacks as f64,
+            StatType::ConvertPhysicalToSlow => self.convert_physical_to_slow * 100.0,
+            StatType::ConvertFireToSlow => self.convert_fire_to_slow * 100.0,
+            StatType::ConvertColdToSlow => self.convert_cold_to_slow * 100.0,
+            StatType::ConvertLightningToSlow => self.convert_lightning_to_slow * 100.0,
+            StatType::ConvertChaosToSlow => self.convert_chaos_to_slow * 100.0,
+        }
+    }
+
+    /// Get the "more" multipliers accumulated for a StatType (decimal, 0.2 = 20% more)
+    pub fn more_multipliers(&self, stat: StatType) -> &[f64] {
+        match stat {
+            StatType::IncreasedPhysicalDamage => &self.physical_damage_more,
+            StatType::IncreasedFireDamage => &self.fire_damage_more,
+            StatType::IncreasedColdDamage => &self.cold_damage_more,
+            StatType::IncreasedLightningDamage => &self.lightning_damage_more,
+            StatType::IncreasedChaosDamage => &self.chaos_damage_more,
+            StatType::IncreasedLife => &self.life_more,
+            StatType::IncreasedMana => &self.mana_more,
+            StatType::IncreasedArmour => &self.armour_more,
+            StatType::IncreasedEvasion => &self.evasion_more,
+            StatType::IncreasedEnergyShield => &self.energy_shield_more,
+            StatType::IncreasedAttackSpeed => &self.attack_speed_more,
+            StatType::IncreasedCriticalChance => &self.critical_chance_more,
+            _ => &[],
+        }
+    }
+
+    /// Add a "more" multiplier for the "increased" StatType it scales
+    ///
+    /// `value` is in the StatType's percent units (50.0 = 50% more). Returns
+    /// false, without applying anything, if the stat has no "more" channel.
+    /// Cast speed has no StatType, so sources push to `cast_speed_more` directly.
+    pub fn apply_more_stat_type(&mut self, stat: StatType, value: f64) -> bool {
+        let more = match stat {
+            StatType::IncreasedPhysicalDamage => &mut self.physical_damage_more,
+            StatType::IncreasedFireDamage => &mut self.fire_damage_more,
+            StatType::IncreasedColdDamage => &mut self.cold_damage_more,
+            StatType::IncreasedLightningDamage => &mut self.lightning_damage_more,
+            StatType::IncreasedChaosDamage => &mut self.chaos_damage_more,
+            StatType::IncreasedLife => &mut self.life_more,
+            StatType::IncreasedMana => &mut self.mana_more,
+            StatType::IncreasedArmour => &mut self.armour_more,
+            StatType::IncreasedEvasion => &mut self.evasion_more,
+            StatType::IncreasedEnergyShield => &mut self.energy_shield_more,
+            StatType::IncreasedAttackSpeed => &mut self.attack_speed_more,
+            StatType::IncreasedCriticalChance => &mut self.critical_chance_more,
+            _ => return false,
+        };
+        more.push(value / 100.0);
+        true
+    }
+
     /// Get conversion percentage for a damage type to a status effect
     pub fn get_conversion(&self, from: DamageType, to: StatusEffect) -> f64 {
         match (from, to) {
@@ -566,6 +1145,7 @@ impl StatAccumulator {
         for more in &self.mana_more {
             block.max_mana.add_more(*more);
         }
+        block.mana_reservation += self.mana_reservation;
 
         // Attributes (all_attributes applies to all)
         block.strength.add_flat(self.strength_flat + self.all_attributes_flat);
@@ -578,14 +1158,57 @@ impl StatAccumulator {
         // Defenses
         block.armour.add_flat(self.armour_flat);
         block.armour.add_increased(self.armour_increased);
+        for more in &self.armour_more {
+            block.armour.add_more(*more);
+        }
         block.evasion.add_flat(self.evasion_flat);
         block.evasion.add_increased(self.evasion_increased);
+        for more in &self.evasion_more {
+            block.evasion.add_more(*more);
+        }
+        let energy_shield_more: f64 = self
+            .energy_shield_more
+            .iter()
+            .map(|more| 1.0 + more)
+            .product();
+        block.max_energy_shield +=
+            self.energy_shield_flat * (1.0 + self.energy_shield_increased) * energy_shield_more;
+
+        // Cross-defense contributions use the summed totals, so neither
+        // defense is reduced and the two cannot feed into each other
+        let total_armour = block.armour.compute();
+        let total_evasion = block.evasion.compute();
+        if self.armour_as_evasion != 0.0 {
+            block
+                .evasion
+                .add_flat(total_armour * self.armour_as_evasion);
+        }
+        if self.evasion_as_armour != 0.0 {
+            block
+                .armour
+                .add_flat(total_evasion * self.evasion_as_armour);
+        }
 
         // Resistances (all_resistances applies to elemental)
         block.fire_resistance.add_flat(self.fire_resistance + self.all_resistances);
         block.cold_resistance.add_flat(self.cold_resistance + self.all_resistances);
         block.lightning_resistance.add_flat(self.lightning_resistance + self.all_resistances);
         block.chaos_resistance.add_flat(self.chaos_resistance);
+        block.max_fire_resistance += self.max_fire_resistance + self.max_all_resistances;
+        block.max_cold_resistance += self.max_cold_resistance + self.max_all_resistances;
+        block.max_lightning_resistance += self.max_lightning_resistance + self.max_all_resistances;
+        block.max_chaos_resistance += self.max_chaos_resistance;
+        block.dot_damage_taken_reduced += self.dot_damage_taken_reduced;
+        block.damage_taken_reduction += self.damage_taken_reduction;
+        block.physical_damage_reduction += self.physical_damage_reduction;
+        block.taken_as.merge(&self.taken_as);
+        block.block_chance.add_flat(self.block_chance);
+        block
+            .block_chance
+            .add_increased(self.block_chance_increased);
+        block.physical_damage_reflected += self.physical_damage_reflected;
+        block.ailment_immunities.merge(&self.ailment_immunities);
+        block.ailment_threshold += self.ailment_threshold;
 
         // Damage - apply elemental increased to fire/cold/lightning
         block.global_physical_damage.add_flat(self.physical_damage_flat);
@@ -617,14 +1240,33 @@ impl StatAccumulator {
         for more in &self.chaos_damage_more {
             block.global_chaos_damage.add_more(*more);
         }
+        block.damage_conversions.merge(&self.damage_conversions);
+        block.gain_as_extra.merge(&self.gain_as_extra);
 
         // Attack/Cast speed
         block.attack_speed.add_increased(self.attack_speed_increased);
+        for more in &self.attack_speed_more {
+            block.attack_speed.add_more(*more);
+        }
         block.cast_speed.add_increased(self.cast_speed_increased);
+        for more in &self.cast_speed_more {
+            block.cast_speed.add_more(*more);
+        }
+        block.spell_damage_increased += self.spell_damage_increased;
+        block.effect_duration_increased += self.effect_duration_increased;
+        for (tag, increased) in &self.tag_damage_increased {
+            *block.tag_damage_increased.entry(*tag).or_insert(0.0) += increased;
+        }
+        for (tag, more) in &self.tag_damage_more {
+            block.tag_damage_more.entry(*tag).or_default().extend(more);
+        }
 
         // Crit
         block.critical_chance.add_flat(self.critical_chance_flat);
         block.critical_chance.add_increased(self.critical_chance_increased);
+        for more in &self.critical_chance_more {
+            block.critical_chance.add_more(*more);
+        }
         block.critical_multiplier.add_flat(self.critical_multiplier_flat);
 
         // Penetration
@@ -632,28 +1274,42 @@ impl StatAccumulator {
         block.cold_penetration.add_flat(self.cold_penetration);
         block.lightning_penetration.add_flat(self.lightning_penetration);
         block.chaos_penetration.add_flat(self.chaos_penetration);
+        block.fire_penetration_percent += self.fire_penetration_percent;
+        block.cold_penetration_percent += self.cold_penetration_percent;
+        block.lightning_penetration_percent += self.lightning_penetration_percent;
+        block.chaos_penetration_percent += self.chaos_penetration_percent;
+        block
+            .enemy_resistance_reduction
+            .add(&self.enemy_resistance_reduction);
+        block.overwhelm += self.overwhelm;
+        block.culling_strike_threshold = block
+            .culling_strike_threshold
+            .max(self.culling_strike_threshold);
 
         // Recovery
         block.life_regen.add_flat(self.life_regen_flat);
         block.mana_regen.add_flat(self.mana_regen_flat);
         block.life_leech.add_flat(self.life_leech_percent);
         block.mana_leech.add_flat(self.mana_leech_percent);
-
-        // Weapon stats - apply local increased physical damage
-        if self.weapon_physical_min > 0.0 || self.weapon_physical_max > 0.0 {
-            let phys_mult = 1.0 + self.weapon_physical_increased;
-            block.weapon_physical_min = self.weapon_physical_min * phys_mult;
-            block.weapon_physical_max = self.weapon_physical_max * phys_mult;
+        block.life_on_hit += self.life_on_hit;
+        block.es_regen_from_life_regen += self.es_regen_from_life_regen;
+
+        // Weapon stats - blend dual-wielded weapons, apply local increased physical damage
+        let weapon = self.combined_weapon();
+        if weapon.physical_min > 0.0 || weapon.physical_max > 0.0 {
+            let phys_mult = 1.0 + weapon.physical_increased;
+            block.weapon_physical_min = weapon.physical_min * phys_mult;
+            block.weapon_physical_max = weapon.physical_max * phys_mult;
         }
-        if self.weapon_attack_speed > 0.0 {
-            block.weapon_attack_speed = self.weapon_attack_speed;
+        if weapon.attack_speed > 0.0 {
+            block.weapon_attack_speed = weapon.attack_speed;
         }
-        if self.weapon_crit_chance > 0.0 {
-            block.weapon_crit_chance = self.weapon_crit_chance;
+        if weapon.crit_chance > 0.0 {
+            block.weapon_crit_chance = weapon.crit_chance;
         }
 
         // Apply weapon elemental damages
-        for (dmg_type, min, max) in &self.weapon_elemental_damages {
+        for (dmg_type, min, max) in &weapon.elemental_damages {
             match dmg_type {
                 DamageType::Fire => {
                     block.weapon_fire_min += min;
@@ -682,7 +1338,9 @@ impl StatAccumulator {
         block.accuracy.add_increased(self.accuracy_increased);
 
         // Utility
-        block.movement_speed_increased += self.movement_speed_increased;
+        block
+            .movement_speed_increased
+            .add_flat(self.movement_speed_increased);
         block.item_rarity_increased += self.item_rarity_increased;
         block.item_quantity_increased += self.item_quantity_increased;
 
@@ -0,0 +1,137 @@
+//! Per-source stat attribution for breakdown views
+
+use crate::source::StatSource;
+use crate::stat_block::{StatAccumulator, StatBlock};
+use loot_core::types::StatType;
+
+/// One source's contribution to a stat
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatContribution {
+    /// The source's `StatSource::id()`
+    pub source_id: String,
+    /// Flat or increased contribution, in the StatType's own units
+    /// (e.g. 75.0 for "+75% increased physical damage")
+    pub value: f64,
+    /// "More" multipliers from this source (0.2 = 20% more)
+    pub more: Vec<f64>,
+}
+
+/// Contributions of each source to a single stat
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatBreakdown {
+    /// The stat being explained
+    pub stat: StatType,
+    /// Contributions in source application order (sources contributing nothing are omitted)
+    pub contributions: Vec<StatContribution>,
+}
+
+impl StatBreakdown {
+    /// Build a breakdown by applying each source to its own accumulator
+    pub fn from_sources(sources: &[Box<dyn StatSource>], stat: StatType) -> Self {
+        let mut sorted_sources: Vec<_> = sources.iter().collect();
+        sorted_sources.sort_by_key(|s| s.priority());
+
+        let contributions = sorted_sources
+            .into_iter()
+            .filter_map(|source| {
+                let mut accumulator = StatAccumulator::new();
+                source.apply(&mut accumulator);
+
+                let value = accumulator.stat_value(stat);
+                let more = accumulator.more_multipliers(stat).to_vec();
+                if value == 0.0 && more.is_empty() {
+                    return None;
+                }
+                Some(StatContribution {
+                    source_id: source.id().to_string(),
+                    value,
+                    more,
+                })
+            })
+            .collect();
+
+        StatBreakdown {
+            stat,
+            contributions,
+        }
+    }
+
+    /// Sum of all flat/increased contributions
+    pub fn total(&self) -> f64 {
+        self.contributions.iter().map(|c| c.value).sum()
+    }
+}
+
+impl StatBlock {
+    /// Explain which sources contribute to a stat
+    ///
+    /// Returns `(source id, value)` pairs in the StatType's own units, e.g.
+    /// `[("iron_sword", 75.0), ("skill_tree", 20.0)]` for increased physical
+    /// damage. Derived bonuses (such as those from attributes) are not listed.
+    pub fn explain_stat(
+        &self,
+        sources: &[Box<dyn StatSource>],
+        stat: StatType,
+    ) -> Vec<(String, f64)> {
+        StatBreakdown::from_sources(sources, stat)
+            .contributions
+            .into_iter()
+            .filter(|c| c.value != 0.0)
+            .map(|c| (c.source_id, c.value))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::BuffSource;
+
+    fn buff(id: &str, stat: StatType, value: f64) -> Box<dyn StatSource> {
+        let source = BuffSource::new(id.to_string(), id.to_string(), 10.0, false);
+        Box::new(source.with_modifier(stat, value, false))
+    }
+
+    #[test]
+    fn test_explain_stat_sums_to_computed_value() {
+        let sources = vec![
+            buff("iron_sword", StatType::IncreasedPhysicalDamage, 75.0),
+            buff("skill_tree", StatType::IncreasedPhysicalDamage, 20.0),
+            buff("belt", StatType::AddedLife, 30.0),
+        ];
+        let mut block = StatBlock::new();
+        block.rebuild_from_sources(&sources);
+
+        let phys = block.explain_stat(&sources, StatType::IncreasedPhysicalDamage);
+        assert_eq!(
+            phys,
+            vec![
+                ("iron_sword".to_string(), 75.0),
+                ("skill_tree".to_string(), 20.0)
+            ]
+        );
+        let total: f64 = phys.iter().map(|(_, v)| v).sum();
+        let increased = block.global_physical_damage.total_increased_multiplier() - 1.0;
+        assert!((total / 100.0 - increased).abs() < 1e-9);
+
+        let life = block.explain_stat(&sources, StatType::AddedLife);
+        let total: f64 = life.iter().map(|(_, v)| v).sum();
+        assert!((block.max_life.base + total - block.computed_max_life()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_breakdown_reports_more_multipliers() {
+        let sources: Vec<Box<dyn StatSource>> = vec![Box::new(
+            BuffSource::new("rage".to_string(), "Rage".to_string(), 10.0, false).with_modifier(
+                StatType::IncreasedPhysicalDamage,
+                20.0,
+                true,
+            ),
+        )];
+
+        let breakdown = StatBreakdown::from_sources(&sources, StatType::IncreasedPhysicalDamage);
+        assert_eq!(breakdown.contributions.len(), 1);
+        assert!((breakdown.total() - 0.0).abs() < f64::EPSILON);
+        assert_eq!(breakdown.contributions[0].more, vec![0.2]);
+    }
+}
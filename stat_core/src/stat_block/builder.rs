@@ -0,0 +1,227 @@
+//! StatBlockBuilder - Chainable construction of StatBlocks
+//!
+//! Setters assign base values; everything else defaults the way
+//! `StatBlock::new()` does.
+//!
+//! ```rust,ignore
+//! let goblin = StatBlockBuilder::new("goblin")
+//!     .max_life(500.0)
+//!     .armour(200.0)
+//!     .fire_resistance(25.0)
+//!     .build();
+//! ```
+
+use crate::stat_block::StatBlock;
+use loot_core::types::StatusEffect;
+
+/// Builder for `StatBlock`
+#[derive(Debug, Clone)]
+pub struct StatBlockBuilder {
+    block: StatBlock,
+}
+
+impl Default for StatBlockBuilder {
+    fn default() -> Self {
+        StatBlockBuilder {
+            block: StatBlock::new(),
+        }
+    }
+}
+
+impl StatBlockBuilder {
+    /// Start building a StatBlock with a specific ID
+    pub fn new(id: impl Into<String>) -> Self {
+        StatBlockBuilder {
+            block: StatBlock::with_id(id),
+        }
+    }
+
+    // === Resources ===
+
+    /// Set base maximum life
+    pub fn max_life(mut self, value: f64) -> Self {
+        self.block.max_life.base = value;
+        self
+    }
+
+    /// Set base maximum mana
+    pub fn max_mana(mut self, value: f64) -> Self {
+        self.block.max_mana.base = value;
+        self
+    }
+
+    /// Set maximum energy shield
+    pub fn energy_shield(mut self, value: f64) -> Self {
+        self.block.max_energy_shield = value;
+        self
+    }
+
+    // === Attributes ===
+
+    /// Set base strength
+    pub fn strength(mut self, value: f64) -> Self {
+        self.block.strength.base = value;
+        self
+    }
+
+    /// Set base dexterity
+    pub fn dexterity(mut self, value: f64) -> Self {
+        self.block.dexterity.base = value;
+        self
+    }
+
+    /// Set base intelligence
+    pub fn intelligence(mut self, value: f64) -> Self {
+        self.block.intelligence.base = value;
+        self
+    }
+
+    // === Defenses ===
+
+    /// Set base armour
+    pub fn armour(mut self, value: f64) -> Self {
+        self.block.armour.base = value;
+        self
+    }
+
+    /// Set base evasion
+    pub fn evasion(mut self, value: f64) -> Self {
+        self.block.evasion.base = value;
+        self
+    }
+
+    /// Set base fire resistance (percentage)
+    pub fn fire_resistance(mut self, value: f64) -> Self {
+        self.block.fire_resistance.base = value;
+        self
+    }
+
+    /// Set base cold resistance (percentage)
+    pub fn cold_resistance(mut self, value: f64) -> Self {
+        self.block.cold_resistance.base = value;
+        self
+    }
+
+    /// Set base lightning resistance (percentage)
+    pub fn lightning_resistance(mut self, value: f64) -> Self {
+        self.block.lightning_resistance.base = value;
+        self
+    }
+
+    /// Set base chaos resistance (percentage)
+    pub fn chaos_resistance(mut self, value: f64) -> Self {
+        self.block.chaos_resistance.base = value;
+        self
+    }
+
+    /// Set base fire, cold and lightning resistance (percentage)
+    pub fn elemental_resistances(self, value: f64) -> Self {
+        self.fire_resistance(value)
+            .cold_resistance(value)
+            .lightning_resistance(value)
+    }
+
+    /// Set base block chance (percentage)
+    pub fn block_chance(mut self, value: f64) -> Self {
+        self.block.block_chance.base = value;
+        self
+    }
+
+    /// Make the entity immune to a status effect
+    pub fn immune_to(mut self, status: StatusEffect) -> Self {
+        self.block.ailment_immunities.grant(status);
+        self
+    }
+
+    // === Offense ===
+
+    /// Set base accuracy
+    pub fn accuracy(mut self, value: f64) -> Self {
+        self.block.accuracy.base = value;
+        self
+    }
+
+    /// Set base critical strike chance (percentage)
+    pub fn critical_chance(mut self, value: f64) -> Self {
+        self.block.critical_chance.base = value;
+        self
+    }
+
+    /// Set weapon physical damage range
+    pub fn weapon_damage(mut self, min: f64, max: f64) -> Self {
+        self.block.weapon_physical_min = min;
+        self.block.weapon_physical_max = max;
+        self
+    }
+
+    /// Set weapon attacks per second
+    pub fn weapon_attack_speed(mut self, value: f64) -> Self {
+        self.block.weapon_attack_speed = value;
+        self
+    }
+
+    // === Recovery ===
+
+    /// Set base life regeneration per second
+    pub fn life_regen(mut self, value: f64) -> Self {
+        self.block.life_regen.base = value;
+        self
+    }
+
+    /// Set base mana regeneration per second
+    pub fn mana_regen(mut self, value: f64) -> Self {
+        self.block.mana_regen.base = value;
+        self
+    }
+
+    /// Finish building, starting with full life, mana and energy shield
+    pub fn build(mut self) -> StatBlock {
+        self.block.current_life = self.block.computed_max_life();
+        self.block.current_mana = self.block.unreserved_max_mana();
+        self.block.current_energy_shield = self.block.max_energy_shield;
+        self.block
+    }
+}
+
+impl StatBlock {
+    /// Start building a StatBlock with a specific ID
+    pub fn builder(id: impl Into<String>) -> StatBlockBuilder {
+        StatBlockBuilder::new(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_built_block_matches_hand_assembled() {
+        let built = StatBlock::builder("goblin")
+            .max_life(500.0)
+            .armour(200.0)
+            .fire_resistance(25.0)
+            .weapon_damage(5.0, 10.0)
+            .build();
+
+        let mut manual = StatBlock::with_id("goblin");
+        manual.max_life.base = 500.0;
+        manual.current_life = 500.0;
+        manual.armour.base = 200.0;
+        manual.fire_resistance.base = 25.0;
+        manual.weapon_physical_min = 5.0;
+        manual.weapon_physical_max = 10.0;
+
+        assert_eq!(built.id, manual.id);
+        assert!(built.diff(&manual).is_empty(), "{:?}", built.diff(&manual));
+        assert!((built.current_life - manual.current_life).abs() < f64::EPSILON);
+        assert!((built.current_mana - manual.current_mana).abs() < f64::EPSILON);
+        assert!((built.current_energy_shield - manual.current_energy_shield).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_build_fills_current_life() {
+        let block = StatBlockBuilder::default().max_life(1234.0).build();
+        assert_eq!(block.id, "entity");
+        assert!((block.current_life - 1234.0).abs() < f64::EPSILON);
+    }
+}
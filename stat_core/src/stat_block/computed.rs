@@ -1,24 +1,48 @@
 //! Computed/derived stat calculations for StatBlock
 
-use crate::stat_block::StatBlock;
-use loot_core::types::DamageType;
+use crate::damage::DamagePacketGenerator;
+use crate::defense::calculate_resistance_mitigation_with_cap;
+use crate::defense::constants::{MAX_BLOCK_CHANCE, MAX_RESISTANCE};
+use crate::stat_block::{StatBlock, StatValue};
+use crate::types::{Effect, EffectType, ResourceKind, SkillTag};
+use loot_core::types::{DamageType, StatusEffect};
 
 impl StatBlock {
-    /// Get the damage scaling multiplier for a specific damage type
-    pub fn damage_multiplier(&self, damage_type: DamageType) -> f64 {
+    /// Get the global damage stat for a damage type
+    pub fn global_damage(&self, damage_type: DamageType) -> &StatValue {
         match damage_type {
-            DamageType::Physical => self.global_physical_damage.compute().max(1.0),
-            DamageType::Fire => self.global_fire_damage.compute().max(1.0),
-            DamageType::Cold => self.global_cold_damage.compute().max(1.0),
-            DamageType::Lightning => self.global_lightning_damage.compute().max(1.0),
-            DamageType::Chaos => self.global_chaos_damage.compute().max(1.0),
+            DamageType::Physical => &self.global_physical_damage,
+            DamageType::Fire => &self.global_fire_damage,
+            DamageType::Cold => &self.global_cold_damage,
+            DamageType::Lightning => &self.global_lightning_damage,
+            DamageType::Chaos => &self.global_chaos_damage,
         }
     }
 
+    /// Get the global damage stat for a damage type, mutably
+    pub fn global_damage_mut(&mut self, damage_type: DamageType) -> &mut StatValue {
+        match damage_type {
+            DamageType::Physical => &mut self.global_physical_damage,
+            DamageType::Fire => &mut self.global_fire_damage,
+            DamageType::Cold => &mut self.global_cold_damage,
+            DamageType::Lightning => &mut self.global_lightning_damage,
+            DamageType::Chaos => &mut self.global_chaos_damage,
+        }
+    }
+
+    /// Get the damage scaling multiplier for a specific damage type
+    pub fn damage_multiplier(&self, damage_type: DamageType) -> f64 {
+        self.global_damage(damage_type).compute().max(1.0)
+    }
+
     /// Get the resistance value for a damage type (uncapped)
+    ///
+    /// Physical has no resistance; it returns the flat physical damage
+    /// reduction percent, the part of physical mitigation that does not
+    /// depend on hit size the way armour does.
     pub fn resistance(&self, damage_type: DamageType) -> f64 {
         match damage_type {
-            DamageType::Physical => 0.0, // Physical uses armour, not resistance
+            DamageType::Physical => self.physical_damage_reduction,
             DamageType::Fire => self.fire_resistance.compute(),
             DamageType::Cold => self.cold_resistance.compute(),
             DamageType::Lightning => self.lightning_resistance.compute(),
@@ -26,10 +50,32 @@ impl StatBlock {
         }
     }
 
+    /// Get the maximum resistance for a damage type
+    ///
+    /// Per-element maximums default to 75% and can be raised by gear; only
+    /// the absolute `MAX_RESISTANCE` (100%) limits them.
+    pub fn max_resistance(&self, damage_type: DamageType) -> f64 {
+        let max = match damage_type {
+            DamageType::Physical => MAX_RESISTANCE,
+            DamageType::Fire => self.max_fire_resistance,
+            DamageType::Cold => self.max_cold_resistance,
+            DamageType::Lightning => self.max_lightning_resistance,
+            DamageType::Chaos => self.max_chaos_resistance,
+        };
+        max.min(MAX_RESISTANCE)
+    }
+
+    /// Get the resistance after reduction applied by enemies (uncapped)
+    pub fn effective_resistance(&self, damage_type: DamageType) -> f64 {
+        self.resistance(damage_type) - self.resistance_reduction.get(damage_type)
+    }
+
     /// Get the penetration value for a damage type
+    ///
+    /// Physical returns overwhelm, which ignores physical damage reduction.
     pub fn penetration(&self, damage_type: DamageType) -> f64 {
         match damage_type {
-            DamageType::Physical => 0.0, // Physical doesn't have penetration
+            DamageType::Physical => self.overwhelm,
             DamageType::Fire => self.fire_penetration.compute(),
             DamageType::Cold => self.cold_penetration.compute(),
             DamageType::Lightning => self.lightning_penetration.compute(),
@@ -37,14 +83,129 @@ impl StatBlock {
         }
     }
 
-    /// Get computed attack speed
+    /// Get the fraction of resistance ignored for a damage type
+    pub fn penetration_percent(&self, damage_type: DamageType) -> f64 {
+        match damage_type {
+            DamageType::Physical => 0.0,
+            DamageType::Fire => self.fire_penetration_percent,
+            DamageType::Cold => self.cold_penetration_percent,
+            DamageType::Lightning => self.lightning_penetration_percent,
+            DamageType::Chaos => self.chaos_penetration_percent,
+        }
+    }
+
+    /// Get block chance in percent, clamped to 0..=MAX_BLOCK_CHANCE
+    pub fn computed_block_chance(&self) -> f64 {
+        self.block_chance.compute().clamp(0.0, MAX_BLOCK_CHANCE)
+    }
+
+    /// Get increased damage taken from active shocks (0.20 = 20%), before any cap
+    ///
+    /// Sums `Effect::total_magnitude` over every active Static effect; each
+    /// effect's stacks are already limited by its `max_stacks`.
+    pub fn shock_effect(&self) -> f64 {
+        self.effects
+            .iter()
+            .filter(|effect| effect.is_active() && effect.status() == Some(StatusEffect::Static))
+            .map(Effect::total_magnitude)
+            .sum::<f64>()
+            .max(0.0)
+    }
+
+    /// Get the multiplier resistance applies to DoT damage of a type
+    ///
+    /// Uses the resistance after enemy reduction, capped at the type's
+    /// maximum; negative resistance increases damage. Physical DoTs (bleed)
+    /// are not mitigated, since armour only applies to hits.
+    pub fn dot_resistance_multiplier(&self, damage_type: DamageType) -> f64 {
+        if damage_type == DamageType::Physical {
+            return 1.0;
+        }
+        calculate_resistance_mitigation_with_cap(
+            1.0,
+            self.effective_resistance(damage_type),
+            0.0,
+            self.max_resistance(damage_type),
+        )
+    }
+
+    /// Get the multiplier applied to all incoming damage, hits and DoTs
+    pub fn damage_taken_multiplier(&self) -> f64 {
+        (1.0 - self.damage_taken_reduction).max(0.0)
+    }
+
+    /// Get the multiplier applied to incoming DoT damage
+    ///
+    /// Combines DoT-specific reduction with the general damage taken reduction.
+    pub fn dot_damage_taken_multiplier(&self) -> f64 {
+        (1.0 - self.dot_damage_taken_reduced).max(0.0) * self.damage_taken_multiplier()
+    }
+
+    /// Get action speed reduction from the strongest active Chill or Slow (0.30 = 30% slower)
+    pub fn action_speed_reduction(&self) -> f64 {
+        let is_slowing =
+            |status: StatusEffect| matches!(status, StatusEffect::Chill | StatusEffect::Slow);
+
+        self.effects
+            .iter()
+            .filter(|effect| effect.is_active())
+            .filter_map(|effect| match &effect.effect_type {
+                EffectType::Ailment {
+                    status, magnitude, ..
+                } if is_slowing(*status) => Some(*magnitude),
+                _ => None,
+            })
+            .fold(0.0, f64::max)
+            .clamp(0.0, 1.0)
+    }
+
+    /// Check if an active ailment of the given type is on this entity
+    pub fn has_active_ailment(&self, status: StatusEffect) -> bool {
+        self.effects
+            .iter()
+            .any(|effect| effect.is_active() && effect.status() == Some(status))
+    }
+
+    /// Check if this entity is frozen
+    pub fn is_frozen(&self) -> bool {
+        self.has_active_ailment(StatusEffect::Freeze)
+    }
+
+    /// Check if this entity is feared
+    pub fn is_feared(&self) -> bool {
+        self.has_active_ailment(StatusEffect::Fear)
+    }
+
+    /// Check if this entity can take actions (alive, not frozen or feared)
+    pub fn can_act(&self) -> bool {
+        self.is_alive() && !self.is_frozen() && !self.is_feared()
+    }
+
+    /// Get computed attack speed (reduced by chill/slow)
     pub fn computed_attack_speed(&self) -> f64 {
-        self.attack_speed.compute() * self.weapon_attack_speed
+        self.attack_speed.compute()
+            * self.weapon_attack_speed
+            * (1.0 - self.action_speed_reduction())
     }
 
-    /// Get computed cast speed
+    /// Get computed cast speed (reduced by chill/slow)
     pub fn computed_cast_speed(&self) -> f64 {
-        self.cast_speed.compute()
+        self.cast_speed.compute() * (1.0 - self.action_speed_reduction())
+    }
+
+    /// Get the speed a skill is used at, including the skill's speed modifier
+    ///
+    /// Spells use cast speed and attacks use attack speed. Skills with
+    /// neither tag fall back to cast speed.
+    pub fn computed_skill_speed(&self, skill: &DamagePacketGenerator) -> f64 {
+        let speed = if skill.is_spell() {
+            self.computed_cast_speed()
+        } else if skill.is_attack() {
+            self.computed_attack_speed()
+        } else {
+            self.computed_cast_speed()
+        };
+        speed * skill.attack_speed_modifier
     }
 
     /// Get computed critical strike chance for attacks
@@ -60,6 +221,24 @@ impl StatBlock {
         self.critical_multiplier.compute()
     }
 
+    /// Total increased damage from tag-scoped modifiers matching any of `tags`
+    ///
+    /// Added to each damage type's increased, like `spell_damage_increased`.
+    pub fn tag_damage_increased_for(&self, tags: &[SkillTag]) -> f64 {
+        tags.iter()
+            .filter_map(|tag| self.tag_damage_increased.get(tag))
+            .sum()
+    }
+
+    /// Product of the tag-scoped more multipliers matching any of `tags`
+    pub fn tag_damage_multiplier(&self, tags: &[SkillTag]) -> f64 {
+        tags.iter()
+            .filter_map(|tag| self.tag_damage_more.get(tag))
+            .flatten()
+            .map(|more| 1.0 + more)
+            .product()
+    }
+
     /// Get weapon damage range for a damage type
     pub fn weapon_damage(&self, damage_type: DamageType) -> (f64, f64) {
         match damage_type {
@@ -83,36 +262,44 @@ impl StatBlock {
         total_avg * self.weapon_attack_speed
     }
 
-    /// Calculate life percentage remaining
-    pub fn life_percent(&self) -> f64 {
-        let max = self.computed_max_life();
+    /// Get a resource's `(current, max)` values
+    pub fn resource(&self, kind: ResourceKind) -> (f64, f64) {
+        match kind {
+            ResourceKind::Life => (self.current_life, self.computed_max_life()),
+            ResourceKind::Mana => (self.current_mana, self.computed_max_mana()),
+            ResourceKind::EnergyShield => (self.current_energy_shield, self.max_energy_shield),
+        }
+    }
+
+    /// Calculate a resource's percentage remaining (0 when it has no maximum)
+    pub fn resource_percent(&self, kind: ResourceKind) -> f64 {
+        let (current, max) = self.resource(kind);
         if max <= 0.0 {
             return 0.0;
         }
-        (self.current_life / max * 100.0).clamp(0.0, 100.0)
+        (current / max * 100.0).clamp(0.0, 100.0)
+    }
+
+    /// Calculate life percentage remaining
+    pub fn life_percent(&self) -> f64 {
+        self.resource_percent(ResourceKind::Life)
     }
 
     /// Calculate mana percentage remaining
     pub fn mana_percent(&self) -> f64 {
-        let max = self.computed_max_mana();
-        if max <= 0.0 {
-            return 0.0;
-        }
-        (self.current_mana / max * 100.0).clamp(0.0, 100.0)
+        self.resource_percent(ResourceKind::Mana)
     }
 
     /// Calculate ES percentage remaining
     pub fn energy_shield_percent(&self) -> f64 {
-        if self.max_energy_shield <= 0.0 {
-            return 0.0;
-        }
-        (self.current_energy_shield / self.max_energy_shield * 100.0).clamp(0.0, 100.0)
+        self.resource_percent(ResourceKind::EnergyShield)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::ALL_DAMAGE_TYPES;
 
     #[test]
     fn test_damage_multiplier_default() {
@@ -121,6 +308,28 @@ mod tests {
         assert!((block.damage_multiplier(DamageType::Physical) - 1.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_global_damage_accessor_per_type() {
+        let mut block = StatBlock::new();
+        for (i, damage_type) in ALL_DAMAGE_TYPES.into_iter().enumerate() {
+            block
+                .global_damage_mut(damage_type)
+                .add_flat(i as f64 + 1.0);
+        }
+
+        assert!((block.global_physical_damage.compute() - 1.0).abs() < f64::EPSILON);
+        assert!((block.global_fire_damage.compute() - 2.0).abs() < f64::EPSILON);
+        assert!((block.global_cold_damage.compute() - 3.0).abs() < f64::EPSILON);
+        assert!((block.global_lightning_damage.compute() - 4.0).abs() < f64::EPSILON);
+        assert!((block.global_chaos_damage.compute() - 5.0).abs() < f64::EPSILON);
+        for (i, damage_type) in ALL_DAMAGE_TYPES.into_iter().enumerate() {
+            assert!(
+                (block.global_damage(damage_type).compute() - (i as f64 + 1.0)).abs()
+                    < f64::EPSILON
+            );
+        }
+    }
+
     #[test]
     fn test_life_percent() {
         let mut block = StatBlock::new();
@@ -129,6 +338,39 @@ mod tests {
         assert!((block.life_percent() - 50.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_resource_percent_at_zero_half_and_full() {
+        let mut block = StatBlock::new();
+        block.max_life.base = 100.0;
+        block.max_mana.base = 60.0;
+        block.max_energy_shield = 40.0;
+
+        for (fraction, expected) in [(0.0, 0.0), (0.5, 50.0), (1.0, 100.0)] {
+            block.current_life = 100.0 * fraction;
+            block.current_mana = 60.0 * fraction;
+            block.current_energy_shield = 40.0 * fraction;
+            for kind in ResourceKind::ALL {
+                assert!(
+                    (block.resource_percent(kind) - expected).abs() < 0.01,
+                    "{:?} at {}",
+                    kind,
+                    fraction
+                );
+            }
+            assert!((block.life_percent() - expected).abs() < 0.01);
+            assert!((block.mana_percent() - expected).abs() < 0.01);
+            assert!((block.energy_shield_percent() - expected).abs() < 0.01);
+        }
+
+        let (current, max) = block.resource(ResourceKind::Mana);
+        assert!((current - 60.0).abs() < f64::EPSILON);
+        assert!((max - 60.0).abs() < f64::EPSILON);
+
+        // No maximum: 0% rather than NaN
+        block.max_energy_shield = 0.0;
+        assert!(block.resource_percent(ResourceKind::EnergyShield).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn test_weapon_dps() {
         let mut block = StatBlock::new();
@@ -138,4 +380,90 @@ mod tests {
         // Average: 15, DPS: 15 * 1.5 = 22.5
         assert!((block.weapon_dps() - 22.5).abs() < 0.01);
     }
+
+    #[test]
+    fn test_freeze_prevents_acting_until_it_expires() {
+        let mut block = StatBlock::new();
+        assert!(block.can_act());
+
+        block.add_effect(crate::types::Effect::freeze(1.0, "attacker"));
+        assert!(block.is_frozen());
+        assert!(!block.is_feared());
+        assert!(!block.can_act());
+
+        // Freeze lasts 0.5 seconds
+        let (block, _) = block.tick_effects(0.6);
+        assert!(!block.is_frozen());
+        assert!(block.can_act());
+    }
+
+    #[test]
+    fn test_chill_slows_attack_and_cast_speed() {
+        let mut block = StatBlock::new();
+        block.weapon_attack_speed = 2.0;
+        assert!((block.computed_attack_speed() - 2.0).abs() < 0.001);
+
+        // The strongest chill applies
+        block.add_effect(crate::types::Effect::chill(0.30, "attacker"));
+        block.add_effect(crate::types::Effect::chill(0.10, "attacker"));
+        assert!((block.computed_attack_speed() - 1.4).abs() < 0.001);
+        assert!((block.computed_cast_speed() - 0.7).abs() < 0.001);
+
+        // Chill lasts 2 seconds
+        let (block, _) = block.tick_effects(2.5);
+        assert!((block.computed_attack_speed() - 2.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_max_resistance_defaults_and_raises() {
+        let mut block = StatBlock::new();
+        block.fire_resistance.base = 80.0;
+
+        // Capped at the default 75%
+        assert!((block.max_resistance(DamageType::Fire) - 75.0).abs() < f64::EPSILON);
+        assert!((block.dot_resistance_multiplier(DamageType::Fire) - 0.25).abs() < 0.001);
+
+        // +15% maximum fire resistance from gear: 80% now mitigates 80%
+        block.max_fire_resistance += 15.0;
+        assert!((block.max_resistance(DamageType::Fire) - 90.0).abs() < f64::EPSILON);
+        assert!((block.dot_resistance_multiplier(DamageType::Fire) - 0.20).abs() < 0.001);
+
+        // Never above the absolute cap
+        block.max_fire_resistance = 150.0;
+        assert!((block.max_resistance(DamageType::Fire) - MAX_RESISTANCE).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_resistance_and_penetration_per_damage_type() {
+        let mut block = StatBlock::new();
+        block.physical_damage_reduction = 15.0;
+        block.fire_resistance.base = 10.0;
+        block.cold_resistance.base = 20.0;
+        block.lightning_resistance.base = 30.0;
+        block.chaos_resistance.base = -40.0;
+        block.overwhelm = 5.0;
+        block.fire_penetration.base = 1.0;
+        block.cold_penetration.base = 2.0;
+        block.lightning_penetration.base = 3.0;
+        block.chaos_penetration.base = 4.0;
+        block.fire_penetration_percent = 0.1;
+        block.cold_penetration_percent = 0.2;
+        block.lightning_penetration_percent = 0.3;
+        block.chaos_penetration_percent = 0.4;
+
+        let expected = [
+            (DamageType::Physical, 15.0, 5.0, 0.0),
+            (DamageType::Fire, 10.0, 1.0, 0.1),
+            (DamageType::Cold, 20.0, 2.0, 0.2),
+            (DamageType::Lightning, 30.0, 3.0, 0.3),
+            (DamageType::Chaos, -40.0, 4.0, 0.4),
+        ];
+        for (damage_type, resistance, penetration, penetration_percent) in expected {
+            assert!((block.resistance(damage_type) - resistance).abs() < f64::EPSILON);
+            assert!((block.penetration(damage_type) - penetration).abs() < f64::EPSILON);
+            assert!(
+                (block.penetration_percent(damage_type) - penetration_percent).abs() < f64::EPSILON
+            );
+        }
+    }
 }
@@ -0,0 +1,168 @@
+//! Structured comparison of two stat blocks (e.g. equip previews)
+
+use crate::stat_block::StatBlock;
+use serde::{Deserialize, Serialize};
+
+/// A single stat that differs between two blocks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatDiff {
+    /// Stable snake_case stat name (e.g. "fire_resistance")
+    pub name: String,
+    /// Value on the block `diff` was called on
+    pub before: f64,
+    /// Value on the block passed to `diff`
+    pub after: f64,
+}
+
+impl StatDiff {
+    /// Change from `before` to `after`
+    pub fn delta(&self) -> f64 {
+        self.after - self.before
+    }
+}
+
+impl StatBlock {
+    /// Compare every comparable computed stat against `other`
+    ///
+    /// Returns only the stats that changed, in a fixed order (resources,
+    /// attributes, defenses, offense, penetration, recovery, weapon, utility).
+    /// Current life/mana/ES and active effects are not compared.
+    pub fn diff(&self, other: &StatBlock) -> Vec<StatDiff> {
+        self.comparable_stats()
+            .into_iter()
+            .zip(other.comparable_stats())
+            .filter(|((_, before), (_, after))| (after - before).abs() > f64::EPSILON)
+            .map(|((name, before), (_, after))| StatDiff {
+                name: name.to_string(),
+                before,
+                after,
+            })
+            .collect()
+    }
+
+    fn comparable_stats(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            // Resources
+            ("max_life", self.computed_max_life()),
+            ("max_mana", self.computed_max_mana()),
+            ("max_energy_shield", self.max_energy_shield),
+            ("mana_reservation", self.mana_reservation),
+            // Attributes
+            ("strength", self.strength.compute()),
+            ("dexterity", self.dexterity.compute()),
+            ("intelligence", self.intelligence.compute()),
+            ("constitution", self.constitution.compute()),
+            ("wisdom", self.wisdom.compute()),
+            ("charisma", self.charisma.compute()),
+            // Defenses
+            ("armour", self.armour.compute()),
+            ("evasion", self.evasion.compute()),
+            ("fire_resistance", self.fire_resistance.compute()),
+            ("cold_resistance", self.cold_resistance.compute()),
+            ("lightning_resistance", self.lightning_resistance.compute()),
+            ("chaos_resistance", self.chaos_resistance.compute()),
+            ("max_fire_resistance", self.max_fire_resistance),
+            ("max_cold_resistance", self.max_cold_resistance),
+            ("max_lightning_resistance", self.max_lightning_resistance),
+            ("max_chaos_resistance", self.max_chaos_resistance),
+            ("block_chance", self.computed_block_chance()),
+            ("physical_damage_reduction", self.physical_damage_reduction),
+            ("damage_taken_reduction", self.damage_taken_reduction),
+            ("dot_damage_taken_reduced", self.dot_damage_taken_reduced),
+            ("physical_damage_reflected", self.physical_damage_reflected),
+            ("ailment_threshold", self.ailment_threshold),
+            // Offense
+            ("accuracy", self.accuracy.compute()),
+            (
+                "global_physical_damage",
+                self.global_physical_damage.compute(),
+            ),
+            ("global_fire_damage", self.global_fire_damage.compute()),
+            ("global_cold_damage", self.global_cold_damage.compute()),
+            (
+                "global_lightning_damage",
+                self.global_lightning_damage.compute(),
+            ),
+            ("global_chaos_damage", self.global_chaos_damage.compute()),
+            ("attack_speed", self.computed_attack_speed()),
+            ("cast_speed", self.computed_cast_speed()),
+            ("spell_damage_increased", self.spell_damage_increased),
+            ("effect_duration_increased", self.effect_duration_increased),
+            ("critical_chance", self.computed_attack_crit_chance()),
+            ("critical_multiplier", self.computed_crit_multiplier()),
+            ("overwhelm", self.overwhelm),
+            ("culling_strike_threshold", self.culling_strike_threshold),
+            // Penetration
+            ("fire_penetration", self.fire_penetration.compute()),
+            ("cold_penetration", self.cold_penetration.compute()),
+            (
+                "lightning_penetration",
+                self.lightning_penetration.compute(),
+            ),
+            ("chaos_penetration", self.chaos_penetration.compute()),
+            ("fire_penetration_percent", self.fire_penetration_percent),
+            ("cold_penetration_percent", self.cold_penetration_percent),
+            (
+                "lightning_penetration_percent",
+                self.lightning_penetration_percent,
+            ),
+            ("chaos_penetration_percent", self.chaos_penetration_percent),
+            // Recovery
+            ("life_regen", self.life_regen.compute()),
+            ("mana_regen", self.mana_regen.compute()),
+            ("life_leech", self.life_leech.compute()),
+            ("mana_leech", self.mana_leech.compute()),
+            ("life_on_hit", self.life_on_hit),
+            ("es_regen_from_life_regen", self.es_regen_from_life_regen),
+            // Weapon
+            ("weapon_physical_min", self.weapon_physical_min),
+            ("weapon_physical_max", self.weapon_physical_max),
+            ("weapon_fire_min", self.weapon_fire_min),
+            ("weapon_fire_max", self.weapon_fire_max),
+            ("weapon_cold_min", self.weapon_cold_min),
+            ("weapon_cold_max", self.weapon_cold_max),
+            ("weapon_lightning_min", self.weapon_lightning_min),
+            ("weapon_lightning_max", self.weapon_lightning_max),
+            ("weapon_chaos_min", self.weapon_chaos_min),
+            ("weapon_chaos_max", self.weapon_chaos_max),
+            ("weapon_attack_speed", self.weapon_attack_speed),
+            ("weapon_crit_chance", self.weapon_crit_chance),
+            ("weapon_dps", self.weapon_dps()),
+            // Utility
+            (
+                "movement_speed_increased",
+                self.movement_speed_increased.compute(),
+            ),
+            ("item_rarity_increased", self.item_rarity_increased),
+            ("item_quantity_increased", self.item_quantity_increased),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_against_self_is_empty() {
+        let mut block = StatBlock::new();
+        block.fire_resistance.base = 40.0;
+        block.armour.base = 300.0;
+        assert!(block.diff(&block.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_changed_stats_in_order() {
+        let before = StatBlock::new();
+        let mut after = before.clone();
+        after.max_life.base += 50.0;
+        after.chaos_resistance.base = 20.0;
+
+        let diff = before.diff(&after);
+        let names: Vec<&str> = diff.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["max_life", "chaos_resistance"]);
+        assert!((diff[0].delta() - 50.0).abs() < 0.01);
+        assert!((diff[1].before - 0.0).abs() < f64::EPSILON);
+        assert!((diff[1].after - 20.0).abs() < f64::EPSILON);
+    }
+}
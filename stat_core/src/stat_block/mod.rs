@@ -1,22 +1,81 @@
 //! StatBlock - Aggregated character stats from all sources
 
 mod aggregator;
+mod breakdown;
+mod builder;
 mod computed;
+mod diff;
+mod score;
 mod stat_value;
 
-pub use aggregator::{StatAccumulator, StatusConversions, StatusEffectStats};
+pub use aggregator::{
+    StatAccumulator, StatusConversions, StatusEffectStats, WeaponStats,
+    DUAL_WIELD_ATTACK_SPEED_BONUS,
+};
+pub use breakdown::{StatBreakdown, StatContribution};
+pub use builder::StatBlockBuilder;
+pub use diff::StatDiff;
 pub use stat_value::StatValue;
 
 use crate::combat::CombatResult;
-use crate::damage::{calculate_damage, DamagePacket, DamagePacketGenerator};
-use crate::dot::ActiveDoT;
-use crate::combat::resolve_damage;
-use crate::source::{BuffSource, GearSource, StatSource};
-use crate::types::{ActiveBuff, ActiveStatusEffect, AilmentStacking, Effect, EffectType, EquipmentSlot, TickResult};
+use crate::config::{AttributeConstants, ConfigError, GameConstants};
+use crate::damage::{
+    calculate_damage_with_registry, DamageConversions, DamagePacket, DamagePacketGenerator,
+    GainAsExtra,
+};
+use crate::defense::constants::DEFAULT_MAX_RESISTANCE;
+use crate::defense::TakenAsConversions;
+use crate::dot::{status_for_id, ActiveDoT, DotRegistry};
+use crate::effect::apply_ailment_from;
+use crate::source::{
+    AuraSource, BuffSource, BuffStacking, FlaskSource, GearSource, SourceContext, StatSource,
+};
+use crate::types::{
+    is_two_handed, ActiveBuff, ActiveStatusEffect, AilmentImmunities, Effect, EffectType,
+    EquipError, EquipmentSlot, EvasionMode, RequirementMode, RequirementShortfall,
+    ResistanceReduction, SkillError, SkillTag, TickResult,
+};
 use loot_core::types::{DamageType, StatusEffect};
 use loot_core::Item;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
+
+fn default_max_resistance() -> f64 {
+    DEFAULT_MAX_RESISTANCE
+}
+
+fn default_movement_speed() -> StatValue {
+    StatValue::default().clamp_range(None, Some(MAX_MOVEMENT_SPEED_INCREASED))
+}
+
+/// Accept saves from before movement speed was a `StatValue`, when it was a plain number
+fn deserialize_movement_speed<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<StatValue, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum MovementSpeed {
+        Stat(StatValue),
+        Legacy(f64),
+    }
+
+    Ok(match MovementSpeed::deserialize(deserializer)? {
+        MovementSpeed::Stat(stat) => stat,
+        MovementSpeed::Legacy(increased) => {
+            let mut stat = default_movement_speed();
+            stat.add_flat(increased);
+            stat
+        }
+    })
+}
+
+/// Floor on the attack and cast speed multipliers, so action timing never divides by ~0
+pub const MIN_ACTION_SPEED: f64 = 0.1;
+
+/// Cap on increased movement speed (1.0 = 100% increased)
+pub const MAX_MOVEMENT_SPEED_INCREASED: f64 = 1.0;
 
 /// Complete stat state for an entity (player, monster, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,19 +89,63 @@ pub struct StatBlock {
     #[serde(default)]
     equipped_items: HashMap<EquipmentSlot, Item>,
 
+    /// Persistent buffs granted by equipped items, keyed by slot
+    #[serde(default)]
+    item_buffs: HashMap<EquipmentSlot, BuffSource>,
+
+    /// Cached per-slot gear contributions, so equipping one slot
+    /// does not reapply every other item
+    #[serde(skip)]
+    gear_cache: HashMap<EquipmentSlot, StatAccumulator>,
+
+    /// How unmet item attribute requirements are handled on rebuild
+    #[serde(default)]
+    pub requirement_mode: RequirementMode,
+
+    /// Whether evasion caps hit damage or gives a chance to evade
+    #[serde(default)]
+    pub evasion_mode: EvasionMode,
+
+    /// Ratios used to derive stats from attributes on rebuild
+    /// (copy `GameConstants::attributes` here to tune)
+    #[serde(default)]
+    pub attribute_constants: AttributeConstants,
+
     // === Buff Sources ===
     /// Active buff sources for stat calculation
-    #[serde(skip)]
+    #[serde(default)]
     buff_sources: Vec<BuffSource>,
 
+    // === Auras ===
+    /// Active auras keyed by the skill that toggled them
+    #[serde(default)]
+    active_auras: HashMap<String, AuraSource>,
+
+    // === Flasks ===
+    /// Flask effects currently running
+    #[serde(skip)]
+    active_flasks: Vec<FlaskSource>,
+
     // === Resources ===
     pub max_life: StatValue,
     pub current_life: f64,
     pub max_mana: StatValue,
     pub current_mana: f64,
+    /// Fraction of maximum mana reserved by active auras (0.25 = 25%)
+    #[serde(default)]
+    pub mana_reservation: f64,
     /// Maximum energy shield from warding spells (does NOT passively regenerate)
     pub max_energy_shield: f64,
     pub current_energy_shield: f64,
+    /// Temporary absorption from guard skills (consumed before ward)
+    #[serde(default)]
+    pub current_guard: f64,
+    /// Ward barrier (consumed after guard, before energy shield)
+    #[serde(default)]
+    pub current_ward: f64,
+    /// Seconds until energy shield starts recharging (reset when ES absorbs damage)
+    #[serde(default)]
+    pub es_recharge_delay_remaining: f64,
 
     // === Attributes ===
     pub strength: StatValue,
@@ -60,6 +163,50 @@ pub struct StatBlock {
     pub cold_resistance: StatValue,
     pub lightning_resistance: StatValue,
     pub chaos_resistance: StatValue,
+    /// Reduced damage taken from DoTs, applied on top of other mitigation (0.30 = 30% reduced)
+    #[serde(default)]
+    pub dot_damage_taken_reduced: f64,
+    /// Less damage taken from all sources, hits and DoTs alike (0.10 = 10% less)
+    ///
+    /// Armour only mitigates hits; this layer also reduces ailment and DoT
+    /// damage.
+    #[serde(default)]
+    pub damage_taken_reduction: f64,
+    /// Maximum resistance per element
+    /// (75% by default; never above `defense::constants::MAX_RESISTANCE`)
+    #[serde(default = "default_max_resistance")]
+    pub max_fire_resistance: f64,
+    #[serde(default = "default_max_resistance")]
+    pub max_cold_resistance: f64,
+    #[serde(default = "default_max_resistance")]
+    pub max_lightning_resistance: f64,
+    #[serde(default = "default_max_resistance")]
+    pub max_chaos_resistance: f64,
+    /// Resistance reduction currently applied to this entity by enemies
+    #[serde(default)]
+    pub resistance_reduction: ResistanceReduction,
+    /// Seconds until `resistance_reduction` wears off
+    #[serde(default)]
+    pub resistance_reduction_remaining: f64,
+    /// Physical damage reduction on top of armour, in percentage points (20.0 = 20%)
+    #[serde(default)]
+    pub physical_damage_reduction: f64,
+    /// Incoming hit damage taken as other types, before mitigation
+    #[serde(default)]
+    pub taken_as: TakenAsConversions,
+    /// Chance to block a hit entirely, in percent (capped by `computed_block_chance`)
+    #[serde(default)]
+    pub block_chance: StatValue,
+    /// Percent of physical hit damage taken reflected to the attacker (10.0 = 10%)
+    #[serde(default)]
+    pub physical_damage_reflected: f64,
+    /// Ailments this entity cannot be afflicted by
+    #[serde(default)]
+    pub ailment_immunities: AilmentImmunities,
+    /// Minimum status damage, as a fraction of max life, for an ailment to
+    /// apply (0.05 = 5%); smaller applications fizzle
+    #[serde(default)]
+    pub ailment_threshold: f64,
 
     // === Offense (Global) ===
     /// Accuracy rating - determines damage cap against evasion
@@ -69,8 +216,26 @@ pub struct StatBlock {
     pub global_cold_damage: StatValue,
     pub global_lightning_damage: StatValue,
     pub global_chaos_damage: StatValue,
+    /// Damage conversions from gear and passives, combined with each skill's own
+    #[serde(default)]
+    pub damage_conversions: DamageConversions,
+    /// Extra damage gained from gear and passives, combined with each skill's own
+    #[serde(default)]
+    pub gain_as_extra: GainAsExtra,
     pub attack_speed: StatValue,
     pub cast_speed: StatValue,
+    /// Increased damage for spells only, added to each type's increased (0.40 = 40%)
+    #[serde(default)]
+    pub spell_damage_increased: f64,
+    /// Increased duration of all status effects, added to each effect's own (0.50 = 50%)
+    #[serde(default)]
+    pub effect_duration_increased: f64,
+    /// Increased damage for skills with a tag, added to each type's increased (0.20 = 20%)
+    #[serde(default)]
+    pub tag_damage_increased: HashMap<SkillTag, f64>,
+    /// More damage multipliers for skills with a tag (0.10 = 10% more)
+    #[serde(default)]
+    pub tag_damage_more: HashMap<SkillTag, Vec<f64>>,
     pub critical_chance: StatValue,
     pub critical_multiplier: StatValue,
 
@@ -79,15 +244,56 @@ pub struct StatBlock {
     pub cold_penetration: StatValue,
     pub lightning_penetration: StatValue,
     pub chaos_penetration: StatValue,
+    /// Fraction of enemy resistance ignored, applied before flat penetration (0.25 = 25%)
+    #[serde(default)]
+    pub fire_penetration_percent: f64,
+    #[serde(default)]
+    pub cold_penetration_percent: f64,
+    #[serde(default)]
+    pub lightning_penetration_percent: f64,
+    #[serde(default)]
+    pub chaos_penetration_percent: f64,
+    /// Resistance reduction this entity applies to enemies it hits
+    #[serde(default)]
+    pub enemy_resistance_reduction: ResistanceReduction,
+    /// Percentage points of enemy physical damage reduction ignored
+    #[serde(default)]
+    pub overwhelm: f64,
+    /// Enemies left below this fraction of max life by a hit die (0.1 = 10%, 0 = off)
+    #[serde(default)]
+    pub culling_strike_threshold: f64,
 
     // === Recovery ===
     pub life_regen: StatValue,
     pub mana_regen: StatValue,
     pub life_leech: StatValue,
     pub mana_leech: StatValue,
+    /// Maximum mana leeched per second as a fraction of max mana (0.20 = 20%, 0 = uncapped)
+    ///
+    /// While capped, leeched mana is queued and restored by `tick_regen`
+    /// (copy `LeechConstants::max_mana_leech_rate` here to use the game default).
+    #[serde(default)]
+    pub max_mana_leech_rate: f64,
+    /// Leeched mana waiting to be restored under `max_mana_leech_rate`
+    #[serde(default)]
+    pub pending_mana_leech: f64,
+    /// Flat life gained for each hit that deals damage
+    #[serde(default)]
+    pub life_on_hit: f64,
+    /// Fraction of life regeneration also applied to energy shield (0.5 = 50%)
+    #[serde(default)]
+    pub es_regen_from_life_regen: f64,
 
     // === Utility ===
-    pub movement_speed_increased: f64,
+    /// Increased movement speed (clamped to at most `MAX_MOVEMENT_SPEED_INCREASED`)
+    #[serde(
+        default = "default_movement_speed",
+        deserialize_with = "deserialize_movement_speed"
+    )]
+    pub movement_speed_increased: StatValue,
+    /// Whether the entity is moving (DoTs tick with their config's `moving_multiplier`)
+    #[serde(default)]
+    pub is_moving: bool,
     pub item_rarity_increased: f64,
     pub item_quantity_increased: f64,
 
@@ -97,10 +303,14 @@ pub struct StatBlock {
     pub effects: Vec<Effect>,
 
     // === Legacy Active Effects (kept for backwards compatibility during transition) ===
+    /// Deprecated: DoTs now live in `effects`. Only read by
+    /// `migrate_legacy_effects`, which `tick_effects` runs first.
     #[serde(default)]
     pub active_dots: Vec<ActiveDoT>,
     #[serde(default)]
     pub active_buffs: Vec<ActiveBuff>,
+    /// Deprecated: status effects now live in `effects`. Only read by
+    /// `migrate_legacy_effects`, which `tick_effects` runs first.
     #[serde(default)]
     pub active_status_effects: Vec<ActiveStatusEffect>,
 
@@ -239,17 +449,32 @@ impl StatBlock {
 
             // Equipment
             equipped_items: HashMap::new(),
+            item_buffs: HashMap::new(),
+            gear_cache: HashMap::new(),
+            requirement_mode: RequirementMode::default(),
+            evasion_mode: EvasionMode::default(),
+            attribute_constants: AttributeConstants::default(),
 
             // Buff sources
             buff_sources: Vec::new(),
 
+            // Auras
+            active_auras: HashMap::new(),
+
+            // Flasks
+            active_flasks: Vec::new(),
+
             // Resources
             max_life: StatValue::with_base(50.0),
             current_life: 50.0,
             max_mana: StatValue::with_base(40.0),
             current_mana: 40.0,
+            mana_reservation: 0.0,
             max_energy_shield: 0.0,
             current_energy_shield: 0.0,
+            current_guard: 0.0,
+            current_ward: 0.0,
+            es_recharge_delay_remaining: 0.0,
 
             // Attributes
             strength: StatValue::with_base(10.0),
@@ -266,6 +491,20 @@ impl StatBlock {
             cold_resistance: StatValue::default(),
             lightning_resistance: StatValue::default(),
             chaos_resistance: StatValue::default(),
+            dot_damage_taken_reduced: 0.0,
+            damage_taken_reduction: 0.0,
+            max_fire_resistance: DEFAULT_MAX_RESISTANCE,
+            max_cold_resistance: DEFAULT_MAX_RESISTANCE,
+            max_lightning_resistance: DEFAULT_MAX_RESISTANCE,
+            max_chaos_resistance: DEFAULT_MAX_RESISTANCE,
+            resistance_reduction: ResistanceReduction::default(),
+            resistance_reduction_remaining: 0.0,
+            physical_damage_reduction: 0.0,
+            taken_as: TakenAsConversions::default(),
+            block_chance: StatValue::default(),
+            physical_damage_reflected: 0.0,
+            ailment_immunities: AilmentImmunities::default(),
+            ailment_threshold: 0.0,
 
             // Offense
             accuracy: StatValue::with_base(1000.0), // Base accuracy
@@ -274,8 +513,14 @@ impl StatBlock {
             global_cold_damage: StatValue::default(),
             global_lightning_damage: StatValue::default(),
             global_chaos_damage: StatValue::default(),
-            attack_speed: StatValue::with_base(1.0),
-            cast_speed: StatValue::with_base(1.0),
+            damage_conversions: DamageConversions::default(),
+            gain_as_extra: GainAsExtra::default(),
+            attack_speed: StatValue::with_base(1.0).clamp_range(Some(MIN_ACTION_SPEED), None),
+            cast_speed: StatValue::with_base(1.0).clamp_range(Some(MIN_ACTION_SPEED), None),
+            spell_damage_increased: 0.0,
+            effect_duration_increased: 0.0,
+            tag_damage_increased: HashMap::new(),
+            tag_damage_more: HashMap::new(),
             critical_chance: StatValue::default(),
             critical_multiplier: StatValue::with_base(1.5), // 150% base crit multiplier
 
@@ -284,15 +529,27 @@ impl StatBlock {
             cold_penetration: StatValue::default(),
             lightning_penetration: StatValue::default(),
             chaos_penetration: StatValue::default(),
+            fire_penetration_percent: 0.0,
+            cold_penetration_percent: 0.0,
+            lightning_penetration_percent: 0.0,
+            chaos_penetration_percent: 0.0,
+            enemy_resistance_reduction: ResistanceReduction::default(),
+            overwhelm: 0.0,
+            culling_strike_threshold: 0.0,
 
             // Recovery
             life_regen: StatValue::default(),
             mana_regen: StatValue::default(),
             life_leech: StatValue::default(),
             mana_leech: StatValue::default(),
+            max_mana_leech_rate: 0.0,
+            pending_mana_leech: 0.0,
+            life_on_hit: 0.0,
+            es_regen_from_life_regen: 0.0,
 
             // Utility
-            movement_speed_increased: 0.0,
+            movement_speed_increased: default_movement_speed(),
+            is_moving: false,
             item_rarity_increased: 0.0,
             item_quantity_increased: 0.0,
 
@@ -323,17 +580,66 @@ impl StatBlock {
         }
     }
 
+    /// Load a StatBlock saved with [`StatBlock::save`]
+    ///
+    /// Equipment, buff and aura sources, unified `effects` and current life,
+    /// mana and energy shield are persisted; flask sources are not.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let content = std::fs::read_to_string(path)?;
+        let mut block: StatBlock = serde_json::from_str(&content)?;
+        block.post_load();
+        Ok(block)
+    }
+
+    /// Restore state that is not serialized after deserializing
+    ///
+    /// Rebuilds from the persisted equipment, buffs and auras so the gear
+    /// cache is repopulated and later rebuilds match the saved stats; current
+    /// resources and effects are kept as saved. Blocks with no equipment,
+    /// buffs or auras are left as loaded, since their stats may have
+    /// been set directly. Buffs saved without a full duration take their
+    /// remaining duration. [`StatBlock::load`] calls this automatically.
+    pub fn post_load(&mut self) {
+        for buff in self
+            .buff_sources
+            .iter_mut()
+            .chain(self.item_buffs.values_mut())
+        {
+            if buff.duration <= 0.0 {
+                buff.duration = buff.duration_remaining;
+            }
+        }
+        if !self.equipped_items.is_empty()
+            || !self.item_buffs.is_empty()
+            || !self.buff_sources.is_empty()
+            || !self.active_auras.is_empty()
+        {
+            self.rebuild();
+        }
+    }
+
+    /// Save this StatBlock as JSON
+    pub fn save(&self, path: &Path) -> Result<(), ConfigError> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
     /// Rebuild stats from all sources (external API for custom sources)
+    ///
+    /// Conditional sources are evaluated against this block's state before
+    /// the rebuild (see `SourceContext::from_block`).
     pub fn rebuild_from_sources(&mut self, sources: &[Box<dyn StatSource>]) {
-        // Preserve identity and equipment
-        let id = std::mem::take(&mut self.id);
-        let equipped_items = std::mem::take(&mut self.equipped_items);
-        let buff_sources = std::mem::take(&mut self.buff_sources);
+        let context = SourceContext::from_block(self);
+        self.rebuild_from_sources_with_context(sources, &context);
+    }
 
-        // Reset to base values
-        *self = StatBlock::with_id(id);
-        self.equipped_items = equipped_items;
-        self.buff_sources = buff_sources;
+    /// Rebuild stats from all sources, evaluating conditional sources against `context`
+    pub fn rebuild_from_sources_with_context(
+        &mut self,
+        sources: &[Box<dyn StatSource>],
+        context: &SourceContext,
+    ) {
+        self.reset_to_base();
 
         // Create accumulator and apply all sources
         let mut accumulator = StatAccumulator::new();
@@ -343,50 +649,314 @@ impl StatBlock {
         sorted_sources.sort_by_key(|s| s.priority());
 
         for source in sorted_sources {
-            source.apply(&mut accumulator);
+            source.apply_with_context(&mut accumulator, context);
         }
 
-        // Apply accumulated stats to self
+        // Apply accumulated stats to self, then attribute-derived stats
         accumulator.apply_to(self);
+        self.apply_attribute_derivations();
 
         // Update current values to max if they exceed
         self.current_life = self.current_life.min(self.max_life.compute());
-        self.current_mana = self.current_mana.min(self.max_mana.compute());
+        self.current_mana = self.current_mana.min(self.unreserved_max_mana());
         self.current_energy_shield = self.current_energy_shield.min(self.max_energy_shield);
     }
 
+    /// Reset stats to base values before a rebuild
+    ///
+    /// Only stats written by sources are reset: each `StatValue` drops its
+    /// modifiers but keeps its base and clamps, and the remaining source-fed
+    /// fields return to their defaults. Everything else (identity, equipment,
+    /// sources and runtime state such as current resources and effects) is
+    /// left untouched; rebuilds clamp current life, mana and energy shield to
+    /// the new maximums once all sources are applied.
+    fn reset_to_base(&mut self) {
+        for stat in [
+            &mut self.max_life,
+            &mut self.max_mana,
+            &mut self.strength,
+            &mut self.dexterity,
+            &mut self.intelligence,
+            &mut self.constitution,
+            &mut self.wisdom,
+            &mut self.charisma,
+            &mut self.armour,
+            &mut self.evasion,
+            &mut self.fire_resistance,
+            &mut self.cold_resistance,
+            &mut self.lightning_resistance,
+            &mut self.chaos_resistance,
+            &mut self.block_chance,
+            &mut self.accuracy,
+            &mut self.global_physical_damage,
+            &mut self.global_fire_damage,
+            &mut self.global_cold_damage,
+            &mut self.global_lightning_damage,
+            &mut self.global_chaos_damage,
+            &mut self.attack_speed,
+            &mut self.cast_speed,
+            &mut self.critical_chance,
+            &mut self.critical_multiplier,
+            &mut self.fire_penetration,
+            &mut self.cold_penetration,
+            &mut self.lightning_penetration,
+            &mut self.chaos_penetration,
+            &mut self.life_regen,
+            &mut self.mana_regen,
+            &mut self.life_leech,
+            &mut self.mana_leech,
+            &mut self.movement_speed_increased,
+        ] {
+            stat.reset_to_base();
+        }
+
+        let defaults = StatBlock::with_id(String::new());
+
+        // Resources
+        self.mana_reservation = defaults.mana_reservation;
+        self.max_energy_shield = defaults.max_energy_shield;
+
+        // Defenses
+        self.dot_damage_taken_reduced = defaults.dot_damage_taken_reduced;
+        self.damage_taken_reduction = defaults.damage_taken_reduction;
+        self.max_fire_resistance = defaults.max_fire_resistance;
+        self.max_cold_resistance = defaults.max_cold_resistance;
+        self.max_lightning_resistance = defaults.max_lightning_resistance;
+        self.max_chaos_resistance = defaults.max_chaos_resistance;
+        self.physical_damage_reduction = defaults.physical_damage_reduction;
+        self.taken_as = defaults.taken_as;
+        self.physical_damage_reflected = defaults.physical_damage_reflected;
+        self.ailment_immunities = defaults.ailment_immunities;
+        self.ailment_threshold = defaults.ailment_threshold;
+
+        // Offense
+        self.damage_conversions = defaults.damage_conversions;
+        self.gain_as_extra = defaults.gain_as_extra;
+        self.spell_damage_increased = defaults.spell_damage_increased;
+        self.effect_duration_increased = defaults.effect_duration_increased;
+        self.tag_damage_increased = defaults.tag_damage_increased;
+        self.tag_damage_more = defaults.tag_damage_more;
+
+        // Penetration
+        self.fire_penetration_percent = defaults.fire_penetration_percent;
+        self.cold_penetration_percent = defaults.cold_penetration_percent;
+        self.lightning_penetration_percent = defaults.lightning_penetration_percent;
+        self.chaos_penetration_percent = defaults.chaos_penetration_percent;
+        self.enemy_resistance_reduction = defaults.enemy_resistance_reduction;
+        self.overwhelm = defaults.overwhelm;
+        self.culling_strike_threshold = defaults.culling_strike_threshold;
+
+        // Recovery
+        self.life_on_hit = defaults.life_on_hit;
+        self.es_regen_from_life_regen = defaults.es_regen_from_life_regen;
+
+        // Utility
+        self.item_rarity_increased = defaults.item_rarity_increased;
+        self.item_quantity_increased = defaults.item_quantity_increased;
+
+        // Weapon stats
+        self.weapon_physical_min = defaults.weapon_physical_min;
+        self.weapon_physical_max = defaults.weapon_physical_max;
+        self.weapon_fire_min = defaults.weapon_fire_min;
+        self.weapon_fire_max = defaults.weapon_fire_max;
+        self.weapon_cold_min = defaults.weapon_cold_min;
+        self.weapon_cold_max = defaults.weapon_cold_max;
+        self.weapon_lightning_min = defaults.weapon_lightning_min;
+        self.weapon_lightning_max = defaults.weapon_lightning_max;
+        self.weapon_chaos_min = defaults.weapon_chaos_min;
+        self.weapon_chaos_max = defaults.weapon_chaos_max;
+        self.weapon_attack_speed = defaults.weapon_attack_speed;
+        self.weapon_crit_chance = defaults.weapon_crit_chance;
+
+        // Status effect stats
+        self.status_effect_stats = defaults.status_effect_stats;
+    }
+
+    /// Grant stats derived from attributes gained above the base values
+    ///
+    /// Runs after all sources are applied so attributes from gear, buffs
+    /// and auras count. Base attributes are already reflected in base
+    /// life/mana, so only the bonus above `StatValue::base` is converted.
+    /// Not idempotent, so it only runs as the last step of a rebuild.
+    fn apply_attribute_derivations(&mut self) {
+        let constants = &self.attribute_constants;
+        if constants.points_per_bonus <= 0.0 {
+            return;
+        }
+        let steps = |attribute: &StatValue| {
+            ((attribute.compute() - attribute.base).max(0.0) / constants.points_per_bonus).floor()
+        };
+
+        let strength = steps(&self.strength);
+        let dexterity = steps(&self.dexterity);
+        let intelligence = steps(&self.intelligence);
+
+        self.max_life
+            .add_flat(strength * constants.life_per_strength);
+        self.global_physical_damage
+            .add_increased(strength * constants.physical_increased_per_strength);
+        self.accuracy
+            .add_flat(dexterity * constants.accuracy_per_dexterity);
+        self.evasion
+            .add_increased(dexterity * constants.evasion_increased_per_dexterity);
+        self.max_mana
+            .add_flat(intelligence * constants.mana_per_intelligence);
+    }
+
+    /// Rebuild many blocks, each from its own list of sources
+    ///
+    /// `sources_per_block[i]` is applied to `blocks[i]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `blocks` and `sources_per_block` differ in length.
+    pub fn rebuild_many(blocks: &mut [StatBlock], sources_per_block: &[Vec<Box<dyn StatSource>>]) {
+        assert_eq!(
+            blocks.len(),
+            sources_per_block.len(),
+            "one source list is needed per block"
+        );
+        for (block, sources) in blocks.iter_mut().zip(sources_per_block) {
+            block.rebuild_from_sources(sources);
+        }
+    }
+
+    /// Rebuild many blocks in parallel, each from its own list of sources
+    ///
+    /// Rebuilds are independent, so results are identical to [`StatBlock::rebuild_many`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `blocks` and `sources_per_block` differ in length.
+    #[cfg(feature = "rayon")]
+    pub fn rebuild_many_par(
+        blocks: &mut [StatBlock],
+        sources_per_block: &[Vec<Box<dyn StatSource>>],
+    ) {
+        use rayon::prelude::*;
+
+        assert_eq!(
+            blocks.len(),
+            sources_per_block.len(),
+            "one source list is needed per block"
+        );
+        blocks
+            .par_iter_mut()
+            .zip(sources_per_block.par_iter())
+            .for_each(|(block, sources)| block.rebuild_from_sources(sources));
+    }
+
     /// Rebuild stats from internal equipment and buffs
+    ///
+    /// In [`RequirementMode::Penalty`], items are re-applied at their requirement
+    /// satisfaction once the full attribute totals are known.
     fn rebuild(&mut self) {
-        // Preserve identity and internal state
-        let id = std::mem::take(&mut self.id);
-        let equipped_items = std::mem::take(&mut self.equipped_items);
-        let buff_sources = std::mem::take(&mut self.buff_sources);
+        self.rebuild_with_gear_effectiveness(&HashMap::new());
+
+        if self.requirement_mode == RequirementMode::Penalty {
+            let penalties: HashMap<EquipmentSlot, f64> = self
+                .equipped_items
+                .iter()
+                .map(|(slot, item)| (*slot, self.requirement_satisfaction(item)))
+                .filter(|(_, satisfaction)| *satisfaction < 1.0)
+                .collect();
+            if !penalties.is_empty() {
+                self.rebuild_with_gear_effectiveness(&penalties);
+            }
+        }
+    }
 
-        // Reset to base values
-        *self = StatBlock::with_id(id);
-        self.equipped_items = equipped_items;
-        self.buff_sources = buff_sources;
+    /// Rebuild stats, scaling each slot's item by the given effectiveness (default 1.0)
+    fn rebuild_with_gear_effectiveness(
+        &mut self,
+        gear_effectiveness: &HashMap<EquipmentSlot, f64>,
+    ) {
+        self.gear_cache = self
+            .equipped_items
+            .keys()
+            .map(|slot| {
+                let effectiveness = gear_effectiveness.get(slot).copied().unwrap_or(1.0);
+                (*slot, self.slot_accumulator(*slot, effectiveness))
+            })
+            .collect();
+        self.recompute_from_cache();
+    }
 
-        // Create accumulator
+    /// Collect the stats contributed by the item (and item buff) in a slot
+    fn slot_accumulator(&self, slot: EquipmentSlot, effectiveness: f64) -> StatAccumulator {
         let mut accumulator = StatAccumulator::new();
-
-        // Apply gear sources
-        for (slot, item) in &self.equipped_items {
-            let gear_source = GearSource::new(*slot, item.clone());
+        if let Some(item) = self.equipped_items.get(&slot) {
+            let mut gear_source =
+                GearSource::borrowed(slot, item).with_effectiveness(effectiveness);
+            if let Some(buff) = self.item_buffs.get(&slot) {
+                gear_source = gear_source.with_buff(buff.clone());
+            }
             gear_source.apply(&mut accumulator);
         }
+        accumulator
+    }
+
+    /// Refresh the changed slot's cached contribution, then recompute
+    ///
+    /// Only the changed slot's `GearSource` is re-read from its item; other
+    /// slots reuse their cached accumulators. This is not an incremental
+    /// update: the block is still reset to base and every cached slot,
+    /// aura, buff and flask is merged and applied again. Falls back to
+    /// [`StatBlock::rebuild`], which re-reads every item, when the cache is
+    /// incomplete (e.g. after deserializing) or in
+    /// [`RequirementMode::Penalty`], where it runs two full passes because
+    /// penalties depend on the new attribute totals.
+    fn recompute_slot(&mut self, slot: EquipmentSlot) {
+        let cache_complete = self
+            .equipped_items
+            .keys()
+            .all(|equipped| *equipped == slot || self.gear_cache.contains_key(equipped));
+        if !cache_complete || self.requirement_mode == RequirementMode::Penalty {
+            self.rebuild();
+            return;
+        }
+
+        if self.equipped_items.contains_key(&slot) {
+            let accumulator = self.slot_accumulator(slot, 1.0);
+            self.gear_cache.insert(slot, accumulator);
+        } else {
+            self.gear_cache.remove(&slot);
+        }
+        self.recompute_from_cache();
+    }
+
+    /// Reset to base values and apply cached gear plus auras and buffs
+    fn recompute_from_cache(&mut self) {
+        self.reset_to_base();
+
+        // Merge cached gear contributions
+        let mut accumulator = StatAccumulator::new();
+        for gear in self.gear_cache.values() {
+            accumulator.merge(gear);
+        }
+
+        // Apply aura sources
+        for aura in self.active_auras.values() {
+            aura.apply(&mut accumulator);
+        }
 
         // Apply buff sources
         for buff in &self.buff_sources {
             buff.apply(&mut accumulator);
         }
 
-        // Apply accumulated stats to self
+        // Apply flask effects
+        for flask in &self.active_flasks {
+            flask.apply(&mut accumulator);
+        }
+
+        // Apply accumulated stats to self, then attribute-derived stats
         accumulator.apply_to(self);
+        self.apply_attribute_derivations();
 
         // Update current values to max if they exceed
         self.current_life = self.current_life.min(self.max_life.compute());
-        self.current_mana = self.current_mana.min(self.max_mana.compute());
+        self.current_mana = self.current_mana.min(self.unreserved_max_mana());
         self.current_energy_shield = self.current_energy_shield.min(self.max_energy_shield);
     }
 
@@ -405,23 +975,130 @@ impl StatBlock {
         self.max_mana.compute()
     }
 
+    /// Get mana currently reserved by active auras
+    pub fn reserved_mana(&self) -> f64 {
+        self.computed_max_mana() * self.mana_reservation.clamp(0.0, 1.0)
+    }
+
+    /// Get maximum mana available after reservation
+    pub fn unreserved_max_mana(&self) -> f64 {
+        self.computed_max_mana() - self.reserved_mana()
+    }
+
     /// Heal life by amount, capped at max
     pub fn heal(&mut self, amount: f64) {
         let max = self.computed_max_life();
         self.current_life = (self.current_life + amount).min(max);
     }
 
-    /// Restore mana by amount, capped at max
+    /// Restore mana by amount, capped at unreserved max
     pub fn restore_mana(&mut self, amount: f64) {
-        let max = self.computed_max_mana();
+        let max = self.unreserved_max_mana();
         self.current_mana = (self.current_mana + amount).min(max);
     }
 
+    /// Apply life and mana regeneration for `delta` seconds
+    ///
+    /// Recovery is capped at max life and unreserved mana. Dead entities
+    /// do not regenerate.
+    pub fn tick_regen(&mut self, delta: f64) {
+        if !self.is_alive() || delta <= 0.0 {
+            return;
+        }
+        self.heal(self.life_regen.compute().max(0.0) * delta);
+        self.restore_mana(self.mana_regen.compute().max(0.0) * delta);
+
+        if self.pending_mana_leech > 0.0 {
+            let limit = if self.max_mana_leech_rate > 0.0 {
+                self.max_mana_leech_rate * self.computed_max_mana() * delta
+            } else {
+                f64::INFINITY
+            };
+            let leeched = self.pending_mana_leech.min(limit);
+            self.pending_mana_leech -= leeched;
+            self.restore_mana(leeched);
+        }
+    }
+
+    /// Recover leeched mana, instantly or queued under `max_mana_leech_rate`
+    pub fn leech_mana(&mut self, amount: f64) {
+        if amount <= 0.0 {
+            return;
+        }
+        if self.max_mana_leech_rate > 0.0 {
+            self.pending_mana_leech += amount;
+        } else {
+            self.restore_mana(amount);
+        }
+    }
+
+    /// Set current life as a fraction of max life (clamped to 0.0..=1.0)
+    pub fn set_life_percent(&mut self, fraction: f64) {
+        self.current_life = self.computed_max_life() * fraction.clamp(0.0, 1.0);
+    }
+
+    /// Set current mana as a fraction of max mana (clamped to 0.0..=1.0, capped at unreserved mana)
+    pub fn set_mana_percent(&mut self, fraction: f64) {
+        let mana = self.computed_max_mana() * fraction.clamp(0.0, 1.0);
+        self.current_mana = mana.min(self.unreserved_max_mana());
+    }
+
+    /// Set current energy shield as a fraction of max energy shield (clamped to 0.0..=1.0)
+    pub fn set_es_percent(&mut self, fraction: f64) {
+        self.current_energy_shield = self.max_energy_shield * fraction.clamp(0.0, 1.0);
+    }
+
     /// Apply energy shield (from warding spells)
     pub fn apply_energy_shield(&mut self, amount: f64) {
         self.current_energy_shield = (self.current_energy_shield + amount).min(self.max_energy_shield);
     }
 
+    /// Grant guard absorption (from guard skills)
+    pub fn apply_guard(&mut self, amount: f64) {
+        self.current_guard += amount.max(0.0);
+    }
+
+    /// Grant ward
+    pub fn apply_ward(&mut self, amount: f64) {
+        self.current_ward += amount.max(0.0);
+    }
+
+    /// Regenerate energy shield over time, returning the amount restored
+    ///
+    /// Uses the default game constants; see [`StatBlock::tick_energy_shield_with_constants`].
+    pub fn tick_energy_shield(&mut self, delta: f64) -> f64 {
+        self.tick_energy_shield_with_constants(delta, &GameConstants::default())
+    }
+
+    /// Regenerate energy shield over time using tunable constants
+    ///
+    /// ES regenerates from `es_regen_from_life_regen` (a fraction of life
+    /// regeneration per second) at all times. Once the recharge delay since ES
+    /// last absorbed damage has elapsed, it also recharges at
+    /// `energy_shield.recharge_rate` of max ES per second.
+    pub fn tick_energy_shield_with_constants(
+        &mut self,
+        delta: f64,
+        constants: &GameConstants,
+    ) -> f64 {
+        if delta <= 0.0 {
+            return 0.0;
+        }
+
+        let regen = (self.life_regen.compute() * self.es_regen_from_life_regen).max(0.0) * delta;
+
+        // Only the part of the tick after the delay counts towards recharge
+        let waited = self.es_recharge_delay_remaining.clamp(0.0, delta);
+        self.es_recharge_delay_remaining -= waited;
+        let recharge_time = delta - waited;
+        let recharge =
+            self.max_energy_shield * constants.energy_shield.recharge_rate.max(0.0) * recharge_time;
+
+        let before = self.current_energy_shield;
+        self.apply_energy_shield(regen + recharge);
+        self.current_energy_shield - before
+    }
+
     /// Set maximum energy shield capacity
     pub fn set_max_energy_shield(&mut self, amount: f64) {
         self.max_energy_shield = amount;
@@ -431,20 +1108,151 @@ impl StatBlock {
     // === Equipment Methods ===
 
     /// Equip an item to a slot, automatically rebuilding stats
-    pub fn equip(&mut self, slot: EquipmentSlot, item: Item) {
+    ///
+    /// Equipping a two-handed weapon in the main hand unequips the off-hand
+    /// item and returns it as `Ok(Some(item))`. While a two-hander is held,
+    /// an off-hand item is refused and handed back as `Err(item)`.
+    pub fn equip(&mut self, slot: EquipmentSlot, item: Item) -> Result<Option<Item>, Item> {
+        self.place_item(slot, item, None)
+    }
+
+    /// Equip an item that grants a persistent buff while equipped
+    ///
+    /// The buff is tied to the slot, so unequipping or replacing the item removes it.
+    /// Item buffs are not ticked; give them an infinite duration to keep them active.
+    /// Two-handed weapons are handled as in [`StatBlock::equip`].
+    pub fn equip_with_buff(
+        &mut self,
+        slot: EquipmentSlot,
+        item: Item,
+        buff: BuffSource,
+    ) -> Result<Option<Item>, Item> {
+        self.place_item(slot, item, Some(buff))
+    }
+
+    /// Check if the off hand is unusable because a two-hander is held
+    pub fn off_hand_blocked(&self) -> bool {
+        self.equipped_items
+            .get(&EquipmentSlot::MainHand)
+            .is_some_and(|item| is_two_handed(&item.class))
+    }
+
+    /// Put an item in a slot, enforcing two-handed weapon rules
+    fn place_item(
+        &mut self,
+        slot: EquipmentSlot,
+        item: Item,
+        buff: Option<BuffSource>,
+    ) -> Result<Option<Item>, Item> {
+        if slot == EquipmentSlot::OffHand && self.off_hand_blocked() {
+            return Err(item);
+        }
+
+        let mut displaced = None;
+        if slot == EquipmentSlot::MainHand && is_two_handed(&item.class) {
+            self.item_buffs.remove(&EquipmentSlot::OffHand);
+            self.gear_cache.remove(&EquipmentSlot::OffHand);
+            displaced = self.equipped_items.remove(&EquipmentSlot::OffHand);
+        }
+
         self.equipped_items.insert(slot, item);
-        self.rebuild();
+        match buff {
+            Some(buff) => self.item_buffs.insert(slot, buff),
+            None => self.item_buffs.remove(&slot),
+        };
+        self.recompute_slot(slot);
+        Ok(displaced)
+    }
+
+    /// Equip an item only if its attribute requirements are met
+    ///
+    /// Requirements are checked against current strength, dexterity and
+    /// intelligence. On failure nothing is equipped and the error lists every
+    /// unmet requirement. [`StatBlock::equip`] remains the unchecked version.
+    /// Equipping into a blocked off hand fails with `EquipError::SlotBlocked`.
+    /// Either error carries the item back (see [`EquipError::into_item`]).
+    /// On success, returns any off-hand item displaced by a two-hander.
+    pub fn try_equip(
+        &mut self,
+        slot: EquipmentSlot,
+        item: Item,
+    ) -> Result<Option<Item>, EquipError> {
+        if slot == EquipmentSlot::OffHand && self.off_hand_blocked() {
+            return Err(EquipError::SlotBlocked(slot, Box::new(item)));
+        }
+        let shortfalls = self.requirement_shortfalls(&item);
+        if !shortfalls.is_empty() {
+            return Err(EquipError::RequirementsNotMet(shortfalls, Box::new(item)));
+        }
+        self.equip(slot, item)
+            .map_err(|item| EquipError::SlotBlocked(slot, Box::new(item)))
+    }
+
+    /// Get the attribute requirements of an item this entity does not meet
+    pub fn requirement_shortfalls(&self, item: &Item) -> Vec<RequirementShortfall> {
+        [
+            (
+                "Str",
+                item.requirements.strength as f64,
+                self.strength.compute(),
+            ),
+            (
+                "Dex",
+                item.requirements.dexterity as f64,
+                self.dexterity.compute(),
+            ),
+            (
+                "Int",
+                item.requirements.intelligence as f64,
+                self.intelligence.compute(),
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, required, current)| current < required)
+        .map(|(attribute, required, current)| RequirementShortfall {
+            attribute,
+            required,
+            current,
+        })
+        .collect()
+    }
+
+    /// Get the buff granted by the item in a slot, if any
+    pub fn item_buff(&self, slot: EquipmentSlot) -> Option<&BuffSource> {
+        self.item_buffs.get(&slot)
     }
 
     /// Unequip an item from a slot, returning it if present
     pub fn unequip(&mut self, slot: EquipmentSlot) -> Option<Item> {
+        self.item_buffs.remove(&slot);
         let item = self.equipped_items.remove(&slot);
         if item.is_some() {
-            self.rebuild();
+            self.recompute_slot(slot);
         }
         item
     }
 
+    /// Get how well this entity meets an item's attribute requirements
+    ///
+    /// Returns 1.0 if every requirement is met, otherwise the lowest
+    /// ratio of attribute to requirement (0.0 to 1.0).
+    pub fn requirement_satisfaction(&self, item: &Item) -> f64 {
+        let requirements = [
+            (item.requirements.strength as f64, self.strength.compute()),
+            (item.requirements.dexterity as f64, self.dexterity.compute()),
+            (
+                item.requirements.intelligence as f64,
+                self.intelligence.compute(),
+            ),
+        ];
+
+        requirements
+            .iter()
+            .filter(|(required, _)| *required > 0.0)
+            .map(|(required, have)| (have / required).clamp(0.0, 1.0))
+            .fold(1.0, f64::min)
+    }
+
     /// Get a reference to the item equipped in a slot
     pub fn equipped(&self, slot: EquipmentSlot) -> Option<&Item> {
         self.equipped_items.get(&slot)
@@ -458,13 +1266,30 @@ impl StatBlock {
     // === Buff Methods ===
 
     /// Apply a buff, automatically rebuilding stats
+    ///
+    /// Reapplying a buff ID that is already active is resolved by the new
+    /// buff's [`BuffStacking`] rule.
     pub fn apply_buff(&mut self, buff: BuffSource) {
-        // Check if buff already exists and refresh/stack instead
-        if let Some(existing) = self.buff_sources.iter_mut().find(|b| b.buff_id == buff.buff_id) {
-            existing.refresh(buff.duration_remaining);
-            existing.add_stack();
-        } else {
-            self.buff_sources.push(buff);
+        // Reapplying an existing buff follows the new buff's stacking rule
+        let existing = self
+            .buff_sources
+            .iter_mut()
+            .find(|b| b.buff_id == buff.buff_id);
+        match (existing, buff.stacking) {
+            (Some(existing), BuffStacking::Refresh) => existing.refresh(buff.duration_remaining),
+            (Some(existing), BuffStacking::Stack { max_stacks }) => {
+                existing.refresh(buff.duration_remaining);
+                if existing.stacks < max_stacks {
+                    existing.add_stack();
+                }
+            }
+            (Some(existing), BuffStacking::StrongestOnly) => {
+                if buff.magnitude() > existing.magnitude() {
+                    *existing = buff;
+                }
+            }
+            // New buffs and independent instances
+            _ => self.buff_sources.push(buff),
         }
         self.rebuild();
     }
@@ -480,9 +1305,10 @@ impl StatBlock {
 
     /// Tick all buffs by delta time, removing expired ones
     pub fn tick_buffs(&mut self, delta: f64) {
-        let count_before = self.buff_sources.len();
+        let stacks = |buffs: &[BuffSource]| buffs.iter().map(|b| b.stacks).collect::<Vec<_>>();
+        let stacks_before = stacks(&self.buff_sources);
         self.buff_sources.retain_mut(|buff| buff.tick(delta));
-        if self.buff_sources.len() != count_before {
+        if stacks(&self.buff_sources) != stacks_before {
             self.rebuild();
         }
     }
@@ -492,12 +1318,128 @@ impl StatBlock {
         &self.buff_sources
     }
 
+    // === Flask Methods ===
+
+    /// Use a flask, spending its charges and starting its effect
+    ///
+    /// Using a flask whose effect is already running restarts the timer.
+    /// Returns false (and changes nothing) if the flask lacks charges.
+    pub fn use_flask(&mut self, flask: &mut FlaskSource) -> bool {
+        let Some(active) = flask.consume() else {
+            return false;
+        };
+        self.active_flasks.retain(|f| f.flask_id != active.flask_id);
+        self.active_flasks.push(active);
+        self.rebuild();
+        true
+    }
+
+    /// Tick all flask effects by delta time, removing expired ones
+    pub fn tick_flasks(&mut self, delta: f64) {
+        let count_before = self.active_flasks.len();
+        self.active_flasks.retain_mut(|flask| flask.tick(delta));
+        if self.active_flasks.len() != count_before {
+            self.rebuild();
+        }
+    }
+
+    /// Get all running flask effects
+    pub fn active_flasks(&self) -> &[FlaskSource] {
+        &self.active_flasks
+    }
+
+    // === Aura Methods ===
+
+    /// Toggle an aura skill on or off, automatically rebuilding stats
+    ///
+    /// Activating applies the aura's modifiers and mana reservation.
+    /// Toggling the same skill again deactivates it.
+    /// Returns true if the aura is now active.
+    pub fn toggle_aura(&mut self, skill_id: &str, mut aura: AuraSource) -> bool {
+        let now_active = if self.active_auras.remove(skill_id).is_some() {
+            false
+        } else {
+            aura.active = true;
+            self.active_auras.insert(skill_id.to_string(), aura);
+            true
+        };
+        self.rebuild();
+        now_active
+    }
+
+    /// Check if the aura toggled by a skill is active
+    pub fn is_aura_active(&self, skill_id: &str) -> bool {
+        self.active_auras.contains_key(skill_id)
+    }
+
+    /// Get all active auras keyed by skill ID
+    pub fn active_auras(&self) -> impl Iterator<Item = (&String, &AuraSource)> {
+        self.active_auras.iter()
+    }
+
     // === Combat Methods ===
 
     /// Generate a damage packet for a skill attack (RNG handled internally)
+    ///
+    /// Uses the thread RNG and default DoT configs; use `attack_seeded` to
+    /// replay an exact damage sequence.
     pub fn attack(&self, skill: &DamagePacketGenerator) -> DamagePacket {
         let mut rng = rand::thread_rng();
-        calculate_damage(self, skill, self.id.clone(), &mut rng)
+        self.attack_seeded(skill, DotRegistry::shared_defaults(), &mut rng)
+    }
+
+    /// Generate a damage packet for a skill attack from the given RNG
+    ///
+    /// Two RNGs seeded the same produce identical packets.
+    pub fn attack_seeded(
+        &self,
+        skill: &DamagePacketGenerator,
+        dot_registry: &DotRegistry,
+        rng: &mut impl Rng,
+    ) -> DamagePacket {
+        calculate_damage_with_registry(self, skill, self.id.clone(), dot_registry, rng)
+    }
+
+    /// Check that the equipped main hand allows a skill
+    ///
+    /// Attacks need a main-hand weapon; skills with `required_weapon_classes`
+    /// need a main hand of one of those classes. Mana is checked separately
+    /// by `can_afford`.
+    pub fn can_use_skill(&self, skill: &DamagePacketGenerator) -> Result<(), SkillError> {
+        let main_hand = self.equipped(EquipmentSlot::MainHand);
+        if !skill.required_weapon_classes.is_empty() {
+            return match main_hand {
+                Some(item) if skill.required_weapon_classes.contains(&item.class) => Ok(()),
+                _ => Err(SkillError::WrongWeapon(
+                    skill.required_weapon_classes.clone(),
+                )),
+            };
+        }
+        if skill.is_attack() && !skill.is_spell() && main_hand.is_none() {
+            return Err(SkillError::NoWeapon);
+        }
+        Ok(())
+    }
+
+    /// Check whether this entity has the mana to use a skill
+    pub fn can_afford(&self, skill: &DamagePacketGenerator) -> bool {
+        self.current_mana >= skill.mana_cost
+    }
+
+    /// Use a skill if affordable, spending its mana cost
+    ///
+    /// Returns `None`, leaving mana unchanged, if the skill costs more mana
+    /// than is available.
+    pub fn try_attack(
+        &mut self,
+        skill: &DamagePacketGenerator,
+        dot_registry: &DotRegistry,
+    ) -> Option<DamagePacket> {
+        if !self.can_afford(skill) {
+            return None;
+        }
+        self.current_mana -= skill.mana_cost;
+        Some(self.attack_seeded(skill, dot_registry, &mut rand::thread_rng()))
     }
 
     /// Receive damage from a damage packet (immutable API)
@@ -516,45 +1458,14 @@ impl StatBlock {
     }
 
     /// Add an effect to this entity (mutable)
+    ///
+    /// Ailments are applied through `effect::apply_ailment_from` using the
+    /// default DoT configs; use that directly to apply with a custom registry.
     pub fn add_effect(&mut self, effect: Effect) {
-        // Handle stacking logic for ailments
-        if let EffectType::Ailment { status, stacking, .. } = &effect.effect_type {
-            let existing = self.effects.iter_mut().find(|e| {
-                if let EffectType::Ailment { status: existing_status, .. } = &e.effect_type {
-                    existing_status == status
-                } else {
-                    false
-                }
-            });
-
-            if let Some(existing_effect) = existing {
-                match stacking {
-                    AilmentStacking::StrongestOnly => {
-                        // Only keep if new is stronger, otherwise just refresh
-                        if effect.dps() >= existing_effect.dps() {
-                            existing_effect.refresh(effect.duration_remaining);
-                            // Update dps if higher
-                            if let EffectType::Ailment { dot_dps: existing_dps, .. } = &mut existing_effect.effect_type {
-                                if let EffectType::Ailment { dot_dps: new_dps, .. } = &effect.effect_type {
-                                    if *new_dps > *existing_dps {
-                                        *existing_dps = *new_dps;
-                                    }
-                                }
-                            }
-                        }
-                        return; // Don't add new effect
-                    }
-                    AilmentStacking::Limited { .. } => {
-                        // Add stack to existing, refresh duration
-                        existing_effect.add_stack();
-                        existing_effect.refresh(effect.duration_remaining);
-                        return; // Don't add new effect
-                    }
-                    AilmentStacking::Unlimited => {
-                        // Just add as new effect (fall through)
-                    }
-                }
-            }
+        // Ailments stack or refresh per their default DoT config
+        if effect.status().is_some() {
+            apply_ailment_from(self, effect, DotRegistry::shared_defaults());
+            return;
         }
 
         // Check for stat modifier with same ID
@@ -570,15 +1481,94 @@ impl StatBlock {
         self.effects.push(effect);
     }
 
+    /// Fold legacy `active_dots` and `active_status_effects` into `effects`
+    ///
+    /// Entries are converted as-is (stacks, effectiveness and remaining
+    /// duration are kept) rather than re-applied, so total damage is
+    /// unchanged. Legacy DoTs whose type ID matches no status effect are
+    /// left in `active_dots`. Returns the number of entries migrated.
+    pub fn migrate_legacy_effects(&mut self) -> usize {
+        let mut migrated = 0;
+
+        let dots = std::mem::take(&mut self.active_dots);
+        for dot in dots {
+            let Some(status) = status_for_id(&dot.dot_type) else {
+                self.active_dots.push(dot);
+                continue;
+            };
+            let dps = if dot.tick_rate > 0.0 {
+                dot.damage_per_tick / dot.tick_rate
+            } else {
+                0.0
+            };
+            let mut effect =
+                Effect::from_status(status, dot.duration_remaining, 0.0, dps, dot.source_id);
+            effect.total_duration = dot.total_duration;
+            if let EffectType::Ailment {
+                tick_rate,
+                time_until_tick,
+                effectiveness,
+                ..
+            } = &mut effect.effect_type
+            {
+                *tick_rate = dot.tick_rate;
+                *time_until_tick = dot.time_until_tick;
+                *effectiveness = dot.effectiveness;
+            }
+            self.effects.push(effect);
+            migrated += 1;
+        }
+
+        for legacy in std::mem::take(&mut self.active_status_effects) {
+            let mut effect = Effect::from_status(
+                legacy.effect_type,
+                legacy.duration_remaining,
+                legacy.magnitude,
+                legacy.dot_dps,
+                legacy.source_id,
+            );
+            effect.stacks = legacy.stacks;
+            effect.max_stacks = effect.max_stacks.max(legacy.stacks);
+            self.effects.push(effect);
+            migrated += 1;
+        }
+
+        migrated
+    }
+
     /// Tick all effects by delta time (immutable pattern)
     /// Returns a new StatBlock and the tick result
+    ///
+    /// This is the single path for DoT and status effect processing; any
+    /// legacy effects are migrated into `effects` before ticking.
     pub fn tick_effects(&self, delta: f64) -> (StatBlock, TickResult) {
         let mut new_block = self.clone();
+        new_block.migrate_legacy_effects();
         let mut result = TickResult::default();
-
-        // Process all effects
-        for effect in &mut new_block.effects {
-            let damage = effect.tick(delta);
+        let taken_mult = new_block.dot_damage_taken_multiplier();
+        let registry = DotRegistry::shared_defaults();
+        let dot_mults: Vec<f64> = new_block
+            .effects
+            .iter()
+            .map(|effect| {
+                effect
+                    .status()
+                    .and_then(|status| registry.config_for(status))
+                    .map_or(1.0, |config| {
+                        let moving_mult = if new_block.is_moving {
+                            config.moving_multiplier
+                        } else {
+                            1.0
+                        };
+                        new_block.dot_resistance_multiplier(config.damage_type) * moving_mult
+                    })
+            })
+            .collect();
+
+        // Process all effects; DoTs are mitigated by resistance to their damage type
+        // and scaled by their moving multiplier while this entity moves
+        for (effect, dot_mult) in new_block.effects.iter_mut().zip(dot_mults) {
+            let damage = effect.tick(delta) * taken_mult * dot_mult;
             if damage > 0.0 {
                 result.dot_damage += damage;
             }
@@ -594,6 +1584,15 @@ impl StatBlock {
         }
         result.life_remaining = new_block.current_life;
 
+        // Applied resistance reduction wears off once its duration runs out
+        if !new_block.resistance_reduction.is_empty() {
+            new_block.resistance_reduction_remaining -= delta;
+            if new_block.resistance_reduction_remaining <= 0.0 {
+                new_block.resistance_reduction = ResistanceReduction::default();
+                new_block.resistance_reduction_remaining = 0.0;
+            }
+        }
+
         // Collect expired effects
         for effect in &new_block.effects {
             if !effect.is_active() {
@@ -641,4 +1640,1253 @@ impl StatBlock {
     pub fn clear_effects(&mut self) {
         self.effects.clear();
     }
+
+    /// Remove every effect matching a predicate, returning how many were removed
+    ///
+    /// Stats are rebuilt only if a stat modifier effect was removed.
+    pub fn remove_effects_where(&mut self, mut predicate: impl FnMut(&Effect) -> bool) -> usize {
+        let count_before = self.effects.len();
+        let mut stat_effect_removed = false;
+        self.effects.retain(|effect| {
+            let remove = predicate(effect);
+            stat_effect_removed |= remove && effect.is_stat_modifier();
+            !remove
+        });
+
+        if stat_effect_removed {
+            self.rebuild_from_effects();
+        }
+        count_before - self.effects.len()
+    }
+
+    /// Remove all ailments (poison, freeze, etc.), returning how many were removed
+    pub fn cleanse_ailments(&mut self) -> usize {
+        self.remove_effects_where(Effect::is_ailment)
+    }
+
+    /// Remove all debuff stat modifiers, returning how many were removed
+    pub fn cleanse_debuffs(&mut self) -> usize {
+        self.remove_effects_where(Effect::is_debuff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::BuffExpiry;
+    use loot_core::types::StatType;
+
+    #[test]
+    fn test_toggle_aura() {
+        let mut block = StatBlock::new();
+        let aura = AuraSource::new("determination".to_string(), "Determination".to_string())
+            .with_modifier(StatType::AddedArmour, 100.0)
+            .with_mana_reservation(25.0);
+
+        // Toggle on: stats change and mana is reserved
+        assert!(block.toggle_aura("determination", aura.clone()));
+        assert!(block.is_aura_active("determination"));
+        assert!((block.armour.compute() - 100.0).abs() < 0.01);
+        // 25% of 40 base mana reserved
+        assert!((block.reserved_mana() - 10.0).abs() < 0.01);
+        assert!(block.current_mana <= block.unreserved_max_mana());
+
+        // Toggle off: stats and reservation revert
+        assert!(!block.toggle_aura("determination", aura));
+        assert!(!block.is_aura_active("determination"));
+        assert!((block.armour.compute() - 0.0).abs() < 0.01);
+        assert!((block.reserved_mana() - 0.0).abs() < 0.01);
+        assert!((block.unreserved_max_mana() - block.computed_max_mana()).abs() < 0.01);
+    }
+
+    fn encounter_sources(count: usize) -> Vec<Vec<Box<dyn StatSource>>> {
+        (0..count)
+            .map(|i| {
+                let buff = BuffSource::new(format!("buff_{}", i), "Buff".to_string(), 10.0, false)
+                    .with_modifier(StatType::AddedLife, i as f64, false)
+                    .with_modifier(StatType::IncreasedArmour, (i % 7) as f64 * 10.0, false);
+                let sources: Vec<Box<dyn StatSource>> = vec![
+                    Box::new(crate::source::BaseStatsSource::new(1 + (i % 50) as u32)),
+                    Box::new(buff),
+                ];
+                sources
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rebuild_many() {
+        let sources = encounter_sources(100);
+        let mut blocks: Vec<StatBlock> = (0..100)
+            .map(|i| StatBlock::with_id(format!("enemy_{}", i)))
+            .collect();
+        StatBlock::rebuild_many(&mut blocks, &sources);
+
+        for (i, block) in blocks.iter().enumerate() {
+            let mut expected = StatBlock::with_id(format!("enemy_{}", i));
+            expected.rebuild_from_sources(&sources[i]);
+            assert_eq!(
+                serde_json::to_string(block).unwrap(),
+                serde_json::to_string(&expected).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "one source list is needed per block")]
+    fn test_rebuild_many_rejects_mismatched_lengths() {
+        let sources = encounter_sources(2);
+        let mut blocks = vec![StatBlock::new(); 3];
+        StatBlock::rebuild_many(&mut blocks, &sources);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_rebuild_many_par_matches_serial() {
+        let sources = encounter_sources(100);
+        let mut serial: Vec<StatBlock> = (0..100)
+            .map(|i| StatBlock::with_id(format!("enemy_{}", i)))
+            .collect();
+        let mut parallel = serial.clone();
+
+        StatBlock::rebuild_many(&mut serial, &sources);
+        StatBlock::rebuild_many_par(&mut parallel, &sources);
+
+        for (s, p) in serial.iter().zip(&parallel) {
+            assert_eq!(
+                serde_json::to_string(s).unwrap(),
+                serde_json::to_string(p).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_poison_from_resolution_ticks_and_expires() {
+        use crate::damage::PendingStatusEffect;
+
+        let mut defender = StatBlock::new();
+        defender.max_life = StatValue::with_base(1000.0);
+        defender.current_life = 1000.0;
+
+        // Status damage above max life: guaranteed to apply
+        let mut packet = DamagePacket::new("attacker".to_string(), "venom".to_string());
+        packet
+            .status_effects_to_apply
+            .push(PendingStatusEffect::new_with_dot(
+                StatusEffect::Poison,
+                5000.0,
+                2.0,
+                0.0,
+                30.0,
+            ));
+        let (defender, result) = defender.receive_damage(&packet);
+        assert_eq!(result.effects_applied.len(), 1);
+        assert_eq!(defender.effects_of_status(StatusEffect::Poison).len(), 1);
+        assert!(defender.active_dots.is_empty() && defender.active_status_effects.is_empty());
+
+        let (defender, tick) = defender.tick_effects(1.0);
+        assert!(tick.dot_damage > 0.0);
+        assert!((defender.current_life - (1000.0 - tick.dot_damage)).abs() < 0.01);
+        assert!(defender.has_active_ailment(StatusEffect::Poison));
+
+        let (defender, tick) = defender.tick_effects(1.5);
+        assert_eq!(tick.expired_effects, vec!["poison".to_string()]);
+        assert!(defender.effects.is_empty());
+        assert!(!defender.has_active_ailment(StatusEffect::Poison));
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        use crate::types::StatMod;
+
+        let mut block = StatBlock::with_id("player");
+        block.fire_resistance.base = 40.0;
+        block.current_life = 30.0;
+        block.add_effect(Effect::new_stat_modifier(
+            "fortify",
+            "Fortify",
+            8.0,
+            false,
+            vec![StatMod {
+                stat: StatType::AddedArmour,
+                value_per_stack: 50.0,
+                is_more: false,
+            }],
+            "player",
+        ));
+        block.add_effect(Effect::poison(12.0, "enemy"));
+
+        let path =
+            std::env::temp_dir().join(format!("stat_core_block_{}.json", std::process::id()));
+        block.save(&path).unwrap();
+        let loaded = StatBlock::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        let loaded = loaded.unwrap();
+
+        assert_eq!(loaded.effects.len(), 2);
+        assert_eq!(
+            serde_json::to_string(&loaded).unwrap(),
+            serde_json::to_string(&block).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_cleanse_removes_only_ailments() {
+        use crate::types::StatMod;
+
+        let armour = |value: f64| {
+            vec![StatMod {
+                stat: StatType::AddedArmour,
+                value_per_stack: value,
+                is_more: false,
+            }]
+        };
+        let mut block = StatBlock::new();
+        block.add_effect(Effect::new_stat_modifier(
+            "fortify",
+            "Fortify",
+            10.0,
+            false,
+            armour(50.0),
+            "self",
+        ));
+        block.add_effect(Effect::new_stat_modifier(
+            "sunder",
+            "Sunder",
+            10.0,
+            true,
+            armour(-50.0),
+            "enemy",
+        ));
+        block.add_effect(Effect::poison(20.0, "enemy"));
+        block.add_effect(Effect::poison(20.0, "enemy"));
+
+        assert_eq!(block.cleanse_ailments(), 2);
+        assert_eq!(block.effects.len(), 2);
+        assert!(block.effects.iter().all(|e| e.is_stat_modifier()));
+        assert_eq!(block.cleanse_ailments(), 0);
+
+        assert_eq!(block.cleanse_debuffs(), 1);
+        assert_eq!(block.effects.len(), 1);
+        assert_eq!(block.effects[0].id, "fortify");
+
+        assert_eq!(block.remove_effects_where(|e| e.source_id == "self"), 1);
+        assert!(block.effects.is_empty());
+    }
+
+    #[test]
+    fn test_migrate_legacy_effects() {
+        let mut block = StatBlock::new();
+        block.max_life = StatValue::with_base(1000.0);
+        block.current_life = 1000.0;
+        block.active_dots.push(ActiveDoT::new(
+            "ignite".to_string(),
+            "attacker".to_string(),
+            DamageType::Fire,
+            25.0,
+            0.5,
+            4.0,
+        ));
+        block.active_dots.push(ActiveDoT::new(
+            "custom".to_string(),
+            "attacker".to_string(),
+            DamageType::Fire,
+            10.0,
+            1.0,
+            4.0,
+        ));
+        block.active_status_effects.push(ActiveStatusEffect::new(
+            StatusEffect::Freeze,
+            0.5,
+            1.0,
+            "attacker".to_string(),
+        ));
+
+        assert_eq!(block.migrate_legacy_effects(), 2);
+        assert!(block.active_status_effects.is_empty());
+        assert_eq!(block.active_dots.len(), 1);
+        assert!((block.effects_of_status(StatusEffect::Burn)[0].dps() - 50.0).abs() < 0.01);
+        assert!(block.is_frozen());
+
+        // Ticking runs the migrated burn through the unified path
+        let (_, tick) = block.tick_effects(1.0);
+        assert!((tick.dot_damage - 50.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_resistance_reduction_lasts_through_rebuild_then_wears_off() {
+        let mut defender = StatBlock::new();
+        defender.max_life = StatValue::with_base(1000.0);
+        defender.current_life = 1000.0;
+
+        let mut packet = DamagePacket::new("attacker".to_string(), "curse".to_string());
+        packet.resistance_reduction_applied.chaos = 20.0;
+        let (mut defender, _) = defender.receive_damage(&packet);
+        defender.add_effect(Effect::poison(100.0, "attacker"));
+
+        // The reduction survives a rebuild and worsens poison ticks: -20% chaos resistance
+        defender.rebuild();
+        assert!((defender.effective_resistance(DamageType::Chaos) + 20.0).abs() < f64::EPSILON);
+        let (defender, result) = defender.tick_effects(0.33);
+        assert!((result.dot_damage - 33.0 * 1.2).abs() < 0.01);
+
+        // Once it wears off, new poison ticks are unmitigated again
+        let (mut defender, _) = defender.tick_effects(4.0);
+        assert!(defender.resistance_reduction.is_empty());
+        defender.add_effect(Effect::poison(100.0, "attacker"));
+        let (_, result) = defender.tick_effects(0.33);
+        assert!((result.dot_damage - 33.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_dot_damage_taken_reduced() {
+        let mut block = StatBlock::new();
+        block.max_life = StatValue::with_base(1000.0);
+        block.current_life = 1000.0;
+        block.dot_damage_taken_reduced = 0.30;
+        block.add_effect(Effect::poison(100.0, "attacker"));
+
+        // One poison tick: 100 DPS * 0.33s = 33 damage, 30% reduced
+        let (after, result) = block.tick_effects(0.33);
+        assert!((result.dot_damage - 33.0 * 0.70).abs() < 0.01);
+        assert!((after.current_life - (1000.0 - 33.0 * 0.70)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_damage_taken_reduction_applies_to_dots_but_armour_does_not() {
+        let mut block = StatBlock::new();
+        block.max_life = StatValue::with_base(1000.0);
+        block.current_life = 1000.0;
+        block.armour = StatValue::with_base(10_000.0);
+        block.add_effect(Effect::poison(100.0, "attacker"));
+
+        // Armour does not touch the poison tick
+        let (_, result) = block.tick_effects(0.33);
+        assert!((result.dot_damage - 33.0).abs() < 0.01);
+
+        block.damage_taken_reduction = 0.10;
+        let (_, result) = block.tick_effects(0.33);
+        assert!((result.dot_damage - 33.0 * 0.90).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_poison_tick_respects_chaos_resistance() {
+        let mut block = StatBlock::new();
+        block.max_life = StatValue::with_base(1000.0);
+        block.current_life = 1000.0;
+        block.chaos_resistance = StatValue::with_base(50.0);
+        block.add_effect(Effect::poison(100.0, "attacker"));
+
+        // 33 raw damage, halved by 50% chaos resistance
+        let (_, result) = block.tick_effects(0.33);
+        assert!((result.dot_damage - 16.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_burn_tick_amplified_by_negative_fire_resistance() {
+        let mut block = StatBlock::new();
+        block.max_life = StatValue::with_base(1000.0);
+        block.current_life = 1000.0;
+        block.fire_resistance = StatValue::with_base(-50.0);
+        block.add_effect(Effect::burn(100.0, "attacker"));
+
+        // 50 raw damage per 0.5s tick, 50% more at -50% fire resistance
+        let (_, result) = block.tick_effects(0.5);
+        assert!((result.dot_damage - 75.0).abs() < 0.01);
+    }
+
+    fn test_weapon(
+        base_type_id: &str,
+        physical: (i32, i32),
+        attack_speed: f32,
+        critical_chance: f32,
+    ) -> Item {
+        Item {
+            damage: Some(loot_core::item::WeaponDamage {
+                damages: vec![loot_core::item::DamageRange {
+                    damage_type: DamageType::Physical,
+                    min: physical.0,
+                    max: physical.1,
+                }],
+                attack_speed,
+                critical_chance,
+            }),
+            ..test_item(base_type_id)
+        }
+    }
+
+    fn local_modifier(
+        stat: StatType,
+        value: i32,
+        value_max: Option<i32>,
+    ) -> loot_core::item::Modifier {
+        loot_core::item::Modifier {
+            affix_id: format!("local_{:?}", stat),
+            name: format!("{:?}", stat),
+            stat,
+            scope: loot_core::types::AffixScope::Local,
+            tier: 1,
+            value,
+            value_max,
+        }
+    }
+
+    fn test_item(base_type_id: &str) -> Item {
+        Item {
+            seed: 12345,
+            operations: vec![],
+            base_type_id: base_type_id.to_string(),
+            name: "Test Item".to_string(),
+            base_name: "Item".to_string(),
+            class: loot_core::types::ItemClass::OneHandSword,
+            rarity: loot_core::types::Rarity::Normal,
+            tags: vec![],
+            requirements: loot_core::types::Requirements::default(),
+            implicit: None,
+            prefixes: vec![],
+            suffixes: vec![],
+            defenses: loot_core::item::Defenses::default(),
+            damage: None,
+        }
+    }
+
+    #[test]
+    fn test_item_buff_follows_equipment() {
+        let mut block = StatBlock::new();
+        let buff = BuffSource::new(
+            "set_bonus".to_string(),
+            "Set Bonus".to_string(),
+            f64::INFINITY,
+            false,
+        )
+        .with_modifier(StatType::AddedArmour, 150.0, false);
+
+        block
+            .equip_with_buff(EquipmentSlot::Amulet, test_item("amulet"), buff)
+            .unwrap();
+        assert!(block.item_buff(EquipmentSlot::Amulet).is_some());
+        assert!((block.armour.compute() - 150.0).abs() < 0.01);
+
+        // Buff survives unrelated rebuilds
+        block.apply_buff(
+            BuffSource::new("haste".to_string(), "Haste".to_string(), 10.0, false).with_modifier(
+                StatType::IncreasedAttackSpeed,
+                20.0,
+                false,
+            ),
+        );
+        assert!((block.armour.compute() - 150.0).abs() < 0.01);
+
+        block.unequip(EquipmentSlot::Amulet);
+        assert!(block.item_buff(EquipmentSlot::Amulet).is_none());
+        assert!((block.armour.compute() - 0.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_equip_loop_only_recomputes_changed_slot() {
+        let armour_buff = |id: &str, value: f64| {
+            BuffSource::new(id.to_string(), id.to_string(), f64::INFINITY, false).with_modifier(
+                StatType::AddedArmour,
+                value,
+                false,
+            )
+        };
+
+        let mut block = StatBlock::new();
+        block
+            .equip_with_buff(
+                EquipmentSlot::Amulet,
+                test_item("amulet"),
+                armour_buff("amulet", 100.0),
+            )
+            .unwrap();
+        block
+            .equip_with_buff(
+                EquipmentSlot::Helmet,
+                test_item("helmet"),
+                armour_buff("helmet", 50.0),
+            )
+            .unwrap();
+
+        for i in 0..200 {
+            block
+                .equip_with_buff(
+                    EquipmentSlot::Ring1,
+                    test_item("ring"),
+                    armour_buff("ring", i as f64),
+                )
+                .unwrap();
+            assert!((block.armour.compute() - (150.0 + i as f64)).abs() < 0.01);
+            block.unequip(EquipmentSlot::Ring1);
+            assert!((block.armour.compute() - 150.0).abs() < 0.01);
+        }
+
+        // Tamper with the amulet's cached contribution: swapping the ring keeps
+        // it, proving the amulet's GearSource is not reapplied from scratch
+        block
+            .gear_cache
+            .get_mut(&EquipmentSlot::Amulet)
+            .unwrap()
+            .armour_flat += 1000.0;
+        block
+            .equip_with_buff(
+                EquipmentSlot::Ring1,
+                test_item("ring"),
+                armour_buff("ring", 10.0),
+            )
+            .unwrap();
+        assert!((block.armour.compute() - 1160.0).abs() < 0.01);
+
+        // A full rebuild (e.g. from a buff change) restores the real values
+        block.apply_buff(armour_buff("aura", 0.0));
+        assert!((block.armour.compute() - 160.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_es_regen_from_life_regen() {
+        let mut block = StatBlock::new();
+        block.life_regen.add_flat(10.0);
+        block.set_max_energy_shield(100.0);
+        block.es_regen_from_life_regen = 0.5;
+
+        // Half of 10 life regen per second
+        let restored = block.tick_energy_shield(1.0);
+        assert!((restored - 5.0).abs() < 0.01);
+        assert!((block.current_energy_shield - 5.0).abs() < 0.01);
+
+        block.tick_energy_shield(2.0);
+        assert!((block.current_energy_shield - 15.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_guard_and_ward_kept_across_rebuild() {
+        let mut block = StatBlock::new();
+        block.apply_guard(50.0);
+        block.apply_ward(30.0);
+
+        block.rebuild();
+        assert!((block.current_guard - 50.0).abs() < f64::EPSILON);
+        assert!((block.current_ward - 30.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_es_recharge_delay_kept_across_rebuild() {
+        let mut block = StatBlock::new();
+        block.es_recharge_delay_remaining = 1.5;
+
+        block.rebuild();
+        assert!((block.es_recharge_delay_remaining - 1.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_rebuild_resets_only_source_stats() {
+        let mut block = StatBlock::new();
+        block.armour.base = 300.0;
+        let fortify = BuffSource::new("fortify".to_string(), "Fortify".to_string(), 5.0, false)
+            .with_modifier(StatType::AddedArmour, 50.0, false);
+        block.apply_buff(fortify);
+
+        // Repeated rebuilds do not stack source contributions
+        block.rebuild();
+        block.rebuild();
+        assert!((block.armour.compute() - 350.0).abs() < f64::EPSILON);
+
+        // Bases set directly survive once the source is gone
+        block.remove_buff("fortify");
+        assert!((block.armour.compute() - 300.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_es_recharges_after_delay() {
+        let mut constants = GameConstants::default();
+        constants.energy_shield.recharge_delay = 2.0;
+        constants.energy_shield.recharge_rate = 0.2;
+
+        let mut block = StatBlock::new();
+        block.current_life = 1000.0;
+        block.set_max_energy_shield(100.0);
+        block.apply_energy_shield(100.0);
+
+        let mut packet = DamagePacket::new("attacker".to_string(), "hit".to_string());
+        packet.add_damage(DamageType::Fire, 60.0);
+        let (mut block, result) = crate::combat::resolve_damage_with_constants(
+            &block,
+            &packet,
+            &constants,
+            &mut rand::thread_rng(),
+        );
+        assert!((result.damage_blocked_by_es - 60.0).abs() < 0.01);
+
+        // Within the delay window: no recharge
+        assert!(
+            block
+                .tick_energy_shield_with_constants(1.5, &constants)
+                .abs()
+                < f64::EPSILON
+        );
+        assert!((block.current_energy_shield - 40.0).abs() < 0.01);
+
+        // 0.5s of delay left, then 0.5s at 20%/s of 100
+        let restored = block.tick_energy_shield_with_constants(1.0, &constants);
+        assert!((restored - 10.0).abs() < 0.01);
+
+        // Fully recovers afterward, capped at max
+        block.tick_energy_shield_with_constants(10.0, &constants);
+        assert!((block.current_energy_shield - 100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_tick_regen() {
+        let mut block = StatBlock::new();
+        block.life_regen.add_flat(4.0);
+        block.mana_regen.add_flat(2.0);
+        block.current_life = 10.0;
+        block.current_mana = 10.0;
+
+        // Partial-second tick
+        block.tick_regen(0.25);
+        assert!((block.current_life - 11.0).abs() < 0.01);
+        assert!((block.current_mana - 10.5).abs() < 0.01);
+
+        // Capped at max
+        block.tick_regen(100.0);
+        assert!((block.current_life - block.computed_max_life()).abs() < 0.01);
+        assert!((block.current_mana - block.unreserved_max_mana()).abs() < 0.01);
+
+        // Dead entities do not regenerate
+        block.current_life = 0.0;
+        block.current_mana = 0.0;
+        block.tick_regen(1.0);
+        assert!((block.current_life - 0.0).abs() < f64::EPSILON);
+        assert!((block.current_mana - 0.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_set_resource_percent() {
+        let mut block = StatBlock::new();
+        block.set_max_energy_shield(80.0);
+
+        block.set_life_percent(0.5);
+        assert!((block.current_life - 0.5 * block.computed_max_life()).abs() < f64::EPSILON);
+
+        block.set_mana_percent(0.25);
+        assert!((block.current_mana - 0.25 * block.computed_max_mana()).abs() < f64::EPSILON);
+
+        block.set_es_percent(2.0);
+        assert!((block.current_energy_shield - 80.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_requirement_penalty_mode() {
+        let mut item = test_item("heavy_plate");
+        item.requirements = loot_core::types::Requirements {
+            strength: 100,
+            ..Default::default()
+        };
+        item.defenses = loot_core::item::Defenses {
+            armour: Some(100),
+            ..Default::default()
+        };
+
+        // Base strength 10 vs 100 required
+        let mut block = StatBlock::new();
+        assert!((block.requirement_satisfaction(&item) - 0.1).abs() < 0.001);
+
+        // Ignore mode: full contribution
+        block
+            .equip(EquipmentSlot::BodyArmour, item.clone())
+            .unwrap();
+        assert!((block.armour.compute() - 100.0).abs() < 0.01);
+
+        // Penalty mode: contribution scaled by satisfaction
+        let mut penalized = StatBlock::new();
+        penalized.requirement_mode = RequirementMode::Penalty;
+        penalized.equip(EquipmentSlot::BodyArmour, item).unwrap();
+        assert!((penalized.armour.compute() - 10.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_try_equip_checks_requirements() {
+        let mut item = test_item("great_axe");
+        item.requirements = loot_core::types::Requirements {
+            strength: 45,
+            intelligence: 20,
+            ..Default::default()
+        };
+
+        // Base 10 Str / 10 Int: both requirements unmet, nothing equipped
+        let mut block = StatBlock::new();
+        let err = block
+            .try_equip(EquipmentSlot::MainHand, item.clone())
+            .unwrap_err();
+        let EquipError::RequirementsNotMet(shortfalls, _) = &err else {
+            panic!("expected unmet requirements, got {err:?}");
+        };
+        assert_eq!(shortfalls.len(), 2);
+        assert!((shortfalls[0].missing() - 35.0).abs() < f64::EPSILON);
+        assert_eq!(err.to_string(), "Requires 45 Str, 20 Int");
+        assert!(block.equipped(EquipmentSlot::MainHand).is_none());
+        assert_eq!(err.into_item().base_type_id, item.base_type_id);
+
+        // Meeting the requirements allows the equip
+        block.strength.base = 45.0;
+        block.intelligence.base = 20.0;
+        assert!(block.try_equip(EquipmentSlot::MainHand, item).is_ok());
+        assert!(block.equipped(EquipmentSlot::MainHand).is_some());
+    }
+
+    #[test]
+    fn test_can_use_skill_checks_main_hand() {
+        use loot_core::types::ItemClass;
+
+        let slash = DamagePacketGenerator::basic_attack();
+        let mut block = StatBlock::new();
+        assert_eq!(block.can_use_skill(&slash), Err(SkillError::NoWeapon));
+
+        block
+            .equip(EquipmentSlot::MainHand, test_item("sword"))
+            .unwrap();
+        assert_eq!(block.can_use_skill(&slash), Ok(()));
+
+        // A bow skill rejects the sword, and its error names the bow
+        let shot = DamagePacketGenerator {
+            required_weapon_classes: vec![ItemClass::Bow],
+            ..Default::default()
+        };
+        let err = block.can_use_skill(&shot).unwrap_err();
+        assert_eq!(err, SkillError::WrongWeapon(vec![ItemClass::Bow]));
+        assert!(err.to_string().contains("Bow"));
+
+        let mut bow = test_item("bow");
+        bow.class = ItemClass::Bow;
+        block.equip(EquipmentSlot::MainHand, bow).unwrap();
+        assert_eq!(block.can_use_skill(&shot), Ok(()));
+
+        // Spells need no weapon
+        let spell = DamagePacketGenerator {
+            tags: vec![crate::types::SkillTag::Spell],
+            ..Default::default()
+        };
+        assert_eq!(StatBlock::new().can_use_skill(&spell), Ok(()));
+    }
+
+    #[test]
+    fn test_two_handed_weapon_frees_off_hand() {
+        let mut shield = test_item("shield");
+        shield.defenses = loot_core::item::Defenses {
+            armour: Some(80),
+            ..Default::default()
+        };
+        let mut great_sword = test_item("great_sword");
+        great_sword.class = loot_core::types::ItemClass::TwoHandSword;
+        assert!(is_two_handed(&great_sword.class));
+        let shield_id = Some("shield".to_string());
+
+        let mut block = StatBlock::new();
+        assert!(block
+            .equip(EquipmentSlot::MainHand, test_item("sword"))
+            .unwrap()
+            .is_none());
+        assert!(block
+            .equip(EquipmentSlot::OffHand, shield.clone())
+            .unwrap()
+            .is_none());
+        assert!((block.armour.compute() - 80.0).abs() < 0.01);
+
+        // Two-hander pushes the shield out
+        let displaced = block
+            .equip(EquipmentSlot::MainHand, great_sword.clone())
+            .unwrap();
+        assert_eq!(displaced.map(|item| item.base_type_id), shield_id);
+        assert!(block.equipped(EquipmentSlot::OffHand).is_none());
+        assert!(block.armour.compute().abs() < 0.01);
+
+        // Off hand stays blocked while the two-hander is held
+        assert!(block.off_hand_blocked());
+        let refused = block.equip(EquipmentSlot::OffHand, shield.clone());
+        assert_eq!(refused.err().map(|item| item.base_type_id), shield_id);
+        assert!(block.equipped(EquipmentSlot::OffHand).is_none());
+        let err = block
+            .try_equip(EquipmentSlot::OffHand, shield.clone())
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            EquipError::SlotBlocked(EquipmentSlot::OffHand, _)
+        ));
+        assert_eq!(err.into_item().base_type_id, shield.base_type_id);
+
+        // try_equip hands back the off-hand item a two-hander displaces
+        block
+            .equip(EquipmentSlot::MainHand, test_item("sword"))
+            .unwrap();
+        block.equip(EquipmentSlot::OffHand, shield).unwrap();
+        let displaced = block
+            .try_equip(EquipmentSlot::MainHand, great_sword)
+            .unwrap();
+        assert_eq!(displaced.map(|item| item.base_type_id), shield_id);
+    }
+
+    #[test]
+    fn test_dual_wield_blends_both_weapons() {
+        // Main hand: 10-20 phys with 50% local increased, 1.5 APS, 5% crit
+        let mut main_hand = test_weapon("main_sword", (10, 20), 1.5, 5.0);
+        main_hand.prefixes = vec![local_modifier(StatType::IncreasedPhysicalDamage, 50, None)];
+        // Off hand: 20-30 phys, 10-10 local added fire, 1.3 APS, 7% crit
+        let mut off_hand = test_weapon("off_sword", (20, 30), 1.3, 7.0);
+        off_hand.prefixes = vec![local_modifier(StatType::AddedFireDamage, 10, Some(10))];
+
+        let mut block = StatBlock::new();
+        assert!(block
+            .equip(EquipmentSlot::MainHand, main_hand)
+            .unwrap()
+            .is_none());
+        assert!(block
+            .equip(EquipmentSlot::OffHand, off_hand)
+            .unwrap()
+            .is_none());
+
+        assert!((block.weapon_physical_min - (15.0 + 20.0) / 2.0).abs() < 0.01);
+        assert!((block.weapon_physical_max - (30.0 + 30.0) / 2.0).abs() < 0.01);
+        assert!((block.weapon_fire_min - 5.0).abs() < 0.01);
+        assert!(
+            (block.weapon_attack_speed - 1.4 * (1.0 + DUAL_WIELD_ATTACK_SPEED_BONUS)).abs() < 0.001
+        );
+        assert!((block.weapon_crit_chance - 6.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_strength_from_gear_grants_life_and_damage() {
+        let mut block = StatBlock::new();
+        block.rebuild();
+        let base_life = block.computed_max_life();
+        let base_phys = block.global_physical_damage.total_increased_multiplier();
+
+        // 25 strength above base = 2 full steps
+        let strength = loot_core::item::Modifier {
+            scope: loot_core::types::AffixScope::Global,
+            ..local_modifier(StatType::AddedStrength, 25, None)
+        };
+        let ring = Item {
+            prefixes: vec![strength],
+            ..test_item("iron_ring")
+        };
+        block.equip(EquipmentSlot::Ring1, ring).unwrap();
+
+        assert!((block.computed_max_life() - base_life - 10.0).abs() < 0.01);
+        let phys_gain = block.global_physical_damage.total_increased_multiplier() - base_phys;
+        assert!((phys_gain - 0.04).abs() < 0.001);
+
+        // Tunable ratios
+        block.attribute_constants.life_per_strength = 10.0;
+        block.rebuild();
+        assert!((block.computed_max_life() - base_life - 20.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_armour_as_evasion() {
+        let mut hybrid = test_item("hybrid_unique");
+        hybrid.defenses = loot_core::item::Defenses {
+            armour: Some(200),
+            evasion: Some(100),
+            ..Default::default()
+        };
+        let item_effect = BuffSource::new(
+            "hybrid".to_string(),
+            "Hybrid".to_string(),
+            f64::INFINITY,
+            false,
+        )
+        .with_modifier(StatType::IncreasedArmour, 50.0, false)
+        .with_armour_as_evasion(0.10);
+
+        let mut block = StatBlock::new();
+        block
+            .equip_with_buff(EquipmentSlot::Helmet, hybrid, item_effect)
+            .unwrap();
+
+        // Armour: 200 * 1.5 = 300, unchanged by the conversion
+        assert!((block.armour.compute() - 300.0).abs() < 0.01);
+        // Evasion: 100 + 10% of 300 armour = 130
+        assert!((block.evasion.compute() - 130.0).abs() < 0.01);
+    }
+
+    struct ArmourMoreSource;
+
+    impl StatSource for ArmourMoreSource {
+        fn id(&self) -> &str {
+            "armour_more"
+        }
+
+        fn apply(&self, stats: &mut StatAccumulator) {
+            stats.apply_more_stat_type(StatType::IncreasedArmour, 50.0);
+        }
+    }
+
+    struct BaseArmourSource;
+
+    impl StatSource for BaseArmourSource {
+        fn id(&self) -> &str {
+            "base_armour"
+        }
+
+        fn apply(&self, stats: &mut StatAccumulator) {
+            stats.armour_flat += 100.0;
+        }
+    }
+
+    #[test]
+    fn test_low_life_conditional_source() {
+        use crate::source::{Condition, ConditionalSource};
+
+        let mut block = StatBlock::new();
+        let sources: Vec<Box<dyn StatSource>> = vec![Box::new(ConditionalSource::new(
+            Condition::LowLife { threshold: 0.35 },
+            Box::new(BaseArmourSource),
+        ))];
+
+        // Below the threshold: bonus applies, and the rebuild does not heal
+        let low_life = block.computed_max_life() * 0.30;
+        block.current_life = low_life;
+        block.rebuild_from_sources(&sources);
+        assert!((block.armour.compute() - 100.0).abs() < 0.01);
+        assert!((block.current_life - low_life).abs() < f64::EPSILON);
+
+        // Still low on the next rebuild
+        block.rebuild_from_sources(&sources);
+        assert!((block.armour.compute() - 100.0).abs() < 0.01);
+        assert!((block.current_life - low_life).abs() < f64::EPSILON);
+
+        // Back above it: bonus is gone
+        block.current_life = block.computed_max_life() * 0.50;
+        block.rebuild_from_sources(&sources);
+        assert!(block.armour.compute().abs() < 0.01);
+    }
+
+    #[test]
+    fn test_try_attack_spends_mana_only_when_affordable() {
+        let mut block = StatBlock::new();
+        block.current_mana = 30.0;
+        let skill = DamagePacketGenerator {
+            mana_cost: 20.0,
+            ..Default::default()
+        };
+
+        assert!(block.can_afford(&skill));
+        assert!(block
+            .try_attack(&skill, DotRegistry::shared_defaults())
+            .is_some());
+        assert!((block.current_mana - 10.0).abs() < f64::EPSILON);
+
+        assert!(!block.can_afford(&skill));
+        assert!(block
+            .try_attack(&skill, DotRegistry::shared_defaults())
+            .is_none());
+        assert!((block.current_mana - 10.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_action_speed_floor() {
+        let mut block = StatBlock::new();
+        block.attack_speed.add_increased(-5.0);
+        block.cast_speed.add_increased(-5.0);
+        assert!((block.attack_speed.compute() - MIN_ACTION_SPEED).abs() < f64::EPSILON);
+        assert!((block.cast_speed.compute() - MIN_ACTION_SPEED).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_movement_speed_cap() {
+        let mut block = StatBlock::new();
+        let sprint = BuffSource::new("sprint".to_string(), "Sprint".to_string(), 5.0, false)
+            .with_modifier(StatType::IncreasedMovementSpeed, 150.0, false);
+        block.apply_buff(sprint);
+        assert!(
+            (block.movement_speed_increased.compute() - MAX_MOVEMENT_SPEED_INCREASED).abs()
+                < f64::EPSILON
+        );
+
+        // Saves from when movement speed was a plain number still load, with the cap
+        let mut json = serde_json::to_value(StatBlock::new()).unwrap();
+        json["movement_speed_increased"] = serde_json::json!(2.5);
+        let loaded: StatBlock = serde_json::from_value(json).unwrap();
+        assert!(
+            (loaded.movement_speed_increased.compute() - MAX_MOVEMENT_SPEED_INCREASED).abs()
+                < f64::EPSILON
+        );
+    }
+
+    #[test]
+    fn test_more_armour_sources_multiply() {
+        let mut block = StatBlock::new();
+        let sources: Vec<Box<dyn StatSource>> = vec![
+            Box::new(BaseArmourSource),
+            Box::new(ArmourMoreSource),
+            Box::new(ArmourMoreSource),
+        ];
+        block.rebuild_from_sources(&sources);
+
+        // 100 × 1.5 × 1.5 = 225, not 100 × 2.0
+        assert!((block.armour.compute() - 225.0).abs() < 0.01);
+    }
+
+    struct EnergyShieldSource;
+
+    impl StatSource for EnergyShieldSource {
+        fn id(&self) -> &str {
+            "energy_shield"
+        }
+
+        fn apply(&self, stats: &mut StatAccumulator) {
+            stats.apply_stat_type(StatType::AddedEnergyShield, 100.0);
+            stats.apply_stat_type(StatType::IncreasedEnergyShield, 20.0);
+            assert!(stats.apply_more_stat_type(StatType::IncreasedEnergyShield, 50.0));
+            stats.cast_speed_more.push(0.25);
+        }
+    }
+
+    #[test]
+    fn test_more_energy_shield_and_cast_speed() {
+        let mut block = StatBlock::new();
+        let base_cast_speed = block.cast_speed.compute();
+        let sources: Vec<Box<dyn StatSource>> = vec![Box::new(EnergyShieldSource)];
+        block.rebuild_from_sources(&sources);
+
+        // 100 × 1.2 × 1.5 = 180
+        assert!((block.max_energy_shield - 180.0).abs() < 0.01);
+        assert!((block.cast_speed.compute() - base_cast_speed * 1.25).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_flask_applies_while_active_and_expires() {
+        let mut block = StatBlock::new();
+        let base_life = block.computed_max_life();
+        let mut flask = FlaskSource::new(
+            "granite".to_string(),
+            "Granite Flask".to_string(),
+            4.0,
+            60,
+            30,
+        )
+        .with_modifier(StatType::AddedLife, 100.0);
+
+        assert!(block.use_flask(&mut flask));
+        assert_eq!(flask.charges, 30);
+        assert!((block.computed_max_life() - base_life - 100.0).abs() < 0.01);
+
+        // Still active part-way through
+        block.tick_flasks(3.0);
+        assert!((block.computed_max_life() - base_life - 100.0).abs() < 0.01);
+
+        // Expired: bonus removed
+        block.tick_flasks(1.5);
+        assert!(block.active_flasks().is_empty());
+        assert!((block.computed_max_life() - base_life).abs() < 0.01);
+
+        // Charges allow one more use, then run out
+        assert!(block.use_flask(&mut flask));
+        assert!(!block.use_flask(&mut flask));
+        assert_eq!(block.active_flasks().len(), 1);
+    }
+
+    #[test]
+    fn test_attack_seeded_is_reproducible() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        let mut attacker = StatBlock::new();
+        attacker.weapon_physical_min = 10.0;
+        attacker.weapon_physical_max = 90.0;
+        attacker.critical_chance.add_flat(30.0);
+        let skill = DamagePacketGenerator::basic_attack();
+        let registry = DotRegistry::with_defaults();
+
+        let mut first = ChaCha8Rng::seed_from_u64(42);
+        let mut second = ChaCha8Rng::seed_from_u64(42);
+        for _ in 0..10 {
+            let a = attacker.attack_seeded(&skill, &registry, &mut first);
+            let b = attacker.attack_seeded(&skill, &registry, &mut second);
+            assert_eq!(
+                serde_json::to_string(&a).unwrap(),
+                serde_json::to_string(&b).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_buffs_survive_serialization() {
+        let mut block = StatBlock::with_id("player");
+        block.apply_buff(rage(25.0, 10.0, BuffStacking::default()).with_stacks(2));
+        let before = serde_json::to_string(&block).unwrap();
+
+        let mut loaded: StatBlock = serde_json::from_str(&before).unwrap();
+        loaded.post_load();
+        assert_eq!(loaded.active_buff_sources().len(), 1);
+        assert_eq!(loaded.active_buff_sources()[0].stacks, 2);
+        assert_eq!(serde_json::to_string(&loaded).unwrap(), before);
+
+        // Later rebuilds still include the buff
+        let haste = BuffSource::new("haste".to_string(), "Haste".to_string(), 5.0, false)
+            .with_modifier(StatType::IncreasedAttackSpeed, 10.0, false);
+        loaded.apply_buff(haste.clone());
+        block.apply_buff(haste);
+        assert_eq!(
+            serde_json::to_string(&loaded).unwrap(),
+            serde_json::to_string(&block).unwrap()
+        );
+        assert!((loaded.global_physical_damage.total_increased_multiplier() - 1.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_buff_without_duration_loads() {
+        let mut block = StatBlock::with_id("player");
+        block.apply_buff(rage(25.0, 10.0, BuffStacking::default()));
+        block.tick_buffs(4.0);
+
+        // Saves from before `duration` existed only have `duration_remaining`
+        let mut json = serde_json::to_value(&block).unwrap();
+        json["buff_sources"][0]
+            .as_object_mut()
+            .unwrap()
+            .remove("duration");
+
+        let mut loaded: StatBlock = serde_json::from_value(json).unwrap();
+        loaded.post_load();
+        let buff = &loaded.active_buff_sources()[0];
+        assert!((buff.duration - buff.duration_remaining).abs() < f64::EPSILON);
+        assert!((buff.duration - 6.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_load_keeps_life_and_effects() {
+        let mut block = StatBlock::with_id("player");
+        block.max_life.base = 200.0;
+        block.apply_buff(rage(25.0, 10.0, BuffStacking::default()));
+        block.current_life = 120.0;
+        block.current_mana = 15.0;
+        block.add_effect(Effect::poison(12.0, "enemy"));
+
+        let path =
+            std::env::temp_dir().join(format!("stat_core_buffed_{}.json", std::process::id()));
+        block.save(&path).unwrap();
+        let loaded = StatBlock::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        let loaded = loaded.unwrap();
+
+        assert!((loaded.current_life - 120.0).abs() < f64::EPSILON);
+        assert!((loaded.current_mana - 15.0).abs() < f64::EPSILON);
+        assert_eq!(loaded.effects.len(), 1);
+        assert_eq!(loaded.effects_of_status(StatusEffect::Poison).len(), 1);
+        assert_eq!(loaded.active_buff_sources().len(), 1);
+    }
+
+    fn rage(value: f64, duration: f64, stacking: BuffStacking) -> BuffSource {
+        BuffSource::new("rage".to_string(), "Rage".to_string(), duration, false)
+            .with_modifier(StatType::IncreasedPhysicalDamage, value, false)
+            .with_stacking(stacking)
+    }
+
+    #[test]
+    fn test_buff_stacking_refresh() {
+        let mut block = StatBlock::new();
+        block.apply_buff(rage(10.0, 5.0, BuffStacking::Refresh));
+        block.tick_buffs(3.0);
+        block.apply_buff(rage(10.0, 5.0, BuffStacking::Refresh));
+
+        let buffs = block.active_buff_sources();
+        assert_eq!(buffs.len(), 1);
+        assert_eq!(buffs[0].stacks, 1);
+        assert!((buffs[0].duration_remaining - 5.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_buff_stacking_stack_capped() {
+        let mut block = StatBlock::new();
+        let stacking = BuffStacking::Stack { max_stacks: 2 };
+        block.apply_buff(rage(10.0, 5.0, stacking));
+        block.apply_buff(rage(10.0, 5.0, stacking));
+        assert_eq!(block.active_buff_sources()[0].stacks, 2);
+
+        block.tick_buffs(3.0);
+        block.apply_buff(rage(10.0, 5.0, stacking));
+        let buffs = block.active_buff_sources();
+        assert_eq!(buffs.len(), 1);
+        assert_eq!(buffs[0].stacks, 2);
+        assert!((buffs[0].duration_remaining - 5.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_buff_stacking_strongest_only() {
+        let mut block = StatBlock::new();
+        block.apply_buff(rage(20.0, 5.0, BuffStacking::StrongestOnly));
+
+        // Weaker application is ignored
+        block.apply_buff(rage(10.0, 8.0, BuffStacking::StrongestOnly));
+        let buffs = block.active_buff_sources();
+        assert_eq!(buffs.len(), 1);
+        assert!((buffs[0].magnitude() - 20.0).abs() < 0.01);
+        assert!((buffs[0].duration_remaining - 5.0).abs() < 0.01);
+
+        // Stronger application replaces it
+        block.apply_buff(rage(30.0, 8.0, BuffStacking::StrongestOnly));
+        let buffs = block.active_buff_sources();
+        assert_eq!(buffs.len(), 1);
+        assert!((buffs[0].magnitude() - 30.0).abs() < 0.01);
+        assert!((buffs[0].duration_remaining - 8.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_buff_stacking_independent() {
+        let mut block = StatBlock::new();
+        block.apply_buff(rage(10.0, 5.0, BuffStacking::Independent));
+        block.apply_buff(rage(10.0, 2.0, BuffStacking::Independent));
+        assert_eq!(block.active_buff_sources().len(), 2);
+
+        // Each instance expires on its own
+        block.tick_buffs(3.0);
+        let buffs = block.active_buff_sources();
+        assert_eq!(buffs.len(), 1);
+        assert!((buffs[0].duration_remaining - 2.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_buff_stacks_multiply_contribution() {
+        let mut block = StatBlock::new();
+        let base_strength = block.strength.compute();
+        for _ in 0..3 {
+            block.apply_buff(
+                BuffSource::new("rage".to_string(), "Rage".to_string(), 4.0, false)
+                    .with_modifier(StatType::AddedStrength, 10.0, false)
+                    .with_stacking(BuffStacking::Stack { max_stacks: 5 })
+                    .with_expiry(BuffExpiry::LoseStack),
+            );
+        }
+        assert!((block.strength.compute() - base_strength - 30.0).abs() < 0.01);
+
+        // One stack expires and the stats follow
+        block.tick_buffs(4.5);
+        assert_eq!(block.active_buff_sources()[0].stacks, 2);
+        assert!((block.strength.compute() - base_strength - 20.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_moving_target_doubles_bleed_but_not_poison() {
+        let mut block = StatBlock::new();
+        block.max_life = StatValue::with_base(1000.0);
+        block.current_life = 1000.0;
+        block.add_effect(Effect::bleed(100.0, "attacker"));
+
+        let (_, result) = block.tick_effects(1.0);
+        assert!((result.dot_damage - 100.0).abs() < 0.01);
+
+        block.is_moving = true;
+        let (_, result) = block.tick_effects(1.0);
+        assert!((result.dot_damage - 200.0).abs() < 0.01);
+
+        let mut block = StatBlock::new();
+        block.max_life = StatValue::with_base(1000.0);
+        block.current_life = 1000.0;
+        block.is_moving = true;
+        block.add_effect(Effect::poison(100.0, "attacker"));
+        let (_, result) = block.tick_effects(0.33);
+        assert!((result.dot_damage - 33.0).abs() < 0.01);
+    }
 }
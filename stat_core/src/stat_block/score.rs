@@ -0,0 +1,292 @@
+//! Heuristic build scores for quick comparison
+//!
+//! Scores are not used by combat; they condense a build into a single
+//! sortable number. Weights live in `GameConstants::scoring`.
+
+use crate::config::GameConstants;
+use crate::damage::{
+    calculate_effective_crit_chance, calculate_skill_dps_breakdown, DamagePacketGenerator,
+};
+use crate::defense::{
+    calculate_armour_reduction_with_cap, calculate_resistance_mitigation_with_cap,
+};
+use crate::dot::DotRegistry;
+use crate::stat_block::StatBlock;
+use crate::types::ALL_DAMAGE_TYPES;
+use loot_core::types::DamageType;
+
+impl StatBlock {
+    /// Combined offense score for a skill
+    ///
+    /// `score = hit_dps × (1 - crit_variance_weight × cv) + dot_dps`, where
+    /// `hit_dps` includes crits and `dot_dps` is the sustained ailment DPS
+    /// (both from `calculate_skill_dps_breakdown`), and `cv` is the
+    /// coefficient of variation of per-hit damage introduced by crits. Builds
+    /// that rely on rare, large crits score lower than steady builds with the
+    /// same average DPS; ailment DPS is steady and is not penalised.
+    pub fn offense_score(&self, skill: &DamagePacketGenerator, constants: &GameConstants) -> f64 {
+        let dps = calculate_skill_dps_breakdown(self, skill, DotRegistry::shared_defaults());
+        if dps.total <= 0.0 {
+            return 0.0;
+        }
+
+        let crit_chance = calculate_effective_crit_chance(self, skill);
+        let extra = (self.computed_crit_multiplier() + skill.crit_multiplier_bonus - 1.0).max(0.0);
+        let mean = 1.0 + crit_chance * extra;
+        let cv = (crit_chance * (1.0 - crit_chance)).sqrt() * extra / mean;
+
+        let reliability = (1.0 - constants.scoring.crit_variance_weight * cv).max(0.0);
+        dps.hit_dps * reliability + dps.dot_dps
+    }
+
+    /// Combined defense score
+    ///
+    /// `score = ehp + recovery_weight × (life regen + mana regen)`, where
+    /// `ehp = (max life + max ES) / average damage taken multiplier` across all
+    /// five damage types, each for a hit of `reference_hit` mitigated as in
+    /// [`StatBlock::effective_hp`].
+    pub fn defense_score(&self, constants: &GameConstants) -> f64 {
+        let pool = self.computed_max_life() + self.max_energy_shield;
+        let reference_hit = constants.scoring.reference_hit.max(1.0);
+
+        let taken: f64 = ALL_DAMAGE_TYPES
+            .iter()
+            .map(|&damage_type| {
+                self.hit_damage_taken(damage_type, reference_hit, constants) / reference_hit
+            })
+            .sum::<f64>()
+            / ALL_DAMAGE_TYPES.len() as f64;
+
+        let ehp = pool / taken.max(0.01);
+        let recovery = self.life_regen.compute() + self.mana_regen.compute();
+
+        ehp + constants.scoring.recovery_weight * recovery
+    }
+
+    /// Raw damage of one type this block can take from full life and ES before dying
+    ///
+    /// Simulates repeated hits of `incoming_hit` raw damage: physical hits
+    /// are reduced by armour (which depends on hit size) and physical damage
+    /// reduction, other types by resistance. The general damage taken
+    /// reduction applies to all types. Energy shield is not counted for types
+    /// that bypass it (`constants.energy_shield.bypass`, chaos by default), and
+    /// armour is capped at `constants.armour.max_reduction`, as in combat.
+    /// Block, evasion and recovery are ignored.
+    pub fn effective_hp(
+        &self,
+        damage_type: DamageType,
+        incoming_hit: f64,
+        constants: &GameConstants,
+    ) -> f64 {
+        let energy_shield = if constants.energy_shield.bypasses(damage_type) {
+            0.0
+        } else {
+            self.max_energy_shield
+        };
+        let pool = self.computed_max_life() + energy_shield;
+        if incoming_hit <= 0.0 {
+            return pool;
+        }
+
+        let per_hit = self.hit_damage_taken(damage_type, incoming_hit, constants);
+        if per_hit <= 0.0 {
+            return f64::INFINITY;
+        }
+        pool * incoming_hit / per_hit
+    }
+
+    /// Damage taken from one hit of `hit` raw damage of a type
+    ///
+    /// Physical is reduced by armour (capped at `constants.armour.max_reduction`)
+    /// and physical damage reduction, other types by resistance; the general
+    /// damage taken reduction applies to all types.
+    fn hit_damage_taken(
+        &self,
+        damage_type: DamageType,
+        hit: f64,
+        constants: &GameConstants,
+    ) -> f64 {
+        let after_mitigation = match damage_type {
+            DamageType::Physical => {
+                let after_armour = calculate_armour_reduction_with_cap(
+                    self.armour.compute(),
+                    hit,
+                    constants.armour.max_reduction,
+                );
+                after_armour * (1.0 - self.physical_damage_reduction.clamp(0.0, 100.0) / 100.0)
+            }
+            _ => calculate_resistance_mitigation_with_cap(
+                hit,
+                self.effective_resistance(damage_type),
+                0.0,
+                self.max_resistance(damage_type),
+            ),
+        };
+        after_mitigation * self.damage_taken_multiplier()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::damage::{BaseDamage, SkillStatusConversions};
+    use crate::types::SkillTag;
+
+    #[test]
+    fn test_better_gear_scores_higher() {
+        let constants = GameConstants::default();
+        let skill = DamagePacketGenerator {
+            id: "strike".to_string(),
+            name: "Strike".to_string(),
+            weapon_effectiveness: 1.0,
+            tags: vec![SkillTag::Attack],
+            ..Default::default()
+        };
+
+        let mut weak = StatBlock::new();
+        weak.weapon_physical_min = 10.0;
+        weak.weapon_physical_max = 20.0;
+
+        let mut strong = weak.clone();
+        strong.weapon_physical_min = 30.0;
+        strong.weapon_physical_max = 40.0;
+        strong.global_physical_damage.add_increased(0.5);
+        strong.max_life.add_flat(100.0);
+        strong.armour.add_flat(500.0);
+        strong.fire_resistance.add_flat(40.0);
+        strong.life_regen.add_flat(5.0);
+
+        assert!(strong.offense_score(&skill, &constants) > weak.offense_score(&skill, &constants));
+        assert!(strong.defense_score(&constants) > weak.defense_score(&constants));
+    }
+
+    #[test]
+    fn test_offense_score_uses_skill_crit_and_ailments() {
+        let constants = GameConstants::default();
+        let mut attacker = StatBlock::new();
+        attacker.critical_chance.add_increased(1.0);
+
+        let spell = DamagePacketGenerator {
+            id: "fireball".to_string(),
+            name: "Fireball".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Fire, 100.0, 100.0)],
+            base_crit_chance: 10.0,
+            tags: vec![SkillTag::Spell],
+            ..Default::default()
+        };
+
+        // Increased crit chance applies to the spell's base crit: 10% doubled
+        let crit_chance = calculate_effective_crit_chance(&attacker, &spell);
+        assert!((crit_chance - 0.2).abs() < 1e-9);
+
+        let hit_dps =
+            calculate_skill_dps_breakdown(&attacker, &spell, DotRegistry::shared_defaults())
+                .hit_dps;
+        let extra = attacker.computed_crit_multiplier() - 1.0;
+        let cv = (crit_chance * (1.0 - crit_chance)).sqrt() * extra / (1.0 + crit_chance * extra);
+        let expected = hit_dps * (1.0 - constants.scoring.crit_variance_weight * cv);
+        assert!((attacker.offense_score(&spell, &constants) - expected).abs() < 0.01);
+
+        // Burn adds its DPS on top, without a crit variance penalty
+        let burning = DamagePacketGenerator {
+            status_conversions: SkillStatusConversions {
+                fire_to_burn: 0.5,
+                ..Default::default()
+            },
+            ..spell.clone()
+        };
+        let dot_dps =
+            calculate_skill_dps_breakdown(&attacker, &burning, DotRegistry::shared_defaults())
+                .dot_dps;
+        assert!(dot_dps > 0.0);
+        assert!((attacker.offense_score(&burning, &constants) - (expected + dot_dps)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_defense_score_uses_all_hit_mitigation() {
+        let constants = GameConstants::default();
+        let mut block = StatBlock::new();
+        block.max_life.base = 1000.0;
+        block.armour.base = 1_000_000.0;
+        let capped = block.defense_score(&constants);
+
+        // A lower armour cap lets more physical damage through
+        let mut low_cap = GameConstants::default();
+        low_cap.armour.max_reduction = 50.0;
+        assert!(block.defense_score(&low_cap) < capped);
+
+        // Physical damage reduction and damage taken reduction both count
+        let mut reduced = block.clone();
+        reduced.physical_damage_reduction = 50.0;
+        assert!(reduced.defense_score(&constants) > capped);
+        let mut reduced = block.clone();
+        reduced.damage_taken_reduction = 0.2;
+        assert!(reduced.defense_score(&constants) > capped);
+    }
+
+    #[test]
+    fn test_effective_hp_armour_vs_resistance() {
+        let constants = GameConstants::default();
+        let mut armoured = StatBlock::new();
+        armoured.max_life.base = 1000.0;
+        armoured.armour.base = 5000.0;
+
+        let mut resistant = StatBlock::new();
+        resistant.max_life.base = 1000.0;
+        resistant.fire_resistance.base = 75.0;
+
+        // 75% fire resistance quadruples fire EHP
+        assert!(
+            (resistant.effective_hp(DamageType::Fire, 200.0, &constants) - 4000.0).abs() < 0.01
+        );
+        assert!((armoured.effective_hp(DamageType::Fire, 200.0, &constants) - 1000.0).abs() < 0.01);
+
+        // Armour wins against physical, resistances against fire
+        assert!(
+            armoured.effective_hp(DamageType::Physical, 200.0, &constants)
+                > resistant.effective_hp(DamageType::Physical, 200.0, &constants)
+        );
+        assert!(
+            resistant.effective_hp(DamageType::Fire, 200.0, &constants)
+                > armoured.effective_hp(DamageType::Fire, 200.0, &constants)
+        );
+
+        // Armour is weaker against bigger hits
+        assert!(
+            armoured.effective_hp(DamageType::Physical, 2000.0, &constants)
+                < armoured.effective_hp(DamageType::Physical, 200.0, &constants)
+        );
+
+        // Chaos bypasses energy shield
+        let mut shielded = StatBlock::new();
+        shielded.max_life.base = 1000.0;
+        shielded.max_energy_shield = 500.0;
+        assert!((shielded.effective_hp(DamageType::Fire, 200.0, &constants) - 1500.0).abs() < 0.01);
+        assert!(
+            (shielded.effective_hp(DamageType::Chaos, 200.0, &constants) - 1000.0).abs() < 0.01
+        );
+    }
+
+    #[test]
+    fn test_effective_hp_uses_given_constants() {
+        let mut block = StatBlock::new();
+        block.max_life.base = 1000.0;
+        block.max_energy_shield = 500.0;
+        block.armour.base = 1_000_000.0;
+
+        // Fire bypasses energy shield once configured to
+        let mut constants = GameConstants::default();
+        assert!((block.effective_hp(DamageType::Fire, 200.0, &constants) - 1500.0).abs() < 0.01);
+        constants.energy_shield.bypass.push(DamageType::Fire);
+        assert!((block.effective_hp(DamageType::Fire, 200.0, &constants) - 1000.0).abs() < 0.01);
+
+        // Armour reduction is capped by the configured maximum
+        assert!(
+            (block.effective_hp(DamageType::Physical, 200.0, &constants) - 15000.0).abs() < 0.01
+        );
+        constants.armour.max_reduction = 50.0;
+        assert!(
+            (block.effective_hp(DamageType::Physical, 200.0, &constants) - 3000.0).abs() < 0.01
+        );
+    }
+}
@@ -1,6 +1,7 @@
 //! StatValue - The triple modifier container (Flat → Increased → More)
 
 use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
 
 /// Represents a stat that follows the Flat → Increased → More model
 ///
@@ -11,6 +12,13 @@ use serde::{Deserialize, Serialize};
 /// - `flat`: Sum of all flat additions
 /// - `increased`: Sum of all increased% (as decimal, e.g., 0.40 = 40%)
 /// - `more`: List of more% multipliers (as decimal, each multiplies the result)
+///
+/// An optional `more_cap` clamps the combined more multiplier as a sanity guard,
+/// and an optional `clamp_range` bounds the final value.
+///
+/// The combined more multiplier is memoized, so `compute()` is O(1) however
+/// many more multipliers are stacked. The more list and cap are only changed
+/// through methods, which drop the memoized value.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct StatValue {
     /// Base value (from character/skill)
@@ -20,7 +28,19 @@ pub struct StatValue {
     /// Sum of all increased% (as decimal, e.g., 0.40 = 40%)
     pub increased: f64,
     /// List of more% multipliers (as decimal)
-    pub more: Vec<f64>,
+    more: Vec<f64>,
+    /// Optional upper bound on the combined more multiplier (e.g., 10.0 = at most ×10)
+    #[serde(default)]
+    more_cap: Option<f64>,
+    /// Optional floor on the final value
+    #[serde(default)]
+    pub clamp_min: Option<f64>,
+    /// Optional ceiling on the final value
+    #[serde(default)]
+    pub clamp_max: Option<f64>,
+    /// Memoized `total_more_multiplier`, rebuilt on first use
+    #[serde(skip)]
+    more_multiplier: OnceLock<f64>,
 }
 
 impl StatValue {
@@ -31,15 +51,55 @@ impl StatValue {
             flat: 0.0,
             increased: 0.0,
             more: Vec::new(),
+            more_cap: None,
+            clamp_min: None,
+            clamp_max: None,
+            more_multiplier: OnceLock::new(),
         }
     }
 
-    /// Calculate final value: (base + flat) × (1 + increased) × Π(1 + more)
+    /// Set an upper bound on the combined more multiplier
+    pub fn with_more_cap(mut self, cap: f64) -> Self {
+        self.set_more_cap(Some(cap));
+        self
+    }
+
+    /// Set or remove the upper bound on the combined more multiplier
+    pub fn set_more_cap(&mut self, cap: Option<f64>) {
+        self.more_cap = cap;
+        self.more_multiplier = OnceLock::new();
+    }
+
+    /// Get the upper bound on the combined more multiplier, if any
+    pub fn more_cap(&self) -> Option<f64> {
+        self.more_cap
+    }
+
+    /// Get the more% multipliers applied (as decimals)
+    pub fn more(&self) -> &[f64] {
+        &self.more
+    }
+
+    /// Bound the final value to `[min, max]` (either side optional)
+    pub fn clamp_range(mut self, min: Option<f64>, max: Option<f64>) -> Self {
+        self.clamp_min = min;
+        self.clamp_max = max;
+        self
+    }
+
+    /// Calculate final value: (base + flat) × (1 + increased) × Π(1 + more),
+    /// clamped to `clamp_range` if set
     pub fn compute(&self) -> f64 {
         let base_total = self.base + self.flat;
         let increased_mult = 1.0 + self.increased;
-        let more_mult: f64 = self.more.iter().map(|m| 1.0 + m).product();
-        base_total * increased_mult * more_mult
+        let mut value = base_total * increased_mult * self.total_more_multiplier();
+        if let Some(min) = self.clamp_min {
+            value = value.max(min);
+        }
+        if let Some(max) = self.clamp_max {
+            value = value.min(max);
+        }
+        value
     }
 
     /// Add a flat bonus
@@ -55,6 +115,7 @@ impl StatValue {
     /// Add a more% multiplier (as decimal, e.g., 0.20 for 20% more)
     pub fn add_more(&mut self, value: f64) {
         self.more.push(value);
+        self.more_multiplier = OnceLock::new();
     }
 
     /// Reset to just the base value
@@ -62,6 +123,7 @@ impl StatValue {
         self.flat = 0.0;
         self.increased = 0.0;
         self.more.clear();
+        self.more_multiplier = OnceLock::new();
     }
 
     /// Get the total flat value (base + flat additions)
@@ -74,9 +136,20 @@ impl StatValue {
         1.0 + self.increased
     }
 
-    /// Get the total more multiplier (product of all more multipliers)
+    /// Get the total more multiplier (product of all more multipliers, capped if set)
     pub fn total_more_multiplier(&self) -> f64 {
-        self.more.iter().map(|m| 1.0 + m).product()
+        *self.more_multiplier.get_or_init(|| {
+            let product: f64 = self.more.iter().map(|m| 1.0 + m).product();
+            match self.more_cap {
+                Some(cap) => product.min(cap),
+                None => product,
+            }
+        })
+    }
+
+    /// Get the number of more multipliers applied (for debugging large stacks)
+    pub fn more_multiplier_count(&self) -> usize {
+        self.more.len()
     }
 }
 
@@ -147,6 +220,79 @@ mod tests {
         assert!((stat.compute() - 156.0).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_more_cap_clamps_extreme_stack() {
+        let mut stat = StatValue::with_base(100.0).with_more_cap(10.0);
+        for _ in 0..50 {
+            stat.add_more(0.50);
+        }
+        assert_eq!(stat.more_multiplier_count(), 50);
+        // 1.5^50 would be astronomically large; capped at ×10
+        assert!((stat.total_more_multiplier() - 10.0).abs() < f64::EPSILON);
+        assert!((stat.compute() - 1000.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_clamp_range_bounds_both_sides() {
+        let mut stat = StatValue::with_base(1.0).clamp_range(Some(0.1), Some(2.0));
+
+        stat.add_increased(-0.95);
+        assert!((stat.compute() - 0.1).abs() < f64::EPSILON);
+
+        stat.reset_to_base();
+        stat.add_more(2.0);
+        assert!((stat.compute() - 2.0).abs() < f64::EPSILON);
+
+        // Inside the range the value is untouched, and the range survives a reset
+        stat.reset_to_base();
+        stat.add_increased(0.5);
+        assert!((stat.compute() - 1.5).abs() < f64::EPSILON);
+        assert_eq!(stat.clamp_min, Some(0.1));
+        assert_eq!(stat.clamp_max, Some(2.0));
+    }
+
+    #[test]
+    fn test_mutations_are_picked_up() {
+        let mut stat = StatValue::with_base(100.0);
+        stat.add_more(0.50);
+        assert!((stat.compute() - 150.0).abs() < f64::EPSILON);
+        assert!((stat.compute() - 150.0).abs() < f64::EPSILON);
+
+        stat.add_more(1.0);
+        assert!((stat.compute() - 300.0).abs() < f64::EPSILON);
+
+        stat.add_flat(100.0);
+        stat.add_increased(0.50);
+        assert!((stat.compute() - 900.0).abs() < f64::EPSILON);
+
+        // Direct writes to base, flat and increased are picked up
+        stat.base = 0.0;
+        assert!((stat.compute() - 450.0).abs() < f64::EPSILON);
+        stat.flat = 200.0;
+        stat.increased = 1.0;
+        assert!((stat.compute() - 1200.0).abs() < f64::EPSILON);
+        stat.flat = 100.0;
+        stat.increased = 0.5;
+
+        // Changing the cap drops the memoized more multiplier
+        stat.set_more_cap(Some(2.0));
+        assert!((stat.compute() - 300.0).abs() < f64::EPSILON);
+        stat.set_more_cap(None);
+        assert!((stat.compute() - 450.0).abs() < f64::EPSILON);
+        assert_eq!(stat.more(), &[0.5, 1.0]);
+
+        // Clones are independent
+        let mut copy = stat.clone();
+        copy.reset_to_base();
+        assert!(copy.compute().abs() < f64::EPSILON);
+        assert!((stat.compute() - 450.0).abs() < f64::EPSILON);
+
+        // Deserialized values compute the same
+        let json = serde_json::to_string(&stat).unwrap();
+        let restored: StatValue = serde_json::from_str(&json).unwrap();
+        assert!((restored.compute() - 450.0).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn test_reset_to_base() {
         let mut stat = StatValue::with_base(100.0);
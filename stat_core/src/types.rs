@@ -1,7 +1,9 @@
 //! Core types specific to stat_manager
 
-use loot_core::types::StatusEffect;
+use loot_core::types::{DamageType, ItemClass, StatusEffect};
+use loot_core::Item;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 // ============================================================================
 // Unified Effect System
@@ -176,7 +178,7 @@ impl Effect {
     /// - Duration: 5.0s
     /// - Tick rate: 1.0s
     /// - Stacking: Limited (50% effectiveness for additional stacks)
-    /// - Note: Deals 2x damage while target is moving (handled externally)
+    /// - Note: Deals 2x damage while the target is moving (`StatBlock::is_moving`)
     pub fn bleed(dot_dps: f64, source_id: impl Into<String>) -> Self {
         let mut effect = Self::new_ailment(
             "bleed", "Bleed", StatusEffect::Bleed,
@@ -266,6 +268,32 @@ impl Effect {
         )
     }
 
+    /// Create the ailment effect for a status type with a custom duration
+    ///
+    /// `magnitude` is used by non-damaging ailments and `dot_dps` by
+    /// damaging ones (Poison, Bleed, Burn).
+    pub fn from_status(
+        status: StatusEffect,
+        duration: f64,
+        magnitude: f64,
+        dot_dps: f64,
+        source_id: impl Into<String>,
+    ) -> Self {
+        let mut effect = match status {
+            StatusEffect::Poison => Self::poison(dot_dps, source_id),
+            StatusEffect::Bleed => Self::bleed(dot_dps, source_id),
+            StatusEffect::Burn => Self::burn(dot_dps, source_id),
+            StatusEffect::Freeze => Self::freeze(magnitude, source_id),
+            StatusEffect::Chill => Self::chill(magnitude, source_id),
+            StatusEffect::Static => Self::shock(magnitude, source_id),
+            StatusEffect::Fear => Self::fear(magnitude, source_id),
+            StatusEffect::Slow => Self::slow(magnitude, source_id),
+        };
+        effect.duration_remaining = duration;
+        effect.total_duration = duration;
+        effect
+    }
+
     /// Get the base duration for a status effect type
     pub fn base_duration_for(status: StatusEffect) -> f64 {
         match status {
@@ -306,6 +334,17 @@ impl Effect {
         matches!(self.effect_type, EffectType::Ailment { .. })
     }
 
+    /// Check if this is a stat modifier debuff
+    pub fn is_debuff(&self) -> bool {
+        matches!(
+            self.effect_type,
+            EffectType::StatModifier {
+                is_debuff: true,
+                ..
+            }
+        )
+    }
+
     /// Check if this ailment deals DoT damage
     pub fn is_damaging(&self) -> bool {
         match &self.effect_type {
@@ -325,9 +364,23 @@ impl Effect {
     /// Get DPS for this effect (0 if not a damaging ailment)
     pub fn dps(&self) -> f64 {
         match &self.effect_type {
-            EffectType::Ailment { dot_dps, effectiveness, .. } => {
-                dot_dps * self.stacks as f64 * effectiveness
-            }
+            EffectType::Ailment {
+                dot_dps,
+                effectiveness,
+                ..
+            } => dot_dps * self.stacks as f64 * effectiveness,
+            _ => 0.0,
+        }
+    }
+
+    /// Get the magnitude of this effect across its stacks (0 if not an ailment)
+    pub fn total_magnitude(&self) -> f64 {
+        match &self.effect_type {
+            EffectType::Ailment {
+                magnitude,
+                effectiveness,
+                ..
+            } => magnitude * self.stacks as f64 * effectiveness,
             _ => 0.0,
         }
     }
@@ -420,6 +473,323 @@ impl EquipmentSlot {
     }
 }
 
+/// Check if an item class occupies both hands
+///
+/// A two-handed weapon in the main hand leaves the off hand empty.
+pub fn is_two_handed(class: &ItemClass) -> bool {
+    matches!(
+        class,
+        ItemClass::TwoHandSword
+            | ItemClass::TwoHandAxe
+            | ItemClass::TwoHandMace
+            | ItemClass::Bow
+            | ItemClass::Staff
+    )
+}
+
+/// Resistance reduction per damage type (in resistance points, 20.0 = -20%)
+///
+/// Unlike penetration, applied reduction lowers the target's actual resistance,
+/// so it also affects later ailment and DoT calculations against that target
+/// until it wears off (`ResistanceConstants::reduction_duration`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResistanceReduction {
+    #[serde(default)]
+    pub fire: f64,
+    #[serde(default)]
+    pub cold: f64,
+    #[serde(default)]
+    pub lightning: f64,
+    #[serde(default)]
+    pub chaos: f64,
+}
+
+impl ResistanceReduction {
+    /// Get the reduction for a damage type (physical has no resistance)
+    pub fn get(&self, damage_type: DamageType) -> f64 {
+        match damage_type {
+            DamageType::Physical => 0.0,
+            DamageType::Fire => self.fire,
+            DamageType::Cold => self.cold,
+            DamageType::Lightning => self.lightning,
+            DamageType::Chaos => self.chaos,
+        }
+    }
+
+    /// Add another reduction to this one
+    pub fn add(&mut self, other: &ResistanceReduction) {
+        self.fire += other.fire;
+        self.cold += other.cold;
+        self.lightning += other.lightning;
+        self.chaos += other.chaos;
+    }
+
+    /// Keep the strongest reduction per type (reductions from the same kind of source don't stack)
+    pub fn max_with(&mut self, other: &ResistanceReduction) {
+        self.fire = self.fire.max(other.fire);
+        self.cold = self.cold.max(other.cold);
+        self.lightning = self.lightning.max(other.lightning);
+        self.chaos = self.chaos.max(other.chaos);
+    }
+
+    /// Check if any reduction is present
+    pub fn is_empty(&self) -> bool {
+        self.fire == 0.0 && self.cold == 0.0 && self.lightning == 0.0 && self.chaos == 0.0
+    }
+}
+
+/// Flat resistance penetration per damage type (in resistance points)
+///
+/// Physical has no resistance and no penetration; see overwhelm instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Penetration {
+    #[serde(default)]
+    pub fire: f64,
+    #[serde(default)]
+    pub cold: f64,
+    #[serde(default)]
+    pub lightning: f64,
+    #[serde(default)]
+    pub chaos: f64,
+}
+
+impl Penetration {
+    /// Get the penetration for a damage type (physical has none)
+    pub fn get(&self, damage_type: DamageType) -> f64 {
+        match damage_type {
+            DamageType::Physical => 0.0,
+            DamageType::Fire => self.fire,
+            DamageType::Cold => self.cold,
+            DamageType::Lightning => self.lightning,
+            DamageType::Chaos => self.chaos,
+        }
+    }
+
+    /// Check if any penetration is present
+    pub fn is_empty(&self) -> bool {
+        self.fire == 0.0 && self.cold == 0.0 && self.lightning == 0.0 && self.chaos == 0.0
+    }
+}
+
+/// Ailments an entity cannot be afflicted by
+///
+/// Immunities from several sources combine: immune from any source is immune.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AilmentImmunities {
+    #[serde(default)]
+    pub poison: bool,
+    #[serde(default)]
+    pub bleed: bool,
+    #[serde(default)]
+    pub burn: bool,
+    #[serde(default)]
+    pub freeze: bool,
+    #[serde(default)]
+    pub chill: bool,
+    #[serde(default)]
+    pub static_effect: bool,
+    #[serde(default)]
+    pub fear: bool,
+    #[serde(default)]
+    pub slow: bool,
+}
+
+impl AilmentImmunities {
+    /// Check if this grants immunity to a status effect
+    pub fn is_immune(&self, status: StatusEffect) -> bool {
+        match status {
+            StatusEffect::Poison => self.poison,
+            StatusEffect::Bleed => self.bleed,
+            StatusEffect::Burn => self.burn,
+            StatusEffect::Freeze => self.freeze,
+            StatusEffect::Chill => self.chill,
+            StatusEffect::Static => self.static_effect,
+            StatusEffect::Fear => self.fear,
+            StatusEffect::Slow => self.slow,
+        }
+    }
+
+    /// Grant immunity to a status effect
+    pub fn grant(&mut self, status: StatusEffect) {
+        let flag = match status {
+            StatusEffect::Poison => &mut self.poison,
+            StatusEffect::Bleed => &mut self.bleed,
+            StatusEffect::Burn => &mut self.burn,
+            StatusEffect::Freeze => &mut self.freeze,
+            StatusEffect::Chill => &mut self.chill,
+            StatusEffect::Static => &mut self.static_effect,
+            StatusEffect::Fear => &mut self.fear,
+            StatusEffect::Slow => &mut self.slow,
+        };
+        *flag = true;
+    }
+
+    /// Combine with another set of immunities
+    pub fn merge(&mut self, other: &AilmentImmunities) {
+        self.poison |= other.poison;
+        self.bleed |= other.bleed;
+        self.burn |= other.burn;
+        self.freeze |= other.freeze;
+        self.chill |= other.chill;
+        self.static_effect |= other.static_effect;
+        self.fear |= other.fear;
+        self.slow |= other.slow;
+    }
+}
+
+/// How unmet item attribute requirements are handled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequirementMode {
+    /// Requirements are not enforced
+    #[default]
+    Ignore,
+    /// Items below their requirements contribute proportionally less
+    Penalty,
+}
+
+/// How an entity's evasion protects it from hits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvasionMode {
+    /// Evasion caps the damage of each hit (one-shot protection)
+    #[default]
+    DamageCap,
+    /// Evasion gives a chance to evade hits entirely
+    Chance,
+}
+
+/// An attribute requirement the entity does not meet
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequirementShortfall {
+    /// Short attribute label ("Str", "Dex", "Int")
+    pub attribute: &'static str,
+    /// Value the item requires
+    pub required: f64,
+    /// Value the entity currently has
+    pub current: f64,
+}
+
+impl RequirementShortfall {
+    /// How many points are missing
+    pub fn missing(&self) -> f64 {
+        (self.required - self.current).max(0.0)
+    }
+}
+
+/// Error returned by `StatBlock::try_equip`
+///
+/// Both variants hand back the item that could not be equipped.
+#[derive(Error, Debug, Clone)]
+pub enum EquipError {
+    /// One or more attribute requirements are not met
+    #[error("Requires {}", format_shortfalls(.0))]
+    RequirementsNotMet(Vec<RequirementShortfall>, Box<Item>),
+    /// The slot cannot be used with the current equipment
+    #[error("{0:?} is occupied by a two-handed weapon")]
+    SlotBlocked(EquipmentSlot, Box<Item>),
+}
+
+impl EquipError {
+    /// Take back the item that could not be equipped
+    pub fn into_item(self) -> Item {
+        match self {
+            EquipError::RequirementsNotMet(_, item) | EquipError::SlotBlocked(_, item) => *item,
+        }
+    }
+}
+
+/// Error returned by `StatBlock::can_use_skill`
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum SkillError {
+    /// An attack skill was used with no main-hand weapon
+    #[error("Requires a weapon")]
+    NoWeapon,
+    /// The main-hand weapon is not one of the skill's required classes
+    #[error("Requires {}", format_weapon_classes(.0))]
+    WrongWeapon(Vec<ItemClass>),
+}
+
+fn format_weapon_classes(classes: &[ItemClass]) -> String {
+    classes
+        .iter()
+        .map(|class| format!("{:?}", class))
+        .collect::<Vec<_>>()
+        .join(" or ")
+}
+
+fn format_shortfalls(shortfalls: &[RequirementShortfall]) -> String {
+    shortfalls
+        .iter()
+        .map(|s| format!("{} {}", s.required, s.attribute))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Every damage type, in display order
+///
+/// Iterate this rather than hand-writing the five variants.
+pub const ALL_DAMAGE_TYPES: [DamageType; 5] = [
+    DamageType::Physical,
+    DamageType::Fire,
+    DamageType::Cold,
+    DamageType::Lightning,
+    DamageType::Chaos,
+];
+
+/// A pool that absorbs incoming hit damage after mitigation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DamagePool {
+    /// Temporary absorption from guard skills
+    Guard,
+    /// Ward barrier
+    Ward,
+    /// Energy shield
+    EnergyShield,
+    /// Life (absorbs everything that reaches it)
+    Life,
+}
+
+impl DamagePool {
+    /// Default absorption order: guard → ward → energy shield → life
+    pub fn default_order() -> Vec<DamagePool> {
+        vec![
+            DamagePool::Guard,
+            DamagePool::Ward,
+            DamagePool::EnergyShield,
+            DamagePool::Life,
+        ]
+    }
+}
+
+/// A resource with a current and maximum value, for generic UI bars
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceKind {
+    Life,
+    Mana,
+    EnergyShield,
+}
+
+impl ResourceKind {
+    /// All resources in display order
+    pub const ALL: [ResourceKind; 3] = [
+        ResourceKind::Life,
+        ResourceKind::Mana,
+        ResourceKind::EnergyShield,
+    ];
+
+    /// Display name
+    pub fn name(self) -> &'static str {
+        match self {
+            ResourceKind::Life => "Life",
+            ResourceKind::Mana => "Mana",
+            ResourceKind::EnergyShield => "Energy Shield",
+        }
+    }
+}
+
 /// Skill tags for damage scaling and categorization
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]